@@ -4,25 +4,47 @@
 //!   1. builds the parser's accepted set (statements it parses in that dialect),
 //!   2. times each accepted statement individually to produce a per-statement
 //!      time distribution, and
-//!   3. records the Display round-trip rate among accepted statements.
+//!   3. records the Display round-trip rate among accepted statements, and
+//!   4. times the parser's standalone lexer on each accepted statement, where
+//!      it has one (the lex-time axis), and
+//!   5. times the reparse of a sample of accepted statements after each of the
+//!      deterministic single-token edits in `edit` (the edit-reparse axis),
+//!      through the incremental API where the parser has one.
 //!
 //! Timing uses `parse_once` (no `catch_unwind`) for overhead-free, fair
 //! measurement. Accepted statements are known not to panic.
 //!
 //! Outputs (under `target/bench_dist/`), consumed by `sqlbench export` to build
 //! `web/assets/bench.json.zst` for the explorer:
-//!   - `{dialect}__{parser}.txt` : raw per-statement times (ns, one per line),
-//!     downsampled into the eCDF curves without re-running the benchmark.
-//!   - `summary.csv`             : per-pair percentiles + round-trip rate.
+//!   - `{dialect}__{parser}.txt` : raw per-statement times (ns), one line per
+//!     statement with its byte length and token count (`ns\tbytes\ttokens`),
+//!     downsampled into the eCDF curves and the size-scaling fits without
+//!     re-running the benchmark.
+//!   - `{dialect}__{parser}.kind.{kind}.txt` : the same parse times split by
+//!     statement kind (`stmt_kind`), one file per kind.
+//!   - `{dialect}__{parser}.lex.txt` : raw per-statement lex-only times (ns).
+//!   - `{dialect}__{parser}.edit.{kind}.txt` : raw edit-reparse times (ns), one
+//!     file per edit kind.
+//!   - `summary.csv`             : per-pair percentiles + round-trip rate +
+//!     median lex and edit-reparse times.
+//!
+//! A common-subset mode (`-- --common`) instead times every parser on only the
+//! statements all of a dialect's parsers accept (`common`), writing
+//! `{dialect}__{parser}.common.txt` and leaving the files above alone.
 //!
 //! Full benchmark (long, intended for a dedicated run):  cargo bench
+//! Common-subset timings:                                cargo bench --bench parsing -- --common
 //! Quick smoke check (pre-commit hook, and `cargo test`): cargo bench -- --test
 //!
 //! The full run unpacks `datasets.tar.zst` automatically if `datasets/` is
 //! missing. The smoke path needs no corpus, so `cargo test` stays fast.
 
+use sql_ast_benchmark::common::{common_subset, participants};
 use sql_ast_benchmark::datasets::Dialect;
+use sql_ast_benchmark::edit::{edits, EditKind};
+use sql_ast_benchmark::reduce::lex;
 use sql_ast_benchmark::stats::{quantile, slug};
+use sql_ast_benchmark::stmt_kind::{classify, StatementKind};
 use sql_ast_benchmark::BenchParser;
 use std::fmt::Write as _;
 use std::fs;
@@ -37,6 +59,9 @@ const WORKER_STACK: usize = 1024 * 1024 * 1024;
 
 const OUT_DIR: &str = "target/bench_dist";
 
+/// Accepted statements per pair the edit-reparse axis samples, evenly spread.
+const EDIT_SAMPLE: usize = 1000;
+
 const DIALECTS: &[Dialect] = &[
     Dialect::Postgresql,
     Dialect::Sqlite,
@@ -119,6 +144,12 @@ struct Row {
     /// Display round-trip rate (%) among accepted statements, or -1 if the
     /// parser has no pretty-printer in this dialect (N/A).
     roundtrip_pct: f64,
+    /// Median lex-only time (ns) per accepted statement, or -1 if the parser
+    /// has no standalone lexer.
+    lex_median: f64,
+    /// Median reparse time (ns) after a single-token edit, over every edit
+    /// kind, or -1 if no sampled statement admitted an edit.
+    edit_median: f64,
 }
 
 /// Time one (parser, dialect) pair: accepted set, per-statement distribution
@@ -145,6 +176,8 @@ fn run_pair(parser: BenchParser, dialect: Dialect, stmts: &[String]) -> Row {
         max: 0.0,
         mean: 0.0,
         roundtrip_pct: -1.0,
+        lex_median: -1.0,
+        edit_median: -1.0,
     };
     if accepted.is_empty() {
         return row;
@@ -175,18 +208,52 @@ fn run_pair(parser: BenchParser, dialect: Dialect, stmts: &[String]) -> Row {
         times.push(time_stmt(|| parser.parse_once(s, dialect)));
     }
 
-    // Persist raw times for the JSON export (eCDF downsampling).
-    let raw_path = format!(
-        "{OUT_DIR}/{}__{}.txt",
-        dialect.dir_name(),
-        slug(parser.name())
+    // Persist raw times for the JSON export (eCDF downsampling), each next to
+    // its statement's size so the export can normalize by it.
+    write_sized_times(
+        &format!("{}__{}.txt", dialect.dir_name(), slug(parser.name())),
+        &accepted,
+        &times,
     );
-    if let Ok(mut file) = fs::File::create(&raw_path) {
-        let mut buf = String::with_capacity(times.len() * 8);
-        for t in &times {
-            let _ = writeln!(buf, "{t:.1}");
+    write_kind_times(parser, dialect, &accepted, &times);
+
+    if let Some(median) = time_lex(parser, dialect, &accepted) {
+        row.lex_median = median;
+    }
+
+    // Edit-reparse axis. Preparing the edit (and, for an incremental parser,
+    // the old tree) is untimed: an editor already holds both on a keystroke.
+    // `edit_reparse` drops an edit the parser rejects, so only clean reparses
+    // are timed.
+    let stride = accepted.len().div_ceil(EDIT_SAMPLE);
+    let mut all_edits: Vec<f64> = Vec::new();
+    let mut by_kind: Vec<(EditKind, Vec<f64>)> =
+        EditKind::ALL.iter().map(|&k| (k, Vec::new())).collect();
+    for s in accepted.iter().step_by(stride) {
+        for e in edits(s) {
+            if let Some(mut r) = parser.edit_reparse(s, &e, dialect) {
+                let t = time_stmt(|| r.run());
+                all_edits.push(t);
+                if let Some((_, times)) = by_kind.iter_mut().find(|(k, _)| *k == e.kind) {
+                    times.push(t);
+                }
+            }
         }
-        let _ = file.write_all(buf.as_bytes());
+    }
+    for (kind, times) in &by_kind {
+        write_times(
+            &format!(
+                "{}__{}.edit.{}.txt",
+                dialect.dir_name(),
+                slug(parser.name()),
+                kind.name()
+            ),
+            times,
+        );
+    }
+    if !all_edits.is_empty() {
+        all_edits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        row.edit_median = quantile(&all_edits, 0.50);
     }
 
     // Distribution stats.
@@ -204,6 +271,126 @@ fn run_pair(parser: BenchParser, dialect: Dialect, stmts: &[String]) -> Row {
     row
 }
 
+/// Split the per-statement parse times by statement kind, one file per kind,
+/// for the per-kind speed breakdown.
+fn write_kind_times(parser: BenchParser, dialect: Dialect, accepted: &[&str], times: &[f64]) {
+    let mut by_kind: Vec<Vec<f64>> = vec![Vec::new(); StatementKind::ALL.len()];
+    for (s, &t) in accepted.iter().zip(times) {
+        by_kind[classify(s).index()].push(t);
+    }
+    for (kind, times) in StatementKind::ALL.iter().zip(&by_kind) {
+        write_times(
+            &format!(
+                "{}__{}.kind.{}.txt",
+                dialect.dir_name(),
+                slug(parser.name()),
+                kind.name()
+            ),
+            times,
+        );
+    }
+}
+
+/// Lex-time axis: the lexer alone, over the same statements as the parse
+/// timing, so the parse time can be split into lexing and parsing. Writes the
+/// raw times and returns their median, or `None` without a standalone lexer.
+fn time_lex(parser: BenchParser, dialect: Dialect, accepted: &[&str]) -> Option<f64> {
+    parser.tokenize(accepted.first()?, dialect)?;
+    let mut lex: Vec<f64> = accepted
+        .iter()
+        .map(|s| time_stmt(|| parser.tokenize(s, dialect).is_some()))
+        .collect();
+    write_times(
+        &format!("{}__{}.lex.txt", dialect.dir_name(), slug(parser.name())),
+        &lex,
+    );
+    lex.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Some(quantile(&lex, 0.50))
+}
+
+/// The lex and edit-reparse medians for the console line, skipping the ones
+/// the parser has none of.
+fn optional_medians(row: &Row) -> String {
+    let mut out = String::new();
+    if row.lex_median >= 0.0 {
+        let _ = write!(out, " lex={:.0}ns", row.lex_median);
+    }
+    if row.edit_median >= 0.0 {
+        let _ = write!(out, " edit={:.0}ns", row.edit_median);
+    }
+    out
+}
+
+/// Write raw ns timings, one per line, to `file` under [`OUT_DIR`].
+fn write_times(file: &str, times: &[f64]) {
+    if let Ok(mut f) = fs::File::create(format!("{OUT_DIR}/{file}")) {
+        let mut buf = String::with_capacity(times.len() * 8);
+        for t in times {
+            let _ = writeln!(buf, "{t:.1}");
+        }
+        let _ = f.write_all(buf.as_bytes());
+    }
+}
+
+/// Write raw ns timings with each statement's byte length and token count
+/// (from the shared `reduce::lex` lexer, so every parser is measured against
+/// the same yardstick), tab-separated, one statement per line.
+fn write_sized_times(file: &str, stmts: &[&str], times: &[f64]) {
+    if let Ok(mut f) = fs::File::create(format!("{OUT_DIR}/{file}")) {
+        let mut buf = String::with_capacity(times.len() * 16);
+        for (s, t) in stmts.iter().zip(times) {
+            let _ = writeln!(buf, "{t:.1}\t{}\t{}", s.len(), lex(s).len());
+        }
+        let _ = f.write_all(buf.as_bytes());
+    }
+}
+
+/// Common-subset mode: per dialect, time every participating parser on only
+/// the statements all of them accept, so the distributions compare like with
+/// like. Writes `{dialect}__{parser}.common.txt` under [`OUT_DIR`].
+fn run_common() {
+    // One large-stack worker for the whole pass, for the same reason as the
+    // per-pair workers of the full run.
+    std::thread::Builder::new()
+        .stack_size(WORKER_STACK)
+        .spawn(common_pass)
+        .expect("spawn worker")
+        .join()
+        .expect("common-subset pass panicked");
+}
+
+fn common_pass() {
+    for &dialect in DIALECTS {
+        let stmts = load_dialect(dialect);
+        let parsers = participants(dialect);
+        let subset = common_subset(&stmts, dialect, &parsers);
+        if subset.is_empty() {
+            continue;
+        }
+        for &parser in &parsers {
+            let job_start = Instant::now();
+            let mut times: Vec<f64> = subset
+                .iter()
+                .map(|s| time_stmt(|| parser.parse_once(s, dialect)))
+                .collect();
+            write_times(
+                &format!("{}__{}.common.txt", dialect.dir_name(), slug(parser.name())),
+                &times,
+            );
+            times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            println!(
+                "{:<11} {:<24} common n={:>6}/{:<6} median={:>8.0}ns  ({:.1}s)",
+                dialect.dir_name(),
+                parser.name(),
+                subset.len(),
+                stmts.len(),
+                quantile(&times, 0.50),
+                job_start.elapsed().as_secs_f64(),
+            );
+        }
+    }
+}
+
 /// Quick smoke check used by the pre-commit hook: every parser parses one of
 /// its accepted statements per dialect without panicking. Fast.
 fn smoke() {
@@ -252,10 +439,15 @@ fn main() {
     }
     fs::create_dir_all(OUT_DIR).expect("create out dir");
 
+    if args.iter().any(|a| a == "--common") {
+        run_common();
+        return;
+    }
+
     let mut summary = fs::File::create(format!("{OUT_DIR}/summary.csv")).expect("summary.csv");
     writeln!(
         summary,
-        "dialect,parser,n_total,n_accepted,min_ns,p10_ns,p25_ns,median_ns,p75_ns,p90_ns,p99_ns,max_ns,mean_ns,roundtrip_pct"
+        "dialect,parser,n_total,n_accepted,min_ns,p10_ns,p25_ns,median_ns,p75_ns,p90_ns,p99_ns,max_ns,mean_ns,roundtrip_pct,lex_median_ns,edit_median_ns"
     )
     .unwrap();
 
@@ -293,7 +485,7 @@ fn main() {
 
             writeln!(
                 summary,
-                "{},{},{},{},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1}",
+                "{},{},{},{},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1}",
                 row.dialect,
                 row.parser,
                 row.n_total,
@@ -308,6 +500,8 @@ fn main() {
                 row.max,
                 row.mean,
                 row.roundtrip_pct,
+                row.lex_median,
+                row.edit_median,
             )
             .unwrap();
             summary.flush().unwrap();
@@ -317,8 +511,9 @@ fn main() {
             } else {
                 format!("{:>4.0}%", row.roundtrip_pct)
             };
+            let extra = optional_medians(&row);
             println!(
-                "{:<11} {:<24} n={:>6}/{:<6} median={:>8.0}ns p90={:>9.0}ns rt={}  ({:.1}s)",
+                "{:<11} {:<24} n={:>6}/{:<6} median={:>8.0}ns p90={:>9.0}ns rt={}{}  ({:.1}s)",
                 row.dialect,
                 row.parser,
                 row.n_accepted,
@@ -326,6 +521,7 @@ fn main() {
                 row.median,
                 row.p90,
                 rt,
+                extra,
                 job_start.elapsed().as_secs_f64(),
             );
         }
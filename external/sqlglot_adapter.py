@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+"""Python sqlglot behind the sqlbench subprocess protocol (see src/subprocess.rs).
+
+One JSON request per stdin line, one JSON response per stdout line.
+"""
+
+import json
+import sys
+
+import sqlglot
+from sqlglot.errors import ParseError
+
+DIALECTS = {
+    "postgresql": "postgres",
+    "mysql": "mysql",
+    "sqlite": "sqlite",
+    "clickhouse": "clickhouse",
+    "duckdb": "duckdb",
+    "hive": "hive",
+    "spark_sql": "spark",
+    "trino": "trino",
+    "tsql": "tsql",
+    "oracle": "oracle",
+    "bigquery": "bigquery",
+    "redshift": "redshift",
+}
+
+
+def handle(req):
+    dialect = DIALECTS.get(req["dialect"])
+    if dialect is None:
+        return {"outcome": "unsupported"}
+    try:
+        trees = [t for t in sqlglot.parse(req["sql"], read=dialect) if t is not None]
+    except ParseError as e:
+        err = e.errors[0] if e.errors else {}
+        out = {"outcome": "rejected", "error": str(e).splitlines()[0]}
+        if err.get("line") and err.get("col"):
+            out["line"] = err["line"]
+            out["column"] = err["col"]
+        return out
+    except Exception as e:  # an internal crash, not a rejection
+        return {"outcome": "panicked", "error": repr(e)}
+    if req["op"] == "reprint":
+        return {"outcome": "accepted", "sql": "; ".join(t.sql(dialect=dialect) for t in trees)}
+    if req["op"] == "parse_batch":
+        return {"outcome": "accepted", "statements": len(trees)}
+    return {"outcome": "accepted"}
+
+
+for line in sys.stdin:
+    sys.stdout.write(json.dumps(handle(json.loads(line))) + "\n")
+    sys.stdout.flush()
@@ -9,7 +9,9 @@
 //! Measurement is single-threaded by design: the allocator counters are
 //! process-wide, so concurrent allocations from other threads would corrupt a
 //! window. The libpg_query bindings parse in C and report `None` (their memory
-//! is invisible to the Rust allocator).
+//! is invisible to the Rust allocator). The tree-sitter runtime is C too, but
+//! it takes pluggable allocation functions, so it is pointed at the counting
+//! allocator and measured like the Rust parsers.
 //!
 //! A `batch` subcommand measures whole-script memory instead: per (parser,
 //! dialect) it concatenates the accepted set into one script, parses it holding
@@ -17,16 +19,24 @@
 //! `target/batch_mem_dist/summary.csv`. Databend has no batch entry point and
 //! is skipped there.
 //!
+//! A `common` subcommand measures per statement again, but only over the
+//! statements every parser supporting the dialect accepts
+//! (`sql_ast_benchmark::common`), writing `{dialect}__{parser}.common.peak.txt`
+//! and `.common.retained.txt` for the export's head-to-head memory section.
+//!
 //! Run locally: `cargo run --release -p membench`            (per-statement)
 //!              `cargo run --release -p membench -- batch`    (whole-script)
+//!              `cargo run --release -p membench -- common`   (common subset)
 
 use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
 use std::fmt::Write as _;
 use std::fs;
 use std::io::Write as _;
 use std::path::Path;
 
 use sql_ast_benchmark::batch::{batch_eligible, evaluate_batches, reports_statement_count};
+use sql_ast_benchmark::common::{common_subset, participants};
 use sql_ast_benchmark::datasets::{ensure_corpus, Dialect};
 use sql_ast_benchmark::stats::slug;
 use sql_ast_benchmark::BenchParser;
@@ -66,6 +76,57 @@ unsafe impl GlobalAlloc for Counting {
 #[global_allocator]
 static GLOBAL: Counting = Counting;
 
+/// tree-sitter's C runtime allocates through the functions below, which route
+/// to the (counting) Rust global allocator. A C `free` gets no size, so each
+/// block starts with a header recording it, from which the layout is rebuilt.
+const TS_HEADER: usize = 16;
+
+fn ts_layout(size: usize) -> Layout {
+    Layout::from_size_align(size + TS_HEADER, TS_HEADER).expect("allocation size overflows")
+}
+
+/// Record `size` in the header at `base` and return the block past it.
+unsafe fn ts_block(base: *mut u8, size: usize) -> *mut c_void {
+    if base.is_null() {
+        return base.cast();
+    }
+    base.cast::<usize>().write(size);
+    base.add(TS_HEADER).cast()
+}
+
+/// The header start and recorded size of a block handed out by `ts_block`.
+unsafe fn ts_header(ptr: *mut c_void) -> (*mut u8, usize) {
+    let base = ptr.cast::<u8>().sub(TS_HEADER);
+    (base, base.cast::<usize>().read())
+}
+
+unsafe extern "C" fn ts_malloc(size: usize) -> *mut c_void {
+    ts_block(std::alloc::alloc(ts_layout(size)), size)
+}
+
+unsafe extern "C" fn ts_calloc(nmemb: usize, size: usize) -> *mut c_void {
+    let size = nmemb.saturating_mul(size);
+    ts_block(std::alloc::alloc_zeroed(ts_layout(size)), size)
+}
+
+unsafe extern "C" fn ts_realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    if ptr.is_null() {
+        return ts_malloc(size);
+    }
+    let (base, old) = ts_header(ptr);
+    ts_block(
+        std::alloc::realloc(base, ts_layout(old), size + TS_HEADER),
+        size,
+    )
+}
+
+unsafe extern "C" fn ts_free(ptr: *mut c_void) {
+    if !ptr.is_null() {
+        let (base, size) = ts_header(ptr);
+        std::alloc::dealloc(base, ts_layout(size));
+    }
+}
+
 const OUT_DIR: &str = "target/mem_dist";
 const BATCH_OUT_DIR: &str = "target/batch_mem_dist";
 
@@ -125,6 +186,49 @@ fn write_raw(dialect: &str, parser: &str, kind: &str, values: &[usize]) {
     }
 }
 
+/// Measure peak and retained bytes for each of `stmts` and write them under
+/// `{tag}peak` and `{tag}retained`. Skips parsers whose memory the Rust
+/// allocator cannot see (they report None).
+fn measure(parser: BenchParser, dialect: Dialect, stmts: &[&str], tag: &str) {
+    let Some(&first) = stmts.first() else {
+        return;
+    };
+    if parser.measure_mem(first, dialect).is_none() {
+        return;
+    }
+    // Warm up: let one-time caches/lazy statics allocate before we start,
+    // so they raise the baseline rather than the first statement.
+    let _ = parser.measure_mem(first, dialect);
+
+    let mut peaks = Vec::with_capacity(stmts.len());
+    let mut retained = Vec::with_capacity(stmts.len());
+    for s in stmts {
+        if let Some((pk, rt)) = parser.measure_mem(s, dialect) {
+            peaks.push(pk);
+            retained.push(rt);
+        }
+    }
+    write_raw(
+        dialect.dir_name(),
+        parser.name(),
+        &format!("{tag}peak"),
+        &peaks,
+    );
+    write_raw(
+        dialect.dir_name(),
+        parser.name(),
+        &format!("{tag}retained"),
+        &retained,
+    );
+    eprintln!(
+        "mem {} {}{}: n={}",
+        dialect.dir_name(),
+        parser.name(),
+        if tag.is_empty() { "" } else { " (common)" },
+        peaks.len()
+    );
+}
+
 fn run() {
     fs::create_dir_all(OUT_DIR).expect("create mem_dist dir");
     for &dialect in DIALECTS {
@@ -141,33 +245,21 @@ fn run() {
                 .filter(|s| parser.accepts(s, dialect) == Some(true))
                 .map(String::as_str)
                 .collect();
-            if accepted.is_empty() {
-                continue;
-            }
-            // Parsers whose memory the Rust allocator cannot see report None.
-            if parser.measure_mem(accepted[0], dialect).is_none() {
-                continue;
-            }
-            // Warm up: let one-time caches/lazy statics allocate before we start,
-            // so they raise the baseline rather than the first statement.
-            let _ = parser.measure_mem(accepted[0], dialect);
+            measure(parser, dialect, &accepted, "");
+        }
+    }
+}
 
-            let mut peaks = Vec::with_capacity(accepted.len());
-            let mut retained = Vec::with_capacity(accepted.len());
-            for s in &accepted {
-                if let Some((pk, rt)) = parser.measure_mem(s, dialect) {
-                    peaks.push(pk);
-                    retained.push(rt);
-                }
-            }
-            write_raw(dialect.dir_name(), parser.name(), "peak", &peaks);
-            write_raw(dialect.dir_name(), parser.name(), "retained", &retained);
-            eprintln!(
-                "mem {} {}: n={}",
-                dialect.dir_name(),
-                parser.name(),
-                peaks.len()
-            );
+/// Per-statement memory over each dialect's common subset, the statements
+/// every parser supporting the dialect accepts.
+fn run_common() {
+    fs::create_dir_all(OUT_DIR).expect("create mem_dist dir");
+    for &dialect in DIALECTS {
+        let stmts = load_dialect(dialect);
+        let parsers = participants(dialect);
+        let subset = common_subset(&stmts, dialect, &parsers);
+        for parser in parsers {
+            measure(parser, dialect, &subset, "common.");
         }
     }
 }
@@ -271,11 +363,25 @@ fn run_batch() {
 }
 
 fn main() {
+    // SAFETY: installed before any tree-sitter object exists, so no block is
+    // ever freed by a different allocator than the one that made it.
+    unsafe {
+        tree_sitter::set_allocator(
+            Some(ts_malloc),
+            Some(ts_calloc),
+            Some(ts_realloc),
+            Some(ts_free),
+        );
+    }
     ensure_corpus().expect("dataset corpus");
-    let batch = std::env::args().any(|a| a == "batch");
+    let mode = std::env::args().nth(1).unwrap_or_default();
     std::thread::Builder::new()
         .stack_size(WORKER_STACK)
-        .spawn(move || if batch { run_batch() } else { run() })
+        .spawn(move || match mode.as_str() {
+            "batch" => run_batch(),
+            "common" => run_common(),
+            _ => run(),
+        })
         .expect("spawn worker")
         .join()
         .expect("measurement thread panicked");
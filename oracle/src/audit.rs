@@ -0,0 +1,324 @@
+//! Label-drift audit: freshly computed labels against the committed ones.
+//!
+//! Every recall number on the site moves when a committed cache changes, so a
+//! rerun after a corpus change or an engine bump is compared with what it would
+//! replace before anything is written. A statement *flips* when both sides
+//! label it and the valid bit differs, and is added or dropped when only one
+//! side labels it. Flips are grouped by corpus file and by the engine class on
+//! each side, which is usually enough to tell an engine bump (one class pair
+//! dominates) from a corpus change (one file dominates). The per-release bits
+//! `--target` grades against are audited the same way ([`audit_releases`]):
+//! each release flip counts as a change, as does every committed release bit
+//! a write would drop because that release was not relabeled.
+//!
+//! `oracle diff` prints the full audit and writes nothing. Any other write is
+//! refused when the change rate passes `--max-flip-rate` (default
+//! [`MAX_FLIP_RATE`]) unless `--accept` is given.
+
+use std::collections::BTreeMap;
+
+use sql_ast_benchmark::oracle_cache::Label;
+
+/// Default `--max-flip-rate`: the share of the committed labels, in percent,
+/// that may flip, be dropped or be added to before a write needs `--accept`.
+/// About a hundred statements of a 100,000-statement corpus.
+pub const MAX_FLIP_RATE: f64 = 0.1;
+
+/// Flipped statements listed per direction, and rows per grouping.
+const FLIP_EXAMPLES: usize = 10;
+
+/// How fresh labels differ from the committed ones for one dialect.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Audit {
+    /// Statements both sides label.
+    pub compared: usize,
+    /// Statements only the fresh labels cover.
+    pub added: usize,
+    /// Indices of the first [`FLIP_EXAMPLES`] added statements.
+    pub added_examples: Vec<usize>,
+    /// Statements only the committed labels cover.
+    pub dropped: usize,
+    /// Indices of the first [`FLIP_EXAMPLES`] dropped statements.
+    pub dropped_examples: Vec<usize>,
+    /// Indices of statements that turned valid, the first [`FLIP_EXAMPLES`].
+    pub to_valid: Vec<usize>,
+    /// Total count behind `to_valid`.
+    pub to_valid_total: usize,
+    /// As `to_valid`, for statements that turned invalid.
+    pub to_invalid: Vec<usize>,
+    /// Total count behind `to_invalid`.
+    pub to_invalid_total: usize,
+    /// Flips per corpus file: (turned valid, turned invalid).
+    pub by_file: BTreeMap<String, (usize, usize)>,
+    /// Flips per (committed class, fresh class) name pair.
+    pub by_class: BTreeMap<(&'static str, &'static str), usize>,
+    /// Per-release valid bits that flipped, per release.
+    pub release_flips: BTreeMap<String, usize>,
+    /// Committed per-release bits of releases the fresh run did not label.
+    pub release_dropped: usize,
+}
+
+impl Audit {
+    /// Statements whose valid bit flipped, both directions.
+    pub const fn flips(&self) -> usize {
+        self.to_valid_total + self.to_invalid_total
+    }
+
+    /// Per-release bits that flipped, all releases.
+    pub fn release_flip_total(&self) -> usize {
+        self.release_flips.values().sum()
+    }
+
+    /// Per-release bits that flipped or would be dropped.
+    pub fn release_changes(&self) -> usize {
+        self.release_flip_total() + self.release_dropped
+    }
+
+    /// Flipped, added and dropped statements together, plus the per-release
+    /// bits that flipped or would be dropped.
+    pub fn changes(&self) -> usize {
+        self.flips() + self.added + self.dropped + self.release_changes()
+    }
+
+    /// [`Self::changes`] as a percentage of the committed labels. 0 with no
+    /// committed labels, since a first write replaces nothing.
+    pub fn flip_rate(&self) -> f64 {
+        let committed = self.compared + self.dropped;
+        if committed == 0 {
+            0.0
+        } else {
+            100.0 * self.changes() as f64 / committed as f64
+        }
+    }
+}
+
+/// Compare `fresh` with `committed` statement by statement. `files` names the
+/// corpus file of each statement; `None` on either side means unlabeled.
+pub fn audit(files: &[String], fresh: &[Option<Label>], committed: &[Option<Label>]) -> Audit {
+    let mut a = Audit::default();
+    for (i, ((file, new), old)) in files.iter().zip(fresh).zip(committed).enumerate() {
+        let (new, old) = match (new, old) {
+            (Some(new), Some(old)) => (new, old),
+            (Some(_), None) => {
+                a.added += 1;
+                if a.added_examples.len() < FLIP_EXAMPLES {
+                    a.added_examples.push(i);
+                }
+                continue;
+            }
+            (None, Some(_)) => {
+                a.dropped += 1;
+                if a.dropped_examples.len() < FLIP_EXAMPLES {
+                    a.dropped_examples.push(i);
+                }
+                continue;
+            }
+            (None, None) => continue,
+        };
+        a.compared += 1;
+        if new.valid == old.valid {
+            continue;
+        }
+        let counts = a.by_file.entry(file.clone()).or_default();
+        let (examples, total) = if new.valid {
+            counts.0 += 1;
+            (&mut a.to_valid, &mut a.to_valid_total)
+        } else {
+            counts.1 += 1;
+            (&mut a.to_invalid, &mut a.to_invalid_total)
+        };
+        *total += 1;
+        if examples.len() < FLIP_EXAMPLES {
+            examples.push(i);
+        }
+        *a.by_class
+            .entry((old.class.name(), new.class.name()))
+            .or_default() += 1;
+    }
+    a
+}
+
+/// Add the per-release labels to `a`: each fresh release column in `releases`
+/// is compared with the committed bit `committed_at(i, release)` of every
+/// statement the write keeps (a `Some` in `fresh`), and the committed bits of
+/// each release in `committed_versions` that `releases` lacks are dropped.
+pub fn audit_releases(
+    a: &mut Audit,
+    fresh: &[Option<Label>],
+    releases: &[(String, Vec<bool>)],
+    committed_versions: &[String],
+    committed_at: impl Fn(usize, &str) -> Option<bool>,
+) {
+    for (version, bits) in releases {
+        let flips = fresh
+            .iter()
+            .zip(bits)
+            .enumerate()
+            .filter(|(i, (new, bit))| {
+                new.is_some() && committed_at(*i, version).is_some_and(|old| old != **bit)
+            })
+            .count();
+        if flips > 0 {
+            a.release_flips.insert(version.clone(), flips);
+        }
+    }
+    for version in committed_versions {
+        if !releases.iter().any(|(v, _)| v == version) {
+            a.release_dropped += (0..fresh.len())
+                .filter(|&i| committed_at(i, version).is_some())
+                .count();
+        }
+    }
+}
+
+/// Print the audit for dialect `name`: one summary line, and with `detail` the
+/// groupings and example statements.
+pub fn print(name: &str, stmts: &[String], files: &[String], a: &Audit, detail: bool) {
+    eprintln!(
+        "{name}: {} of {} compared labels flipped ({} turned valid, {} turned invalid), {} newly labeled, {} no longer labeled: {:.2}% changed",
+        a.flips(),
+        a.compared,
+        a.to_valid_total,
+        a.to_invalid_total,
+        a.added,
+        a.dropped,
+        a.flip_rate()
+    );
+    if a.release_changes() > 0 {
+        eprintln!(
+            "{name}: {} per-release labels flipped, {} no longer labeled",
+            a.release_flip_total(),
+            a.release_dropped
+        );
+    }
+    if !detail || a.changes() == 0 {
+        return;
+    }
+    if !a.release_flips.is_empty() {
+        eprintln!("  per-release flips:");
+        for (version, n) in &a.release_flips {
+            eprintln!("    {version}: {n}");
+        }
+    }
+    if !a.by_file.is_empty() {
+        let mut by_file: Vec<_> = a.by_file.iter().collect();
+        by_file.sort_by_key(|(_, (v, i))| std::cmp::Reverse(v + i));
+        eprintln!("  by corpus file (turned valid / turned invalid):");
+        for (file, (v, i)) in by_file.iter().take(FLIP_EXAMPLES) {
+            eprintln!("    {file}: {v} / {i}");
+        }
+        let mut by_class: Vec<_> = a.by_class.iter().collect();
+        by_class.sort_by_key(|(_, n)| std::cmp::Reverse(**n));
+        eprintln!("  by engine class (committed -> fresh):");
+        for ((old, new), n) in by_class {
+            eprintln!("    {old} -> {new}: {n}");
+        }
+    }
+    let show = |title: &str, idx: &[usize]| {
+        if idx.is_empty() {
+            return;
+        }
+        eprintln!("  {title}:");
+        for &i in idx {
+            eprintln!(
+                "    [{}] {}",
+                files[i],
+                stmts[i].chars().take(160).collect::<String>()
+            );
+        }
+    };
+    show("turned valid", &a.to_valid);
+    show("turned invalid", &a.to_invalid);
+    show("newly labeled", &a.added_examples);
+    show("no longer labeled", &a.dropped_examples);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{audit, audit_releases};
+    use sql_ast_benchmark::oracle_cache::{EngineClass, Label};
+
+    #[test]
+    fn flips_are_grouped_by_file_and_class_pair() {
+        let files: Vec<String> = ["a.txt", "a.txt", "b.txt", "b.txt", "b.txt"]
+            .map(String::from)
+            .to_vec();
+        let semantic = Label::parsed(EngineClass::Semantic);
+        let fresh = [
+            Some(semantic),
+            Some(Label::SYNTAX),
+            Some(Label::VALID),
+            Some(Label::VALID),
+            None,
+        ];
+        let committed = [
+            Some(Label::SYNTAX),
+            Some(Label::SYNTAX),
+            Some(Label::SYNTAX),
+            None,
+            Some(Label::VALID),
+        ];
+        let a = audit(&files, &fresh, &committed);
+        assert_eq!((a.compared, a.added, a.dropped), (3, 1, 1));
+        assert_eq!(a.to_valid, [0, 2]);
+        assert_eq!(a.to_invalid_total, 0);
+        assert_eq!(a.by_file["a.txt"], (1, 0));
+        assert_eq!(a.by_file["b.txt"], (1, 0));
+        assert_eq!(a.by_class[&("syntax", "semantic")], 1);
+        assert_eq!(a.by_class[&("syntax", "valid")], 1);
+        // Two flips, one added and one dropped over four committed labels.
+        assert_eq!(a.changes(), 4);
+        assert!((a.flip_rate() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn added_and_dropped_labels_count_as_changes() {
+        let files: Vec<String> = ["a.txt"; 4].map(String::from).to_vec();
+        let valid = Some(Label::VALID);
+        let a = audit(
+            &files,
+            &[valid, valid, valid, None],
+            &[valid, valid, None, valid],
+        );
+        assert_eq!(a.flips(), 0);
+        assert_eq!(
+            (a.added_examples.as_slice(), a.dropped_examples.as_slice()),
+            (&[2][..], &[3][..])
+        );
+        assert!((a.flip_rate() - 200.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_first_write_is_no_drift() {
+        let a = audit(&["a.txt".to_string()], &[Some(Label::VALID)], &[None]);
+        assert_eq!((a.flips(), a.added), (0, 1));
+        assert!(a.flip_rate().abs() < f64::EPSILON);
+    }
+    #[test]
+    fn release_flips_and_dropped_releases_count_as_changes() {
+        let files: Vec<String> = ["a.txt"; 3].map(String::from).to_vec();
+        let valid = Some(Label::VALID);
+        let fresh = [valid, valid, None];
+        let mut a = audit(&files, &fresh, &fresh);
+        assert_eq!(a.changes(), 0);
+        // Committed: "15" labels every statement valid; "16" only the first.
+        let committed_at = |i: usize, v: &str| match v {
+            "15" => Some(true),
+            "16" => (i == 0).then_some(true),
+            _ => None,
+        };
+        let releases = [
+            ("15".to_string(), vec![false, true, false]),
+            ("17".to_string(), vec![false, false, false]),
+        ];
+        let versions = ["15", "16"].map(String::from);
+        audit_releases(&mut a, &fresh, &releases, &versions, committed_at);
+        // Statement 0 flips at 15 (statement 2 is not written); 17 is new;
+        // 16 was not relabeled, so its one committed bit is dropped.
+        assert_eq!(a.release_flips.len(), 1);
+        assert_eq!(a.release_flips["15"], 1);
+        assert_eq!(a.release_dropped, 1);
+        assert_eq!(a.changes(), 2);
+        assert!((a.flip_rate() - 100.0).abs() < 1e-9);
+    }
+}
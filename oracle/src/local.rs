@@ -0,0 +1,279 @@
+//! In-process oracle stand-ins, for relabeling a modified corpus without Docker.
+//!
+//! PostgreSQL labels through libpg_query (the `pg_query` crate the benchmark
+//! already links as its PostgreSQL reference parser): the server's own grammar,
+//! run without a server. SQLite labels through a bundled libsqlite3
+//! (`rusqlite`), preparing `EXPLAIN <stmt>` as the CLI oracle does, so a
+//! missing table or column still counts as parsed.
+//!
+//! Neither is the committed reference. libpg_query stops at the raw parse, so
+//! the few syntax errors the server raises during parse analysis go unseen, and
+//! the bundled SQLite is whatever version `rusqlite` ships rather than the
+//! pinned container's. `--check` diffs the in-process labels against the
+//! committed Docker-derived ones, so that drift is measured rather than
+//! assumed.
+//!
+//! Redshift has no engine to run at all, so its only labels come from here:
+//! libpg_query again, with the delta documented on [`label_redshift`].
+
+use anyhow::{Context, Result};
+use sql_ast_benchmark::datasets::Dialect;
+use sql_ast_benchmark::oracle_cache::{reference_accepts, Label};
+
+use crate::sqlite_label;
+
+/// Dialects with an in-process backend.
+pub const LOCAL: &[&str] = &["postgresql", "sqlite", "redshift"];
+
+/// Statements listed per direction of a [`Drift`] report.
+const DRIFT_EXAMPLES: usize = 10;
+
+/// In-process labels for `stmts`, or `None` if `dialect` has no local backend.
+/// A `None` label is an abstention: the statement is left out of the cache,
+/// so grading skips it.
+pub fn label(dialect: Dialect, stmts: &[String]) -> Option<Result<Vec<Option<Label>>>> {
+    let all = |v: Vec<Label>| v.into_iter().map(Some).collect();
+    match dialect {
+        Dialect::Postgresql => Some(Ok(all(label_postgresql(stmts)))),
+        Dialect::Sqlite => Some(label_sqlite(stmts).map(all)),
+        Dialect::Redshift => Some(Ok(label_redshift(stmts))),
+        _ => None,
+    }
+}
+
+/// PostgreSQL via libpg_query: invalid iff the raw parser rejects it. The
+/// server's `42601` comes from the same grammar, so this agrees with the
+/// container on everything but the parse-analysis checks. The raw parser only
+/// ever raises syntax errors, so there is no class beyond that.
+fn label_postgresql(stmts: &[String]) -> Vec<Label> {
+    stmts
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            if i % 5000 == 0 {
+                eprintln!("  postgresql (local) {i}/{}", stmts.len());
+            }
+            if pg_query::parse(s).is_ok() {
+                Label::VALID
+            } else {
+                Label::SYNTAX
+            }
+        })
+        .collect()
+}
+
+/// Redshift via libpg_query, an approximation: Redshift's parser descends from
+/// PostgreSQL's, so a statement libpg_query accepts is labeled valid, even
+/// where Redshift lacks the PostgreSQL feature. The corpus (taken from
+/// Redshift's own documentation and suites) makes that rare, but a parser that
+/// rejects such a statement is charged a recall miss.
+///
+/// A statement libpg_query rejects is left unlabeled rather than called
+/// invalid: the rejection may be a Redshift extension (distribution and sort
+/// keys, `UNLOAD`, external tables and so on) the PostgreSQL grammar lacks,
+/// and no keyword list tells those apart from real syntax errors. Redshift
+/// therefore grades recall only.
+fn label_redshift(stmts: &[String]) -> Vec<Option<Label>> {
+    stmts
+        .iter()
+        .map(|s| pg_query::parse(s).is_ok().then_some(Label::VALID))
+        .collect()
+}
+
+/// SQLite via a bundled libsqlite3: each statement is prepared (never stepped)
+/// as `EXPLAIN <stmt>`, and a prepare error is graded by the same rule as the
+/// CLI oracle's stderr ([`sqlite_label`]). A line holding several statements
+/// has each prepared in turn, as the CLI would run them.
+fn label_sqlite(stmts: &[String]) -> Result<Vec<Label>> {
+    let conn = rusqlite::Connection::open_in_memory().context("open sqlite")?;
+    let mut valid = Vec::with_capacity(stmts.len());
+    for (i, s) in stmts.iter().enumerate() {
+        let script = format!("EXPLAIN {}", s.trim().trim_end_matches(';'));
+        let mut batch = rusqlite::Batch::new(&conn, &script);
+        let v = loop {
+            match batch.next() {
+                Ok(Some(_)) => {}
+                Ok(None) => break Label::VALID,
+                Err(e) => break sqlite_label(&sqlite_message(e)),
+            }
+        };
+        valid.push(v);
+        if i % 5000 == 0 {
+            eprintln!("  sqlite (local) {i}/{}", stmts.len());
+        }
+    }
+    Ok(valid)
+}
+
+/// The engine's own message for a prepare error, without the statement text
+/// `rusqlite` appends (which could itself contain "no such").
+fn sqlite_message(e: rusqlite::Error) -> String {
+    match e {
+        rusqlite::Error::SqliteFailure(_, Some(msg))
+        | rusqlite::Error::SqlInputError { msg, .. } => msg,
+        other => other.to_string(),
+    }
+}
+
+/// How in-process labels differ from the committed ones for one dialect.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Drift {
+    /// Statements both label the same way.
+    pub agree: usize,
+    /// Statements the committed cache does not cover.
+    pub uncovered: usize,
+    /// Statements the in-process backend abstained on.
+    pub abstained: usize,
+    /// Indices of statements valid here but invalid in the committed cache,
+    /// the first [`DRIFT_EXAMPLES`] only.
+    pub only_local: Vec<usize>,
+    /// Total count behind `only_local`.
+    pub only_local_total: usize,
+    /// As `only_local`, for statements invalid here but valid in the cache.
+    pub only_committed: Vec<usize>,
+    /// Total count behind `only_committed`.
+    pub only_committed_total: usize,
+}
+
+/// Compare `local` labels (`None` where the backend abstained) with the
+/// `committed` verdicts (`None` where the cache has no entry), statement by
+/// statement.
+pub fn compare(local: &[Option<bool>], committed: &[Option<bool>]) -> Drift {
+    let mut drift = Drift::default();
+    for (i, (ours, theirs)) in local.iter().zip(committed).enumerate() {
+        match (ours, theirs) {
+            (_, None) => drift.uncovered += 1,
+            (None, Some(_)) => drift.abstained += 1,
+            (Some(ours), Some(theirs)) if ours == theirs => drift.agree += 1,
+            (Some(true), Some(_)) => {
+                drift.only_local_total += 1;
+                if drift.only_local.len() < DRIFT_EXAMPLES {
+                    drift.only_local.push(i);
+                }
+            }
+            (Some(false), Some(_)) => {
+                drift.only_committed_total += 1;
+                if drift.only_committed.len() < DRIFT_EXAMPLES {
+                    drift.only_committed.push(i);
+                }
+            }
+        }
+    }
+    drift
+}
+
+/// Diff in-process labels against the committed cache for `dialect` and print
+/// the result. Returns whether the two agree on every covered statement. Only
+/// the valid bit is compared: the in-process backends see less of the engine's
+/// error classes than the containers.
+pub fn check(dialect: Dialect, stmts: &[String], local: &[Option<Label>]) -> bool {
+    let committed: Vec<Option<bool>> = stmts
+        .iter()
+        .map(|s| reference_accepts(s, dialect))
+        .collect();
+    let local: Vec<Option<bool>> = local.iter().map(|l| l.map(|l| l.valid)).collect();
+    let drift = compare(&local, &committed);
+    let name = dialect.dir_name();
+    eprintln!(
+        "{name}: {} agree, {} valid only in-process, {} valid only in the committed labels, {} not covered by the committed labels, {} abstained in-process",
+        drift.agree,
+        drift.only_local_total,
+        drift.only_committed_total,
+        drift.uncovered,
+        drift.abstained
+    );
+    let show = |title: &str, idx: &[usize]| {
+        if idx.is_empty() {
+            return;
+        }
+        eprintln!("  {title}:");
+        for &i in idx {
+            eprintln!("    {}", stmts[i].chars().take(160).collect::<String>());
+        }
+    };
+    show(
+        "valid in-process, invalid in the committed labels",
+        &drift.only_local,
+    );
+    show(
+        "invalid in-process, valid in the committed labels",
+        &drift.only_committed,
+    );
+    drift.only_local_total == 0 && drift.only_committed_total == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, label_postgresql, label_redshift, label_sqlite, Drift};
+    use sql_ast_benchmark::oracle_cache::{EngineClass, Label};
+
+    fn owned(v: &[&str]) -> Vec<String> {
+        v.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn postgresql_rejects_only_syntax_errors() {
+        let stmts = owned(&["SELECT * FROM missing", "SELEC 1", "SELECT 1; SELECT 2"]);
+        assert_eq!(
+            label_postgresql(&stmts),
+            [Label::VALID, Label::SYNTAX, Label::VALID]
+        );
+    }
+
+    #[test]
+    fn sqlite_treats_missing_objects_as_parsed() {
+        let stmts = owned(&[
+            "SELECT x FROM missing",
+            "SELECT FROM",
+            "SELECT 1; SELECT 2;",
+            "SELECT 1; SELEC 2",
+        ]);
+        let semantic = Label::parsed(EngineClass::Semantic);
+        assert_eq!(
+            label_sqlite(&stmts).unwrap(),
+            [semantic, Label::SYNTAX, Label::VALID, Label::SYNTAX]
+        );
+    }
+
+    #[test]
+    fn redshift_abstains_on_every_rejection() {
+        let stmts = owned(&[
+            "SELECT a FROM t",
+            "CREATE TABLE t (a int) DISTKEY(a) SORTKEY(a)",
+            "SELEC 1",
+        ]);
+        assert_eq!(label_redshift(&stmts), [Some(Label::VALID), None, None]);
+    }
+
+    #[test]
+    fn compare_splits_disagreements_by_direction() {
+        let local = [
+            Some(true),
+            Some(false),
+            Some(true),
+            Some(false),
+            Some(true),
+            None,
+        ];
+        let committed = [
+            Some(true),
+            Some(true),
+            Some(false),
+            Some(false),
+            None,
+            Some(true),
+        ];
+        assert_eq!(
+            compare(&local, &committed),
+            Drift {
+                agree: 2,
+                uncovered: 1,
+                abstained: 1,
+                only_local: vec![2],
+                only_local_total: 1,
+                only_committed: vec![1],
+                only_committed_total: 1,
+            }
+        );
+    }
+}
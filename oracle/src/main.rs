@@ -9,11 +9,39 @@
 //!   cargo run --release -p oracle              # all implemented dialects
 //!   cargo run --release -p oracle -- sqlite    # one or more by dir name
 //!
-//! Server engines (PostgreSQL, MySQL, ClickHouse, SQL Server) run in
+//! Server engines (PostgreSQL, MySQL, ClickHouse, SQL Server, Trino) run in
 //! testcontainers and connect over a mapped port. SQLite runs as the `sqlite3`
 //! CLI in a one-shot container. DuckDB, which has no server and whose CLI errors
 //! carry no line numbers, links the real `libduckdb` in-process via the `duckdb`
-//! crate. Each uses the engine's parse-only path where one exists.
+//! crate. Each uses the engine's parse-only path where one exists. Redshift, a
+//! managed service with no engine to run, is approximated in-process by
+//! libpg_query (see [`local`]).
+//!
+//! PostgreSQL and SQLite also have in-process stand-ins that need no Docker
+//! (see [`local`]), for relabeling a modified corpus offline:
+//!
+//!   cargo run --release -p oracle -- --local            # write labels in-process
+//!   cargo run --release -p oracle -- --check sqlite     # diff against the committed labels
+//!
+//! PostgreSQL can also be labeled per engine release, so grading can target the
+//! version run in production (`sqlbench correctness --target postgresql=15`).
+//! Each listed release, oldest first, runs in its own `postgres:<v>-alpine`
+//! container next to the pinned one:
+//!
+//!   cargo run --release -p oracle -- --versions 12,13,14,15,16,17 postgresql
+//!
+//! No other dialect has a versioned adapter yet, so `--versions` on one writes
+//! its pinned labels only.
+//!
+//! A rerun never silently replaces the committed labels: each write is audited
+//! against them first (see [`audit`]) and refused when too many statements flip
+//! valid/invalid or gain or lose a label, in the pinned column or any
+//! per-release one, unless `--accept` is given. `diff` prints the full audit and
+//! writes nothing:
+//!
+//!   cargo run --release -p oracle -- diff sqlite                   # review the changes
+//!   cargo run --release -p oracle -- --max-flip-rate 5 sqlite      # allow up to 5%
+//!   cargo run --release -p oracle -- --accept sqlite               # overwrite regardless
 
 use std::collections::HashMap;
 use std::io::Write as _;
@@ -22,8 +50,14 @@ use std::process::{Command, Stdio};
 
 use anyhow::{Context, Result};
 use sql_ast_benchmark::datasets::{ensure_corpus, Dialect};
-use sql_ast_benchmark::oracle_cache::{statement_hash, LABELS_DIR};
-use sql_ast_benchmark::report::load_dialect_from;
+use sql_ast_benchmark::oracle_cache::{
+    engine_versions, reference_accepts_at, reference_label, statement_hash, EngineClass, Label,
+    FORMAT_V2, LABELS_DIR, MAX_RELEASES,
+};
+use sql_ast_benchmark::report::load_dialect_files_from;
+
+mod audit;
+mod local;
 
 /// Dialects with an adapter implemented so far.
 const IMPLEMENTED: &[&str] = &[
@@ -33,68 +67,220 @@ const IMPLEMENTED: &[&str] = &[
     "clickhouse",
     "tsql",
     "duckdb",
+    "trino",
+    "redshift",
 ];
 
 #[tokio::main]
 async fn main() -> Result<()> {
     ensure_corpus().context("dataset corpus")?;
     let args: Vec<String> = std::env::args().skip(1).collect();
-    let wanted: Vec<String> = if args.is_empty() {
-        IMPLEMENTED.iter().map(|s| (*s).to_string()).collect()
+    // `--check` labels in-process too, but diffs instead of writing.
+    let check = args.iter().any(|a| a == "--check");
+    let in_process = check || args.iter().any(|a| a == "--local");
+    let accept = args.iter().any(|a| a == "--accept");
+    let value_at = |flag: &str| args.iter().position(|a| a == flag).map(|i| i + 1);
+    let versions_at = value_at("--versions");
+    let rate_at = value_at("--max-flip-rate");
+    let max_flip_rate = match rate_at {
+        Some(i) => args
+            .get(i)
+            .and_then(|v| v.parse::<f64>().ok())
+            .context("--max-flip-rate needs a percentage")?,
+        None => audit::MAX_FLIP_RATE,
+    };
+    let versions: Vec<String> = versions_at
+        .and_then(|i| args.get(i))
+        .map(|v| v.split(',').map(str::to_string).collect())
+        .unwrap_or_default();
+    anyhow::ensure!(
+        versions_at.is_none() || !versions.is_empty(),
+        "--versions needs a comma-separated release list"
+    );
+    anyhow::ensure!(
+        versions.len() <= MAX_RELEASES,
+        "--versions lists {} releases; a label cache holds at most {MAX_RELEASES}",
+        versions.len()
+    );
+    anyhow::ensure!(
+        versions.is_empty() || !in_process,
+        "--versions labels against real engine releases; it cannot run with --local or --check"
+    );
+    let mut names: Vec<String> = args
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, ref a)| !a.starts_with("--") && Some(i) != versions_at && Some(i) != rate_at)
+        .map(|(_, a)| a)
+        .collect();
+    // `diff` prints the full audit and never writes.
+    let diff = names.first().is_some_and(|n| n == "diff");
+    if diff {
+        names.remove(0);
+    }
+    let wanted: Vec<String> = if !names.is_empty() {
+        names
+    } else if in_process {
+        local::LOCAL.iter().map(|s| (*s).to_string()).collect()
     } else {
-        args
+        IMPLEMENTED.iter().map(|s| (*s).to_string()).collect()
     };
     std::fs::create_dir_all(LABELS_DIR)?;
 
+    let mut drifted = Vec::new();
+    let mut refused = Vec::new();
     for name in &wanted {
         let Some(dialect) = Dialect::from_dir_name(name) else {
             eprintln!("unknown dialect: {name}");
             continue;
         };
-        let stmts = load_dialect_from(Path::new("datasets"), dialect);
+        let (files, stmts): (Vec<String>, Vec<String>) =
+            load_dialect_files_from(Path::new("datasets"), dialect)
+                .into_iter()
+                .flat_map(|(file, stmts)| stmts.into_iter().map(move |s| (file.clone(), s)))
+                .unzip();
         if stmts.is_empty() {
             eprintln!("no corpus for {name}, skipping");
             continue;
         }
         eprintln!("labeling {name}: {} statements", stmts.len());
-        let valid = match dialect {
-            Dialect::Postgresql => label_postgresql(&stmts).await?,
-            Dialect::Sqlite => label_sqlite(&stmts)?,
-            Dialect::Mysql => label_mysql(&stmts).await?,
-            Dialect::Clickhouse => label_clickhouse(&stmts).await?,
-            Dialect::Tsql => label_tsql(&stmts).await?,
-            Dialect::Duckdb => label_duckdb(&stmts)?,
-            _ => {
-                eprintln!("{name}: no adapter yet, skipping");
+        // Redshift is a managed service with no engine to run, so its only
+        // adapter is the in-process stand-in.
+        let valid: Vec<Option<Label>> = if in_process || dialect == Dialect::Redshift {
+            let Some(valid) = local::label(dialect, &stmts) else {
+                eprintln!("{name}: no in-process backend, skipping");
                 continue;
-            }
+            };
+            valid?
+        } else {
+            let valid = match dialect {
+                Dialect::Postgresql => label_postgresql(&stmts, None).await?,
+                Dialect::Sqlite => label_sqlite(&stmts)?,
+                Dialect::Mysql => label_mysql(&stmts).await?,
+                Dialect::Clickhouse => label_clickhouse(&stmts).await?,
+                Dialect::Tsql => label_tsql(&stmts).await?,
+                Dialect::Duckdb => label_duckdb(&stmts)?,
+                Dialect::Trino => label_trino(&stmts).await?,
+                _ => {
+                    eprintln!("{name}: no adapter yet, skipping");
+                    continue;
+                }
+            };
+            valid.into_iter().map(Some).collect()
         };
-        write_cache(dialect, &stmts, &valid)?;
+        let mut releases = Vec::with_capacity(versions.len());
+        if !versions.is_empty() && dialect != Dialect::Postgresql {
+            eprintln!("{name}: no versioned adapter, writing the pinned labels only");
+        } else {
+            for v in &versions {
+                eprintln!("labeling {name} {v}");
+                let labels = label_postgresql(&stmts, Some(v)).await?;
+                releases.push((v.clone(), labels.iter().map(|l| l.valid).collect()));
+            }
+        }
+        if check {
+            if !local::check(dialect, &stmts, &valid) {
+                drifted.push(name.as_str());
+            }
+        } else {
+            let committed: Vec<Option<Label>> =
+                stmts.iter().map(|s| reference_label(s, dialect)).collect();
+            let mut drift = audit::audit(&files, &valid, &committed);
+            audit::audit_releases(
+                &mut drift,
+                &valid,
+                &releases,
+                engine_versions(dialect),
+                |i, v| reference_accepts_at(&stmts[i], dialect, v),
+            );
+            audit::print(name, &stmts, &files, &drift, diff);
+            if diff {
+                continue;
+            }
+            if drift.flip_rate() > max_flip_rate && !accept {
+                eprintln!(
+                    "{name}: change rate over the {max_flip_rate}% threshold, keeping the committed labels (review with `oracle diff {name}`, rerun with --accept to overwrite)"
+                );
+                refused.push(name.as_str());
+                continue;
+            }
+            write_cache(dialect, &stmts, &valid, &releases)?;
+        }
     }
+    anyhow::ensure!(
+        drifted.is_empty(),
+        "in-process labels differ from the committed ones for: {}",
+        drifted.join(", ")
+    );
+    anyhow::ensure!(
+        refused.is_empty(),
+        "label changes over the threshold, not written for: {}",
+        refused.join(", ")
+    );
     Ok(())
 }
 
-/// Write the per-dialect validity cache: header line = corpus statement count,
-/// then `hash\t0|1` for each unique statement.
-fn write_cache(dialect: Dialect, stmts: &[String], valid: &[bool]) -> Result<()> {
-    let mut map: HashMap<u64, bool> = HashMap::new();
-    for (s, &v) in stmts.iter().zip(valid) {
-        map.entry(statement_hash(s)).or_insert(v);
+/// Write the per-dialect validity cache in format version 2: header line = `v2`
+/// and the corpus statement count, then `hash\t0|1\tclass` for each unique
+/// statement. A `None` label (an adapter abstaining) is left out, so grading
+/// skips the statement.
+///
+/// With per-release labels (`releases`, oldest first) the header also lists the
+/// releases and each row gains a fourth column of one `0`/`1` per release, the
+/// format `oracle_cache` documents, for at most [`MAX_RELEASES`] releases.
+fn write_cache(
+    dialect: Dialect,
+    stmts: &[String],
+    valid: &[Option<Label>],
+    releases: &[(String, Vec<bool>)],
+) -> Result<()> {
+    anyhow::ensure!(
+        releases.len() <= MAX_RELEASES,
+        "{} releases do not fit a label cache (at most {MAX_RELEASES})",
+        releases.len()
+    );
+    let mut map: HashMap<u64, (Label, String)> = HashMap::new();
+    for (i, (s, v)) in stmts.iter().zip(valid).enumerate() {
+        if let Some(v) = *v {
+            map.entry(statement_hash(s)).or_insert_with(|| {
+                let bits = releases
+                    .iter()
+                    .map(|(_, r)| if r[i] { '1' } else { '0' })
+                    .collect();
+                (v, bits)
+            });
+        }
     }
-    let mut body = format!("{}\n", stmts.len());
-    for (h, v) in &map {
-        body.push_str(&format!("{h}\t{}\n", u8::from(*v)));
+    let mut body = format!("{FORMAT_V2}{}", stmts.len());
+    if !releases.is_empty() {
+        let names: Vec<&str> = releases.iter().map(|(v, _)| v.as_str()).collect();
+        body.push_str(&format!("\t{}", names.join(",")));
+    }
+    body.push('\n');
+    for (h, (l, bits)) in &map {
+        body.push_str(&format!("{h}\t{}\t{}", u8::from(l.valid), l.class.name()));
+        if !bits.is_empty() {
+            body.push_str(&format!("\t{bits}"));
+        }
+        body.push('\n');
     }
     let path = format!("{LABELS_DIR}/{}.tsv.zst", dialect.dir_name());
     let raw = std::fs::File::create(&path).with_context(|| format!("create {path}"))?;
     let mut enc = zstd::stream::Encoder::new(raw, 19)?;
     enc.write_all(body.as_bytes())?;
     enc.finish()?;
-    let n_valid = map.values().filter(|v| **v).count();
+    let n_valid = map.values().filter(|(l, _)| l.valid).count();
+    let classes: Vec<String> = EngineClass::ALL
+        .iter()
+        .map(|&c| {
+            let n = map.values().filter(|(l, _)| l.class == c).count();
+            format!("{n} {}", c.name())
+        })
+        .collect();
     eprintln!(
-        "wrote {path}: {} unique statements, {n_valid} valid, {} invalid",
+        "wrote {path}: {} unique statements, {n_valid} valid, {} invalid ({})",
         map.len(),
-        map.len() - n_valid
+        map.len() - n_valid,
+        classes.join(", ")
     );
     Ok(())
 }
@@ -103,16 +289,21 @@ fn write_cache(dialect: Dialect, stmts: &[String], valid: &[bool]) -> Result<()>
 /// back transaction (PG has transactional DDL). Invalid iff the SQLSTATE is
 /// `42601` (syntax_error). Schema errors (42P01, 42703) and "cannot run in a
 /// transaction" (25xxx) are not syntax, so they count as valid (parsed fine).
-async fn label_postgresql(stmts: &[String]) -> Result<Vec<bool>> {
+/// The SQLSTATE also gives the class ([`postgres_label`]).
+///
+/// `release` picks the `postgres:<release>-alpine` image for per-release labels;
+/// `None` runs the pinned default.
+async fn label_postgresql(stmts: &[String], release: Option<&str>) -> Result<Vec<Label>> {
     use testcontainers_modules::postgres::Postgres;
     use testcontainers_modules::testcontainers::runners::AsyncRunner;
-    use tokio_postgres::error::SqlState;
+    use testcontainers_modules::testcontainers::{ContainerRequest, ImageExt};
     use tokio_postgres::NoTls;
 
-    let node = Postgres::default()
-        .start()
-        .await
-        .context("start postgres container")?;
+    let image: ContainerRequest<Postgres> = match release {
+        Some(v) => Postgres::default().with_tag(format!("{v}-alpine")),
+        None => Postgres::default().into(),
+    };
+    let node = image.start().await.context("start postgres container")?;
     let host = node.get_host().await?;
     let port = node.get_host_port_ipv4(5432).await?;
     let conn_str =
@@ -147,8 +338,8 @@ async fn label_postgresql(stmts: &[String]) -> Result<Vec<bool>> {
             // -> valid). An error with no code is a transport/connection failure,
             // which must never be recorded as "valid".
             let verdict = match res {
-                Ok(()) => Some(true),
-                Err(e) => e.code().map(|code| code != &SqlState::SYNTAX_ERROR),
+                Ok(()) => Some(Label::VALID),
+                Err(e) => e.code().map(postgres_label),
             };
             match verdict {
                 Some(v) => {
@@ -165,7 +356,7 @@ async fn label_postgresql(stmts: &[String]) -> Result<Vec<bool>> {
                     // stage proves it parsed (a syntax error would carry code
                     // 42601 and be a real verdict above), so it is valid. Record it
                     // and reconnect to replace the now-broken connection.
-                    valid.push(true);
+                    valid.push(Label::VALID);
                     death_idx = None;
                     death_count = 0;
                     reconnects += 1;
@@ -194,7 +385,7 @@ async fn label_postgresql(stmts: &[String]) -> Result<Vec<bool>> {
                             "  postgresql: statement {i} repeatedly kills the backend; marking invalid and skipping: {}",
                             stmts[i].chars().take(120).collect::<String>()
                         );
-                        valid.push(false);
+                        valid.push(Label::rejected(EngineClass::Limit));
                         death_idx = None;
                         death_count = 0;
                     } else {
@@ -216,6 +407,18 @@ async fn label_postgresql(stmts: &[String]) -> Result<Vec<bool>> {
     Ok(valid)
 }
 
+/// The label of a PostgreSQL error by its SQLSTATE: `42601` is a syntax error,
+/// `0A000` (feature_not_supported) an unsupported feature and class `54`
+/// (program_limit_exceeded) a limit, all but the first raised after the parse.
+fn postgres_label(code: &tokio_postgres::error::SqlState) -> Label {
+    match code.code() {
+        "42601" => Label::SYNTAX,
+        "0A000" => Label::parsed(EngineClass::Unsupported),
+        c if c.starts_with("54") => Label::parsed(EngineClass::Limit),
+        _ => Label::parsed(EngineClass::Semantic),
+    }
+}
+
 /// Whether a statement is `COPY ... TO STDOUT`: valid SQL whose result is streamed
 /// over the COPY sub-protocol, which the simple-query probe cannot consume (it
 /// breaks the connection with no SQLSTATE). A syntactically invalid COPY instead
@@ -229,8 +432,9 @@ fn is_copy_to_stdout(stmt: &str) -> bool {
 /// it parses (and name-resolves) without executing, so there are no side effects
 /// and nothing blocks. Invalid iff `PREPARE` fails with error 1064
 /// (ER_PARSE_ERROR). A missing table/column (1146/1054) or "unsupported in the
-/// prepared-statement protocol" (1295) means it parsed, so it is valid.
-async fn label_mysql(stmts: &[String]) -> Result<Vec<bool>> {
+/// prepared-statement protocol" (1295) means it parsed, so it is valid. The
+/// error code also gives the class ([`mysql_label`]).
+async fn label_mysql(stmts: &[String]) -> Result<Vec<Label>> {
     use mysql_async::prelude::Queryable;
     use testcontainers_modules::mysql::Mysql;
     use testcontainers_modules::testcontainers::runners::AsyncRunner;
@@ -258,12 +462,12 @@ async fn label_mysql(stmts: &[String]) -> Result<Vec<bool>> {
             Ok(()) => match conn.query_drop("PREPARE _ck FROM @q").await {
                 Ok(()) => {
                     let _ = conn.query_drop("DEALLOCATE PREPARE _ck").await;
-                    Some(true)
+                    Some(Label::VALID)
                 }
-                Err(mysql_async::Error::Server(e)) => Some(e.code != 1064),
+                Err(mysql_async::Error::Server(e)) => Some(mysql_label(e.code)),
                 Err(_) => None,
             },
-            Err(mysql_async::Error::Server(e)) => Some(e.code != 1064),
+            Err(mysql_async::Error::Server(e)) => Some(mysql_label(e.code)),
             Err(_) => None,
         };
         match verdict {
@@ -290,10 +494,25 @@ async fn label_mysql(stmts: &[String]) -> Result<Vec<bool>> {
     Ok(valid)
 }
 
+/// The label of a MySQL server error: 1064 (ER_PARSE_ERROR) is a syntax error,
+/// 1235 (ER_NOT_SUPPORTED_YET) an unsupported feature, and 1059, 1117 and 1436
+/// (identifier too long, too many columns, thread stack overrun) limits. 1295
+/// (ER_UNSUPPORTED_PS) is semantic like the rest: the statement parsed and only
+/// the prepared-statement protocol the oracle labels through refused it.
+fn mysql_label(code: u16) -> Label {
+    match code {
+        1064 => Label::SYNTAX,
+        1235 => Label::parsed(EngineClass::Unsupported),
+        1059 | 1117 | 1436 => Label::parsed(EngineClass::Limit),
+        _ => Label::parsed(EngineClass::Semantic),
+    }
+}
+
 /// ClickHouse: real server in a container, queried over HTTP. `EXPLAIN AST`
 /// parses only (no execution, no tables needed). Invalid iff the exception code
 /// is 62 (SYNTAX_ERROR). Any other code (unknown table/identifier, not
-/// implemented) means it parsed, so it is valid.
+/// implemented) means it parsed, so it is valid. The code also gives the class
+/// ([`clickhouse_label`]).
 ///
 /// Hardened on two fronts:
 ///
@@ -306,11 +525,11 @@ async fn label_mysql(stmts: &[String]) -> Result<Vec<bool>> {
 ///    container is restarted and labeling resumes from the same statement (each
 ///    `EXPLAIN AST` is independent, so a fresh engine yields identical verdicts).
 ///    A restart cap stops an unrecoverable engine from looping forever.
-async fn label_clickhouse(stmts: &[String]) -> Result<Vec<bool>> {
+async fn label_clickhouse(stmts: &[String]) -> Result<Vec<Label>> {
     use testcontainers_modules::clickhouse::ClickHouse;
     use testcontainers_modules::testcontainers::runners::AsyncRunner;
 
-    let mut valid: Vec<bool> = Vec::with_capacity(stmts.len());
+    let mut valid: Vec<Label> = Vec::with_capacity(stmts.len());
     let mut restarts = 0usize;
     let mut poisoned: Vec<usize> = Vec::new();
     // Track repeated deaths at one index: a statement that crashes the engine twice
@@ -364,7 +583,7 @@ async fn label_clickhouse(stmts: &[String]) -> Result<Vec<bool>> {
                             "  clickhouse: statement {i} repeatedly crashes the engine; marking invalid and skipping: {}",
                             stmts[i].chars().take(120).collect::<String>()
                         );
-                        valid.push(false);
+                        valid.push(Label::rejected(EngineClass::Limit));
                         poisoned.push(i);
                         death_idx = None;
                         death_count = 0;
@@ -397,11 +616,10 @@ async fn label_clickhouse(stmts: &[String]) -> Result<Vec<bool>> {
 /// Classify one ClickHouse `EXPLAIN AST` request, retrying transient transport
 /// failures. The response body is always fully read before returning, so a
 /// connection is never left mid-stream (the bug that desynced reused connections).
-/// `Some(true)` if the request succeeded (2xx) or failed with a non-syntax
-/// exception code (the statement parsed, the engine just could not resolve or
-/// execute it); `Some(false)` for exception code 62 (`SYNTAX_ERROR`) or an
-/// unclassifiable response; `None` if the engine was unreachable after retries.
-async fn clickhouse_classify(client: &reqwest::Client, url: &str, query: &str) -> Option<bool> {
+/// A valid label if the request succeeded (2xx), the [`clickhouse_label`] of
+/// its exception code if it failed, a syntax error for an unclassifiable
+/// response, and `None` if the engine was unreachable after retries.
+async fn clickhouse_classify(client: &reqwest::Client, url: &str, query: &str) -> Option<Label> {
     for attempt in 0..3 {
         match client.post(url).body(query.to_string()).send().await {
             Ok(resp) => {
@@ -413,13 +631,13 @@ async fn clickhouse_classify(client: &reqwest::Client, url: &str, query: &str) -
                     .and_then(|s| s.parse::<i32>().ok());
                 let body = resp.text().await.unwrap_or_default();
                 if success {
-                    return Some(true);
+                    return Some(Label::VALID);
                 }
-                return Some(match header_code.or_else(|| parse_clickhouse_code(&body)) {
-                    Some(62) => false,
-                    Some(_) => true,
-                    None => false,
-                });
+                return Some(
+                    header_code
+                        .or_else(|| parse_clickhouse_code(&body))
+                        .map_or(Label::SYNTAX, clickhouse_label),
+                );
             }
             Err(_) if attempt < 2 => {
                 tokio::time::sleep(std::time::Duration::from_millis(200)).await;
@@ -430,6 +648,20 @@ async fn clickhouse_classify(client: &reqwest::Client, url: &str, query: &str) -
     None
 }
 
+/// The label of a ClickHouse exception code: 62 (`SYNTAX_ERROR`) is a syntax
+/// error, 1, 48 and 344 (`UNSUPPORTED_METHOD`, `NOT_IMPLEMENTED`,
+/// `SUPPORT_IS_DISABLED`) unsupported features, and 167, 168 and 306
+/// (`TOO_DEEP_AST`, `TOO_BIG_AST`, `TOO_DEEP_RECURSION`) limits. Every other
+/// code comes after the parse.
+fn clickhouse_label(code: i32) -> Label {
+    match code {
+        62 => Label::SYNTAX,
+        1 | 48 | 344 => Label::parsed(EngineClass::Unsupported),
+        167 | 168 | 306 => Label::parsed(EngineClass::Limit),
+        _ => Label::parsed(EngineClass::Semantic),
+    }
+}
+
 /// Parse the leading exception code from a ClickHouse error body, e.g.
 /// `"Code: 62. DB::Exception: ..."` -> `Some(62)`.
 fn parse_clickhouse_code(body: &str) -> Option<i32> {
@@ -447,7 +679,7 @@ fn parse_clickhouse_code(body: &str) -> Option<i32> {
 /// every batch without compiling or executing it (and without resolving object
 /// names), so the only errors that can surface are syntax errors. A statement is
 /// therefore valid iff it runs without error.
-async fn label_tsql(stmts: &[String]) -> Result<Vec<bool>> {
+async fn label_tsql(stmts: &[String]) -> Result<Vec<Label>> {
     use testcontainers_modules::mssql_server::MssqlServer;
     use testcontainers_modules::testcontainers::runners::AsyncRunner;
     use tiberius::{AuthMethod, Client, Config};
@@ -492,11 +724,11 @@ async fn label_tsql(stmts: &[String]) -> Result<Vec<bool>> {
         // verdict, retry, and abort if the engine stays unreachable.
         let verdict = match client.simple_query(stmts[i].as_str()).await {
             Ok(stream) => match stream.into_results().await {
-                Ok(_) => Some(true),
-                Err(tiberius::error::Error::Server(_)) => Some(false),
+                Ok(_) => Some(Label::VALID),
+                Err(tiberius::error::Error::Server(_)) => Some(Label::SYNTAX),
                 Err(_) => None,
             },
-            Err(tiberius::error::Error::Server(_)) => Some(false),
+            Err(tiberius::error::Error::Server(_)) => Some(Label::SYNTAX),
             Err(_) => None,
         };
         match verdict {
@@ -521,20 +753,141 @@ async fn label_tsql(stmts: &[String]) -> Result<Vec<bool>> {
     Ok(valid)
 }
 
+/// Trino: real server in a container, queried over its HTTP client protocol.
+/// Each statement is wrapped in `EXPLAIN (TYPE VALIDATE)`, which parses and
+/// analyzes without executing. Invalid iff the query fails with
+/// `SYNTAX_ERROR`. Any other failure (an unknown catalog, table or function, or
+/// a statement kind `EXPLAIN` cannot wrap) comes after the parse, so it is
+/// valid.
+async fn label_trino(stmts: &[String]) -> Result<Vec<Label>> {
+    use testcontainers::core::IntoContainerPort;
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{GenericImage, ImageExt};
+
+    // Pinned for reproducible labels: bump deliberately, then regenerate the
+    // Trino cache.
+    let node = GenericImage::new("trinodb/trino", "476")
+        .with_exposed_port(8080.tcp())
+        .with_startup_timeout(std::time::Duration::from_secs(300))
+        .start()
+        .await
+        .context("start trino container")?;
+    let host = node.get_host().await?;
+    let port = node.get_host_port_ipv4(8080).await?;
+    let base = format!("http://{host}:{port}");
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .context("build trino http client")?;
+
+    // The container accepts connections well before the coordinator can run
+    // queries: wait until `/v1/info` stops reporting `"starting": true`.
+    let mut ready = false;
+    for _ in 0..300 {
+        if let Ok(resp) = client.get(format!("{base}/v1/info")).send().await {
+            let body = resp.text().await.unwrap_or_default();
+            let info: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+            if info.get("starting").and_then(serde_json::Value::as_bool) == Some(false) {
+                ready = true;
+                break;
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+    anyhow::ensure!(ready, "trino did not finish starting within 300s");
+
+    let mut valid = Vec::with_capacity(stmts.len());
+    let mut unreachable_streak = 0usize;
+    let mut i = 0;
+    while i < stmts.len() {
+        let query = format!(
+            "EXPLAIN (TYPE VALIDATE) {}",
+            stmts[i].trim().trim_end_matches(';')
+        );
+        match trino_classify(&client, &base, &query).await {
+            Some(v) => {
+                unreachable_streak = 0;
+                valid.push(v);
+                i += 1;
+                if i.is_multiple_of(2000) {
+                    eprintln!("  trino {i}/{}", stmts.len());
+                }
+            }
+            None => {
+                unreachable_streak += 1;
+                anyhow::ensure!(
+                    unreachable_streak < 10,
+                    "trino became unreachable at statement {i}; aborting without writing a label cache"
+                );
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            }
+        }
+    }
+    Ok(valid)
+}
+
+/// Run one query through Trino's client protocol: submit it, then follow
+/// `nextUri` until the query finishes. A valid label if it finished, the
+/// [`trino_label`] of its error if it failed, and `None` if the server could
+/// not be reached or answered with something that is not a protocol response
+/// (never guessed as a verdict).
+async fn trino_classify(client: &reqwest::Client, base: &str, query: &str) -> Option<Label> {
+    let mut next = client
+        .post(format!("{base}/v1/statement"))
+        .header("X-Trino-User", "oracle")
+        .body(query.to_string())
+        .send()
+        .await
+        .ok()?;
+    loop {
+        if !next.status().is_success() {
+            return None;
+        }
+        let body = next.text().await.ok()?;
+        let page: serde_json::Value = serde_json::from_str(&body).ok()?;
+        if let Some(error) = page.get("error") {
+            let field = |k: &str| error.get(k).and_then(serde_json::Value::as_str);
+            return Some(trino_label(
+                field("errorName").unwrap_or(""),
+                field("errorType").unwrap_or(""),
+            ));
+        }
+        let Some(uri) = page.get("nextUri").and_then(serde_json::Value::as_str) else {
+            return Some(Label::VALID);
+        };
+        next = client.get(uri).send().await.ok()?;
+    }
+}
+
+/// The label of a failed Trino query by its error name and type: only
+/// `SYNTAX_ERROR` is a rejection. An `INSUFFICIENT_RESOURCES` error, an
+/// oversized query text or too many arguments is a limit. `NOT_SUPPORTED` is
+/// semantic like the rest: Trino raises it from analysis, after the statement
+/// parsed, often for a statement `EXPLAIN (TYPE VALIDATE)` cannot validate.
+fn trino_label(name: &str, kind: &str) -> Label {
+    match name {
+        "SYNTAX_ERROR" => Label::SYNTAX,
+        "QUERY_TEXT_TOO_LARGE" | "TOO_MANY_ARGUMENTS" => Label::parsed(EngineClass::Limit),
+        _ if kind == "INSUFFICIENT_RESOURCES" => Label::parsed(EngineClass::Limit),
+        _ => Label::parsed(EngineClass::Semantic),
+    }
+}
+
 /// DuckDB: real engine via the in-process `duckdb` crate (the actual libduckdb).
 /// DuckDB has no server, and its CLI errors carry no line numbers (so the
 /// container batch-correlation used for SQLite is unreliable), so we link the
 /// real engine directly. `prepare` parses and binds without executing. A
 /// "Parser Error" is a syntax error (invalid), while a "Binder"/"Catalog Error"
-/// (unknown table or column) means it parsed, so it is valid.
-fn label_duckdb(stmts: &[String]) -> Result<Vec<bool>> {
+/// (unknown table or column) means it parsed, so it is valid. The error type
+/// also gives the class ([`duckdb_label`]).
+fn label_duckdb(stmts: &[String]) -> Result<Vec<Label>> {
     let conn = duckdb::Connection::open_in_memory().context("open duckdb")?;
     let mut valid = Vec::with_capacity(stmts.len());
     for (i, s) in stmts.iter().enumerate() {
         let stmt = s.trim().trim_end_matches(';');
         let v = match conn.prepare(stmt) {
-            Ok(_) => true,
-            Err(e) => !e.to_string().contains("Parser Error"),
+            Ok(_) => Label::VALID,
+            Err(e) => duckdb_label(&e.to_string()),
         };
         valid.push(v);
         if i % 5000 == 0 {
@@ -544,10 +897,31 @@ fn label_duckdb(stmts: &[String]) -> Result<Vec<bool>> {
     Ok(valid)
 }
 
+/// The label of a DuckDB prepare error by its type prefix: a "Parser Error" is
+/// a rejection (a limit when it reports the recursion depth), "Not implemented
+/// Error" an unsupported feature, "Out of Memory Error" a limit, and anything
+/// else (binder, catalog) a semantic error.
+fn duckdb_label(msg: &str) -> Label {
+    if msg.contains("Parser Error") {
+        if msg.contains("recursion depth") {
+            Label::rejected(EngineClass::Limit)
+        } else {
+            Label::SYNTAX
+        }
+    } else if msg.contains("Not implemented Error") {
+        Label::parsed(EngineClass::Unsupported)
+    } else if msg.contains("Out of Memory Error") {
+        Label::parsed(EngineClass::Limit)
+    } else {
+        Label::parsed(EngineClass::Semantic)
+    }
+}
+
 /// SQLite: real engine via the `sqlite3` CLI in a one-shot container. We feed a
 /// script of `EXPLAIN <stmt>;` (compiles, does not execute, so no side effects)
 /// and read stderr. `EXPLAIN` resolves names, so "no such table/column" surfaces
 /// as a non-syntax error (valid). Only a syntax error makes a statement invalid.
+/// Each error message is classed by [`sqlite_label`].
 ///
 /// A statement that does not close its own quotes is graded invalid up front and
 /// kept out of the batch: it is not valid SQL, and an unterminated quote in the
@@ -555,14 +929,14 @@ fn label_duckdb(stmts: &[String]) -> Result<Vec<bool>> {
 /// one string literal, silently grading the lot valid (the masking that a single
 /// malformed corpus line once caused). `batch[k]` maps the k-th batched script
 /// line back to its original statement index.
-fn label_sqlite(stmts: &[String]) -> Result<Vec<bool>> {
-    let mut valid = vec![true; stmts.len()];
+fn label_sqlite(stmts: &[String]) -> Result<Vec<Label>> {
+    let mut valid = vec![Label::VALID; stmts.len()];
     // Script line 1 is `.bail off`; the k-th batched statement is on line k + 2.
     let mut script = String::from(".bail off\n");
     let mut batch: Vec<usize> = Vec::with_capacity(stmts.len());
     for (idx, s) in stmts.iter().enumerate() {
         if !is_sqlite_balanced(s) {
-            valid[idx] = false;
+            valid[idx] = Label::SYNTAX;
             continue;
         }
         script.push_str("EXPLAIN ");
@@ -618,8 +992,8 @@ fn label_sqlite(stmts: &[String]) -> Result<Vec<bool>> {
         if let Some((lineno, msg)) = parse_sqlite_err(line) {
             if lineno >= 2 {
                 let k = lineno - 2;
-                if k < batch.len() && is_sqlite_invalid(msg) {
-                    valid[batch[k]] = false;
+                if k < batch.len() {
+                    valid[batch[k]] = sqlite_label(msg);
                 }
             }
         }
@@ -654,6 +1028,27 @@ fn is_sqlite_invalid(msg: &str) -> bool {
     !(m.contains("no such") || m.contains("ambiguous column") || m.contains("unknown database"))
 }
 
+/// The label of a sqlite3 prepare error: valid-but-semantic when
+/// [`is_sqlite_invalid`] lets it through, otherwise a rejection classed by its
+/// message ("not supported" forms, then "too many"/"too large"/"too big" and
+/// stack overflows as limits, and the rest syntax).
+fn sqlite_label(msg: &str) -> Label {
+    if !is_sqlite_invalid(msg) {
+        return Label::parsed(EngineClass::Semantic);
+    }
+    let m = msg.to_ascii_lowercase();
+    if m.contains("not supported") || m.contains("not currently supported") {
+        Label::rejected(EngineClass::Unsupported)
+    } else if ["too many", "too large", "too big", "stack overflow"]
+        .iter()
+        .any(|w| m.contains(w))
+    {
+        Label::rejected(EngineClass::Limit)
+    } else {
+        Label::SYNTAX
+    }
+}
+
 /// Whether a statement closes every string/identifier quote and block comment it
 /// opens, per SQLite lexing: `'..'`/`".."`/`` `..` `` with doubling escapes,
 /// `[..]` (no escape), `/* .. */`; backslash is an ordinary character. An
@@ -721,9 +1116,11 @@ fn is_sqlite_balanced(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::{
-        is_copy_to_stdout, is_sqlite_balanced, is_sqlite_invalid, parse_clickhouse_code,
-        parse_sqlite_err,
+        clickhouse_label, duckdb_label, is_copy_to_stdout, is_sqlite_balanced, is_sqlite_invalid,
+        mysql_label, parse_clickhouse_code, parse_sqlite_err, postgres_label, sqlite_label,
+        trino_label, EngineClass, Label,
     };
+    use tokio_postgres::error::SqlState;
 
     #[test]
     fn sqlite_balance_guard_excludes_swallowing_statements() {
@@ -798,4 +1195,66 @@ mod tests {
         );
         assert_eq!(parse_sqlite_err("just some output"), None);
     }
+
+    #[test]
+    fn engine_errors_are_classed_by_code() {
+        let pg = |c: &str| postgres_label(&SqlState::from_code(c));
+        assert_eq!(pg("42601"), Label::SYNTAX);
+        assert_eq!(pg("0A000"), Label::parsed(EngineClass::Unsupported));
+        assert_eq!(pg("54001"), Label::parsed(EngineClass::Limit));
+        assert_eq!(pg("42P01"), Label::parsed(EngineClass::Semantic));
+        assert_eq!(mysql_label(1064), Label::SYNTAX);
+        assert_eq!(mysql_label(1235), Label::parsed(EngineClass::Unsupported));
+        assert_eq!(mysql_label(1295), Label::parsed(EngineClass::Semantic));
+        assert_eq!(mysql_label(1146), Label::parsed(EngineClass::Semantic));
+        assert_eq!(clickhouse_label(62), Label::SYNTAX);
+        assert_eq!(
+            clickhouse_label(48),
+            Label::parsed(EngineClass::Unsupported)
+        );
+        assert_eq!(clickhouse_label(167), Label::parsed(EngineClass::Limit));
+        assert_eq!(clickhouse_label(47), Label::parsed(EngineClass::Semantic));
+        assert_eq!(trino_label("SYNTAX_ERROR", "USER_ERROR"), Label::SYNTAX);
+        assert_eq!(
+            trino_label("NOT_SUPPORTED", "USER_ERROR"),
+            Label::parsed(EngineClass::Semantic)
+        );
+        assert_eq!(
+            trino_label("EXCEEDED_LOCAL_MEMORY_LIMIT", "INSUFFICIENT_RESOURCES"),
+            Label::parsed(EngineClass::Limit)
+        );
+        assert_eq!(
+            trino_label("CATALOG_NOT_FOUND", "USER_ERROR"),
+            Label::parsed(EngineClass::Semantic)
+        );
+    }
+
+    #[test]
+    fn engine_errors_are_classed_by_message() {
+        assert_eq!(
+            sqlite_label("no such table: t"),
+            Label::parsed(EngineClass::Semantic)
+        );
+        assert_eq!(sqlite_label("near \"FROM\": syntax error"), Label::SYNTAX);
+        assert_eq!(
+            sqlite_label("RIGHT and FULL OUTER JOINs are not currently supported"),
+            Label::rejected(EngineClass::Unsupported)
+        );
+        assert_eq!(
+            sqlite_label("too many terms in compound SELECT"),
+            Label::rejected(EngineClass::Limit)
+        );
+        assert_eq!(
+            duckdb_label("Parser Error: syntax error at or near \"FORM\""),
+            Label::SYNTAX
+        );
+        assert_eq!(
+            duckdb_label("Not implemented Error: COPY FROM DATABASE"),
+            Label::parsed(EngineClass::Unsupported)
+        );
+        assert_eq!(
+            duckdb_label("Catalog Error: Table with name t does not exist!"),
+            Label::parsed(EngineClass::Semantic)
+        );
+    }
 }
@@ -46,14 +46,27 @@ pub fn reports_statement_count(mut count: impl FnMut(&str) -> usize) -> bool {
 /// statement between terminators, and the last statement gets no terminator.
 #[must_use]
 pub fn join_batch(stmts: &[&str]) -> String {
-    let mut out = String::with_capacity(stmts.iter().map(|s| s.len() + 3).sum());
+    join_batch_with(stmts, ";").0
+}
+
+/// [`join_batch`] with an arbitrary terminator (`//` in a `DELIMITER` block).
+///
+/// Also returns the byte offset of each terminator placed: the statement
+/// boundaries the script is known to have.
+#[must_use]
+pub fn join_batch_with(stmts: &[&str], terminator: &str) -> (String, Vec<usize>) {
+    let mut out = String::with_capacity(stmts.iter().map(|s| s.len() + terminator.len() + 2).sum());
+    let mut at = Vec::with_capacity(stmts.len().saturating_sub(1));
     for (i, s) in stmts.iter().enumerate() {
         if i > 0 {
-            out.push_str("\n;\n");
+            out.push('\n');
+            at.push(out.len());
+            out.push_str(terminator);
+            out.push('\n');
         }
         out.push_str(s.trim().trim_end_matches(';').trim_end());
     }
-    out
+    (out, at)
 }
 
 /// Whether a statement is safe to place in a concatenated batch script.
@@ -76,14 +89,15 @@ pub fn batch_eligible(stmt: &str) -> bool {
 
 /// Deterministic `SplitMix64`. Used to sample batches reproducibly without
 /// pulling in an RNG dependency (the rest of the benchmark is deterministic).
-struct SplitMix64(u64);
+/// The fuzzer ([`crate::fuzz`]) draws its mutations from it too.
+pub(crate) struct SplitMix64(u64);
 
 impl SplitMix64 {
-    const fn new(seed: u64) -> Self {
+    pub(crate) const fn new(seed: u64) -> Self {
         Self(seed)
     }
 
-    const fn next_u64(&mut self) -> u64 {
+    pub(crate) const fn next_u64(&mut self) -> u64 {
         self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
         let mut z = self.0;
         z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
@@ -92,7 +106,7 @@ impl SplitMix64 {
     }
 
     /// Uniform-ish index in `0..n` (n > 0). Modulo bias is negligible here.
-    const fn below(&mut self, n: usize) -> usize {
+    pub(crate) const fn below(&mut self, n: usize) -> usize {
         (self.next_u64() % n as u64) as usize
     }
 }
@@ -210,6 +224,14 @@ mod tests {
         assert!(joined.contains("\n;\n"));
     }
 
+    #[test]
+    fn join_with_reports_where_each_terminator_sits() {
+        let (script, at) = join_batch_with(&["SELECT 1;", "SELECT 2", "SELECT 3"], "//");
+        assert_eq!(script, "SELECT 1\n//\nSELECT 2\n//\nSELECT 3");
+        assert_eq!(at, vec![9, 21]);
+        assert!(at.iter().all(|&i| script[i..].starts_with("//")));
+    }
+
     #[test]
     fn single_statement_has_no_terminator() {
         assert_eq!(join_batch(&["SELECT 1"]), "SELECT 1");
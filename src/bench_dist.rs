@@ -2,7 +2,9 @@
 //!
 //! The timings live in `target/bench_dist/` and are consumed by the JSON
 //! [`crate::export`] step that feeds the web explorer. One file per
-//! `(dialect, parser)`, one ns value per line.
+//! `(dialect, parser)`, one ns value per line. The main parse-time file also
+//! carries each statement's byte length and token count after the time,
+//! tab-separated ([`load_sized`]).
 
 use crate::stats::slug;
 use std::fs;
@@ -30,6 +32,37 @@ pub fn load_mem(dialect: &str, parser: &str, kind: &str) -> Vec<f64> {
         .unwrap_or_default()
 }
 
+/// Ascending-sorted lex-only ns timings for one `(dialect, parser)`, from
+/// `target/bench_dist/{dialect}__{slug}.lex.txt` (empty if absent).
+#[must_use]
+pub fn load_lex(dialect: &str, parser: &str) -> Vec<f64> {
+    let path = format!("{DIST_DIR}/{dialect}__{}.lex.txt", slug(parser));
+    fs::read_to_string(path)
+        .map(|c| parse_times(&c))
+        .unwrap_or_default()
+}
+
+/// Ascending-sorted edit-reparse ns timings for one `(dialect, parser, kind)`,
+/// from `target/bench_dist/{dialect}__{slug}.edit.{kind}.txt` (empty if absent).
+#[must_use]
+pub fn load_edit(dialect: &str, parser: &str, kind: &str) -> Vec<f64> {
+    let path = format!("{DIST_DIR}/{dialect}__{}.edit.{kind}.txt", slug(parser));
+    fs::read_to_string(path)
+        .map(|c| parse_times(&c))
+        .unwrap_or_default()
+}
+
+/// Ascending-sorted parse ns timings of one statement kind for one
+/// `(dialect, parser)`, from `target/bench_dist/{dialect}__{slug}.kind.{kind}.txt`
+/// (empty if absent).
+#[must_use]
+pub fn load_kind(dialect: &str, parser: &str, kind: &str) -> Vec<f64> {
+    let path = format!("{DIST_DIR}/{dialect}__{}.kind.{kind}.txt", slug(parser));
+    fs::read_to_string(path)
+        .map(|c| parse_times(&c))
+        .unwrap_or_default()
+}
+
 /// Ascending-sorted ns timings for one `(dialect, parser)` from its raw
 /// `target/bench_dist/{dialect}__{slug}.txt` file (empty if absent).
 #[must_use]
@@ -40,12 +73,60 @@ pub fn load_times(dialect: &str, parser: &str) -> Vec<f64> {
         .unwrap_or_default()
 }
 
+/// Ascending-sorted parse ns timings over the dialect's common subset (see
+/// [`crate::common`]) for one `(dialect, parser)`, from
+/// `target/bench_dist/{dialect}__{slug}.common.txt` (empty if absent).
+#[must_use]
+pub fn load_common(dialect: &str, parser: &str) -> Vec<f64> {
+    let path = format!("{DIST_DIR}/{dialect}__{}.common.txt", slug(parser));
+    fs::read_to_string(path)
+        .map(|c| parse_times(&c))
+        .unwrap_or_default()
+}
+
+/// One timed statement with its size, in bench order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizedTime {
+    pub ns: f64,
+    pub bytes: usize,
+    pub tokens: usize,
+}
+
+/// Per-statement parse times with their statement sizes, in bench order.
+///
+/// Read for one `(dialect, parser)` from the raw
+/// `target/bench_dist/{dialect}__{slug}.txt` file. Empty if absent or written
+/// before sizes were recorded.
+#[must_use]
+pub fn load_sized(dialect: &str, parser: &str) -> Vec<SizedTime> {
+    let path = format!("{DIST_DIR}/{dialect}__{}.txt", slug(parser));
+    fs::read_to_string(path)
+        .map(|c| parse_sized(&c))
+        .unwrap_or_default()
+}
+
+/// Parse `ns\tbytes\ttokens` lines, dropping lines without sizes and
+/// non-positive times.
+fn parse_sized(content: &str) -> Vec<SizedTime> {
+    content
+        .lines()
+        .filter_map(|l| {
+            let mut f = l.split_whitespace();
+            let ns: f64 = f.next()?.parse().ok()?;
+            let bytes = f.next()?.parse().ok()?;
+            let tokens = f.next()?.parse().ok()?;
+            (ns > 0.0).then_some(SizedTime { ns, bytes, tokens })
+        })
+        .collect()
+}
+
 /// Parse one-value-per-line ns timings: drop blanks/unparsable/non-positive,
-/// return ascending-sorted.
+/// return ascending-sorted. Only the first field of a line is read, so the
+/// sized parse-time file loads too.
 fn parse_times(content: &str) -> Vec<f64> {
     let mut v: Vec<f64> = content
         .lines()
-        .filter_map(|l| l.trim().parse::<f64>().ok())
+        .filter_map(|l| l.split_whitespace().next()?.parse::<f64>().ok())
         .filter(|x| *x > 0.0)
         .collect();
     v.sort_by(|a, b| a.partial_cmp(b).unwrap());
@@ -54,7 +135,7 @@ fn parse_times(content: &str) -> Vec<f64> {
 
 #[cfg(test)]
 mod tests {
-    use super::{load_times, parse_times};
+    use super::{load_times, parse_sized, parse_times, SizedTime};
 
     #[test]
     fn parse_times_drops_junk_and_sorts() {
@@ -62,6 +143,28 @@ mod tests {
         assert_eq!(v, vec![10.0, 20.0, 30.0]);
     }
 
+    #[test]
+    fn sized_lines_keep_their_sizes_and_bench_order() {
+        let content = "30.5\t120\t24\n10\t40\t8\n7\n0\t5\t1\n";
+        assert_eq!(
+            parse_sized(content),
+            [
+                SizedTime {
+                    ns: 30.5,
+                    bytes: 120,
+                    tokens: 24
+                },
+                SizedTime {
+                    ns: 10.0,
+                    bytes: 40,
+                    tokens: 8
+                },
+            ]
+        );
+        // The time column alone still loads as a plain sample.
+        assert_eq!(parse_times(content), vec![7.0, 10.0, 30.5]);
+    }
+
     #[test]
     fn parse_times_empty_input() {
         assert!(parse_times("").is_empty());
@@ -7,12 +7,12 @@
 //! Spark's own harness wraps any statement that contains inner `;` (the scripting
 //! `BEGIN ... END` blocks) in `--QUERY-DELIMITER-START` / `--QUERY-DELIMITER-END`
 //! markers, so we honor those: text between a marker pair is one statement,
-//! everything else splits on `;`.
+//! everything else splits on `;`. That is the shared Spark splitter in `ingest`.
 //!
 //! Oracle source: oracle-samples/db-sample-schemas. These are SQL*Plus scripts:
 //! a PL/SQL block (`DECLARE`/`BEGIN`/`CREATE ... PROCEDURE|FUNCTION|PACKAGE|
 //! TRIGGER|TYPE`) runs until a line containing only `/`; every other statement
-//! ends at `;`.
+//! ends at `;`. That is the shared SQL*Plus splitter in `ingest`.
 //!
 //!   cargo run --release --bin build_proc_suites -- <spark inputs dir> <oracle schemas dir>
 //!
@@ -28,141 +28,15 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-/// Collapse a raw statement to one trimmed line (drops comments already removed).
-fn normalize(s: &str) -> String {
-    s.split_whitespace().collect::<Vec<_>>().join(" ")
-}
-
-/// Copy a quoted literal verbatim from `chars[i..]` into `buf`, returning the
-/// index just past the closing quote. Handles `'`, `"`, backtick (doubling
-/// escape) and `[` (closed by `]`, no escape).
-fn copy_quote(chars: &[char], mut i: usize, buf: &mut String) -> usize {
-    let open = chars[i];
-    let close = if open == '[' { ']' } else { open };
-    buf.push(open);
-    i += 1;
-    while i < chars.len() {
-        let d = chars[i];
-        if d == close {
-            if close != ']' && chars.get(i + 1) == Some(&close) {
-                buf.push(d);
-                buf.push(d);
-                i += 2;
-                continue;
-            }
-            buf.push(d);
-            return i + 1;
-        }
-        buf.push(d);
-        i += 1;
-    }
-    i
-}
+use sql_ast_benchmark::datasets::Dialect;
+use sql_ast_benchmark::ingest::{source_files, Blocks, Directives, Splitter};
 
 /// Split Spark golden-test SQL into statements, honoring `--QUERY-DELIMITER`
 /// regions (one statement each) and otherwise splitting on top-level `;`. Lines
 /// that are pure directive comments (`--CONFIG`, `--SET`, `--IMPORT`, ...) are
 /// dropped; trailing `--` and `/* */` comments are stripped.
 fn split_spark(input: &str) -> Vec<String> {
-    let mut out = Vec::new();
-    let mut buf = String::new();
-    let mut region = false;
-
-    for raw_line in input.lines() {
-        let trimmed = raw_line.trim_start();
-        if trimmed.starts_with("--QUERY-DELIMITER-START") {
-            region = true;
-            continue;
-        }
-        if trimmed.starts_with("--QUERY-DELIMITER-END") {
-            let s = normalize(&buf);
-            if !s.is_empty() {
-                out.push(s);
-            }
-            buf.clear();
-            region = false;
-            continue;
-        }
-        if region {
-            // Whole region is one statement; keep code, drop full-line comments.
-            if !trimmed.starts_with("--") {
-                strip_line_into(raw_line, &mut buf, &mut Vec::new(), true);
-                buf.push(' ');
-            }
-            continue;
-        }
-        if trimmed.starts_with("--") {
-            continue; // directive / comment line
-        }
-        // Normal line: split on `;`, stripping inline comments and quotes.
-        strip_line_into(raw_line, &mut buf, &mut out, false);
-        buf.push(' ');
-    }
-    let s = normalize(&buf);
-    if !s.is_empty() {
-        out.push(s);
-    }
-    out
-}
-
-/// Append `line` to `buf`, stripping comments and copying quotes verbatim. When
-/// `region_only` is false, a top-level `;` flushes `buf` (normalized) into `out`.
-fn strip_line_into(line: &str, buf: &mut String, out: &mut Vec<String>, region_only: bool) {
-    let chars: Vec<char> = line.chars().collect();
-    let mut i = 0;
-    while i < chars.len() {
-        let c = chars[i];
-        if c == '-' && chars.get(i + 1) == Some(&'-') {
-            break; // rest of line is a comment
-        }
-        if c == '/' && chars.get(i + 1) == Some(&'*') {
-            i += 2;
-            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
-                i += 1;
-            }
-            i += 2;
-            continue;
-        }
-        if matches!(c, '\'' | '"' | '`' | '[') {
-            i = copy_quote(&chars, i, buf);
-            continue;
-        }
-        if c == ';' && !region_only {
-            let s = normalize(buf);
-            if !s.is_empty() {
-                out.push(s);
-            }
-            buf.clear();
-            i += 1;
-            continue;
-        }
-        buf.push(c);
-        i += 1;
-    }
-}
-
-/// Split a comment-free string on top-level `;`, respecting quoted literals.
-fn split_semicolons(s: &str) -> Vec<String> {
-    let chars: Vec<char> = s.chars().collect();
-    let mut out = Vec::new();
-    let mut buf = String::new();
-    let mut i = 0;
-    while i < chars.len() {
-        let c = chars[i];
-        if matches!(c, '\'' | '"' | '`' | '[') {
-            i = copy_quote(&chars, i, &mut buf);
-            continue;
-        }
-        if c == ';' {
-            out.push(std::mem::take(&mut buf));
-            i += 1;
-            continue;
-        }
-        buf.push(c);
-        i += 1;
-    }
-    out.push(buf);
-    out
+    Splitter::for_dialect(Dialect::SparkSql).split(input)
 }
 
 /// Harvest the standalone DML statements from inside a PL/SQL block, so the bulk
@@ -170,9 +44,14 @@ fn split_semicolons(s: &str) -> Vec<String> {
 /// leading `BEGIN` glued to the first inner statement is stripped. Non-DML pieces
 /// (declarations, control flow, BEGIN/END) are dropped.
 fn harvest_dml(block: &str) -> Vec<String> {
+    // Every `;` in the block, quotes aside, ends a piece.
+    let pieces = Splitter {
+        blocks: Blocks::None,
+        directives: Directives::None,
+        ..Splitter::for_dialect(Dialect::Oracle)
+    };
     let mut out = Vec::new();
-    for piece in split_semicolons(block) {
-        let mut p = normalize(&piece);
+    for mut p in pieces.split(block) {
         if let Some(rest) = p
             .strip_prefix("BEGIN ")
             .or_else(|| p.strip_prefix("begin "))
@@ -196,157 +75,22 @@ fn harvest_dml(block: &str) -> Vec<String> {
 
 /// Split Oracle SQL*Plus script text into `(normal, special)`: normal per-statement
 /// corpus entries, and special whole PL/SQL anonymous blocks (kept once, isolated
-/// from the per-statement metrics). A `/` line ends a block; `;` ends other
-/// statements. Anonymous `DECLARE`/`BEGIN` blocks go to `special`, and their inner
-/// DML is also harvested into `normal`; `CREATE ... PROCEDURE/...` blocks are kept
-/// whole in `normal` (real DDL statements).
+/// from the per-statement metrics). The shared SQL*Plus splitter cuts the
+/// statements; anonymous `DECLARE`/`BEGIN` blocks go to `special`, and their
+/// inner DML is also harvested into `normal`; `CREATE ... PROCEDURE/...` blocks
+/// are kept whole in `normal` (real DDL statements).
 fn split_oracle(input: &str) -> (Vec<String>, Vec<String>) {
     let mut normal = Vec::new();
     let mut special = Vec::new();
-    let mut buf = String::new();
-    let mut in_block = false;
-    let mut anon = false;
-    let mut started = false;
-
-    for raw_line in input.lines() {
-        let trimmed = raw_line.trim();
-        // SQL*Plus block terminator: end the current PL/SQL block.
-        if trimmed == "/" {
-            let s = normalize(&buf);
-            if !s.is_empty() {
-                if anon {
-                    special.push(s);
-                    normal.extend(harvest_dml(&buf));
-                } else {
-                    normal.push(s);
-                }
-            }
-            buf.clear();
-            in_block = false;
-            anon = false;
-            started = false;
-            continue;
-        }
-        // Skip pure comment lines and SQL*Plus client directives (REM, PROMPT,
-        // SET, ACCEPT, etc.) when no statement is in progress. These are not SQL
-        // and, left in the buffer, would also set `started` and mask a following
-        // `BEGIN` block opener (the ACCEPT ... HIDE / BEGIN IF ... pattern).
-        if buf.trim().is_empty() {
-            let up = trimmed.to_ascii_uppercase();
-            // Leading SQL*Plus command words (skip the whole line when one starts it).
-            const DIRECTIVES: &[&str] = &[
-                "PROMPT",
-                "SET ",
-                "DEFINE",
-                "UNDEFINE",
-                "SPOOL",
-                "WHENEVER",
-                "CONNECT",
-                "ALTER SESSION",
-                "COLUMN ",
-                "ACCEPT ",
-                "PAUSE",
-                "EXEC ",
-                "EXECUTE ",
-                "VARIABLE ",
-                "VAR ",
-                "PRINT ",
-                "SHOW ",
-                "BREAK",
-                "COMPUTE ",
-                "TTITLE",
-                "BTITLE",
-                "STORE ",
-                "SAVE ",
-                "HOST",
-                "CLEAR ",
-                "TIMING",
-                "START ",
-                "ACCEPT",
-            ];
-            if trimmed.is_empty()
-                || trimmed.starts_with("--")
-                || trimmed.starts_with('@')
-                || up.starts_with("REM ")
-                || up == "REM"
-                || DIRECTIVES.iter().any(|d| up.starts_with(d))
-            {
-                continue;
-            }
-        }
-
-        let chars: Vec<char> = raw_line.chars().collect();
-        let mut i = 0;
-        while i < chars.len() {
-            let c = chars[i];
-            if c == '-' && chars.get(i + 1) == Some(&'-') {
-                break;
-            }
-            if c == '/' && chars.get(i + 1) == Some(&'*') {
-                i += 2;
-                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
-                    i += 1;
-                }
-                i += 2;
-                continue;
-            }
-            if matches!(c, '\'' | '"' | '`' | '[') {
-                i = copy_quote(&chars, i, &mut buf);
-                started = true;
-                continue;
-            }
-            if (c.is_alphanumeric() || c == '_') && !started && c.is_alphabetic() {
-                // Detect the leading keyword to decide block vs simple.
-                let mut j = i;
-                while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
-                    j += 1;
-                }
-                let w: String = chars[i..j].iter().collect::<String>().to_ascii_uppercase();
-                if w == "DECLARE" || w == "BEGIN" {
-                    in_block = true;
-                    anon = true;
-                }
-                started = true;
-                // fall through to copy chars normally below
-            }
-            // Once inside a CREATE statement, promote to block on a body keyword.
-            if c.is_alphabetic() {
-                let mut j = i;
-                while j < chars.len() && (chars[j].is_alphanumeric() || chars[j] == '_') {
-                    j += 1;
-                }
-                let w: String = chars[i..j].iter().collect::<String>().to_ascii_uppercase();
-                if matches!(
-                    w.as_str(),
-                    "PROCEDURE" | "FUNCTION" | "PACKAGE" | "TRIGGER" | "TYPE"
-                ) && buf.to_ascii_uppercase().trim_start().starts_with("CREATE")
-                {
-                    in_block = true;
-                }
-                buf.push_str(&chars[i..j].iter().collect::<String>());
-                i = j;
-                continue;
-            }
-            if c == ';' && !in_block {
-                let s = normalize(&buf);
-                if !s.is_empty() {
-                    normal.push(s);
-                }
-                buf.clear();
-                started = false;
-                i += 1;
-                continue;
-            }
-            buf.push(c);
-            i += 1;
-        }
-        buf.push(' ');
-    }
-    let s = normalize(&buf);
-    if !s.is_empty() {
-        if anon {
+    for s in Splitter::for_dialect(Dialect::Oracle).split(input) {
+        let first = s
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .next()
+            .unwrap_or("")
+            .to_ascii_uppercase();
+        if first == "DECLARE" || first == "BEGIN" {
+            normal.extend(harvest_dml(&s));
             special.push(s);
-            normal.extend(harvest_dml(&buf));
         } else {
             normal.push(s);
         }
@@ -354,24 +98,12 @@ fn split_oracle(input: &str) -> (Vec<String>, Vec<String>) {
     (normal, special)
 }
 
-fn sql_files(dir: &Path) -> Vec<PathBuf> {
-    let mut out = Vec::new();
-    let mut stack = vec![dir.to_path_buf()];
-    while let Some(d) = stack.pop() {
-        let Ok(entries) = fs::read_dir(&d) else {
-            continue;
-        };
-        for e in entries.flatten() {
-            let p = e.path();
-            if p.is_dir() {
-                stack.push(p);
-            } else if p.extension().is_some_and(|x| x == "sql") {
-                out.push(p);
-            }
-        }
-    }
-    out.sort();
-    out
+/// The `.sql` scripts under `dir`, recursively, in path order.
+fn sql_scripts(dir: &Path) -> Vec<PathBuf> {
+    source_files(dir)
+        .into_iter()
+        .filter(|p| p.extension().is_some_and(|x| x == "sql"))
+        .collect()
 }
 
 /// Load the lines of `datasets/<dialect>/<file>` into `seen` (for cross-file dedup).
@@ -391,7 +123,7 @@ fn build_spark(src: &Path) {
     seed_seen(&mut seen, "spark_sql/databricks_perf.txt");
     let mut kept = Vec::new();
     let mut total = 0usize;
-    for f in sql_files(src) {
+    for f in sql_scripts(src) {
         for s in split_spark(&fs::read_to_string(&f).unwrap_or_default()) {
             total += 1;
             if seen.insert(s.clone()) {
@@ -417,7 +149,7 @@ fn build_oracle(src: &Path) {
     let mut special_seen = HashSet::new();
     let mut special_kept = Vec::new();
     let (mut n_total, mut s_total) = (0usize, 0usize);
-    for f in sql_files(src) {
+    for f in sql_scripts(src) {
         let (normal, special) = split_oracle(&fs::read_to_string(&f).unwrap_or_default());
         for s in normal {
             n_total += 1;
@@ -21,6 +21,8 @@
 //! unterminated string, silently grading them valid. Extracting the TCL test
 //! bodies ourselves and re-splitting with our own tokenizer (which treats
 //! backslash as an ordinary character, per real SQLite) removes that at the root.
+//! The extractor and the splitter are the shared ones in `ingest`, so a new
+//! corpus source added with the `ingest` binary is split the same way.
 //!
 //! Clone the SQLite source at a release tag and pass its `test/` directory:
 //!
@@ -40,6 +42,9 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use sql_ast_benchmark::datasets::Dialect;
+use sql_ast_benchmark::ingest::{tcl_sql_bodies, Splitter};
+
 /// Split raw SQLite script text into normalized one-line statements.
 ///
 /// Splits on top-level `;` only: semicolons inside single/double/backtick/bracket
@@ -48,198 +53,9 @@ use std::path::Path;
 /// single line (whitespace runs collapsed) with comments removed.
 #[must_use]
 fn split_sql(input: &str) -> Vec<String> {
-    let mut out = Vec::new();
-    let mut buf = String::new();
-    let mut word = String::new();
-    let mut case_depth = 0usize;
-    let mut block_depth = 0usize;
-    let mut is_trigger = false;
-
-    // Apply a completed word's effect on block/case tracking.
-    fn classify(
-        word: &mut String,
-        case_depth: &mut usize,
-        block_depth: &mut usize,
-        is_trigger: &mut bool,
-    ) {
-        if word.is_empty() {
-            return;
-        }
-        match word.to_ascii_uppercase().as_str() {
-            "TRIGGER" => *is_trigger = true,
-            "CASE" => *case_depth += 1,
-            "END" => {
-                if *case_depth > 0 {
-                    *case_depth -= 1;
-                } else if *block_depth > 0 {
-                    *block_depth -= 1;
-                }
-            }
-            // The only BEGIN inside a CREATE TRIGGER is the body opener. A bare
-            // BEGIN (transaction) is not a trigger, so it does not open a block.
-            "BEGIN" if *is_trigger => *block_depth += 1,
-            _ => {}
-        }
-        word.clear();
-    }
-
-    // Push a single normalizing space (collapse runs, skip leading).
-    fn push_space(buf: &mut String) {
-        if !buf.is_empty() && !buf.ends_with(' ') {
-            buf.push(' ');
-        }
-    }
-
-    let end_statement = |buf: &mut String,
-                         out: &mut Vec<String>,
-                         case_depth: &mut usize,
-                         block_depth: &mut usize,
-                         is_trigger: &mut bool| {
-        let s = buf.trim().to_string();
-        if !s.is_empty() {
-            // Final pass: collapse any whitespace that survived inside quoted
-            // literals so the statement is one line (string contents do not
-            // affect parse benchmarking).
-            let normalized = s.split_whitespace().collect::<Vec<_>>().join(" ");
-            out.push(normalized);
-        }
-        buf.clear();
-        *case_depth = 0;
-        *block_depth = 0;
-        *is_trigger = false;
-    };
-
-    let chars: Vec<char> = input.chars().collect();
-    let mut i = 0;
-    while i < chars.len() {
-        let c = chars[i];
-
-        // Comments: strip to a single space.
-        if c == '-' && chars.get(i + 1) == Some(&'-') {
-            classify(
-                &mut word,
-                &mut case_depth,
-                &mut block_depth,
-                &mut is_trigger,
-            );
-            while i < chars.len() && chars[i] != '\n' {
-                i += 1;
-            }
-            push_space(&mut buf);
-            continue;
-        }
-        if c == '/' && chars.get(i + 1) == Some(&'*') {
-            classify(
-                &mut word,
-                &mut case_depth,
-                &mut block_depth,
-                &mut is_trigger,
-            );
-            i += 2;
-            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
-                i += 1;
-            }
-            i += 2;
-            push_space(&mut buf);
-            continue;
-        }
-
-        // Quoted string / identifier: copy verbatim, honoring doubling escapes.
-        if matches!(c, '\'' | '"' | '`' | '[') {
-            classify(
-                &mut word,
-                &mut case_depth,
-                &mut block_depth,
-                &mut is_trigger,
-            );
-            let close = if c == '[' { ']' } else { c };
-            buf.push(c);
-            i += 1;
-            loop {
-                if i >= chars.len() {
-                    break;
-                }
-                let d = chars[i];
-                if d == close {
-                    // Doubling escape ('' "" ``) keeps the quote open. Brackets
-                    // have no escape in SQLite.
-                    if close != ']' && chars.get(i + 1) == Some(&close) {
-                        buf.push(d);
-                        buf.push(d);
-                        i += 2;
-                        continue;
-                    }
-                    buf.push(d);
-                    i += 1;
-                    break;
-                }
-                buf.push(d);
-                i += 1;
-            }
-            continue;
-        }
-
-        if c.is_alphanumeric() || c == '_' {
-            word.push(c);
-            buf.push(c);
-            i += 1;
-            continue;
-        }
-
-        // Non-word character: settle the pending word first.
-        classify(
-            &mut word,
-            &mut case_depth,
-            &mut block_depth,
-            &mut is_trigger,
-        );
-
-        if c == ';' && case_depth == 0 && block_depth == 0 {
-            end_statement(
-                &mut buf,
-                &mut out,
-                &mut case_depth,
-                &mut block_depth,
-                &mut is_trigger,
-            );
-            i += 1;
-            continue;
-        }
-
-        if c.is_whitespace() {
-            push_space(&mut buf);
-        } else {
-            buf.push(c);
-        }
-        i += 1;
-    }
-    classify(
-        &mut word,
-        &mut case_depth,
-        &mut block_depth,
-        &mut is_trigger,
-    );
-    end_statement(
-        &mut buf,
-        &mut out,
-        &mut case_depth,
-        &mut block_depth,
-        &mut is_trigger,
-    );
-    out
+    Splitter::for_dialect(Dialect::Sqlite).split(input)
 }
 
-/// TCL test commands whose first brace-delimited argument is a SQL script.
-/// `do_*_test` take a test name first, `execsql`/`catchsql` take the script
-/// directly; in both cases the first `{...}` group after the keyword is the SQL.
-const SQL_CMDS: [&str; 5] = [
-    "do_execsql_test",
-    "do_catchsql_test",
-    "do_eqp_test",
-    "execsql",
-    "catchsql",
-];
-
 /// Negative-test statements the schema-free SQLite oracle cannot grade correctly.
 ///
 /// Each is genuinely invalid (a grammar rule SQLite enforces only after resolving
@@ -280,10 +96,6 @@ const SCHEMA_MASKED_INVALID: [&str; 25] = [
     "SELECT * FROM t1 NATURAL JOIN t2 USING(b)",
 ];
 
-const fn is_word_byte(b: u8) -> bool {
-    b.is_ascii_alphanumeric() || b == b'_'
-}
-
 /// Whether a statement closes every string/identifier quote, bracket, and block
 /// comment it opens. SQLite quotes: `'..'`/`".."`/`` `..` `` with doubling
 /// escapes, `[..]` (no escape), `/* .. */`; backslash is an ordinary character.
@@ -296,139 +108,7 @@ const fn is_word_byte(b: u8) -> bool {
 /// they never enter the corpus.
 #[must_use]
 fn is_balanced(stmt: &str) -> bool {
-    #[derive(PartialEq)]
-    enum Quote {
-        None,
-        Single,
-        Double,
-        Back,
-        Bracket,
-        Block,
-    }
-    let bytes = stmt.as_bytes();
-    let mut state = Quote::None;
-    let mut idx = 0;
-    while idx < bytes.len() {
-        let byte = bytes[idx];
-        match state {
-            Quote::None => match byte {
-                b'\'' => state = Quote::Single,
-                b'"' => state = Quote::Double,
-                b'`' => state = Quote::Back,
-                b'[' => state = Quote::Bracket,
-                b'-' if bytes.get(idx + 1) == Some(&b'-') => return true, // line comment to EOL
-                b'/' if bytes.get(idx + 1) == Some(&b'*') => {
-                    state = Quote::Block;
-                    idx += 1;
-                }
-                _ => {}
-            },
-            Quote::Single | Quote::Double | Quote::Back => {
-                let close = match state {
-                    Quote::Single => b'\'',
-                    Quote::Double => b'"',
-                    _ => b'`',
-                };
-                if byte == close {
-                    if bytes.get(idx + 1) == Some(&close) {
-                        idx += 1; // doubling escape
-                    } else {
-                        state = Quote::None;
-                    }
-                }
-            }
-            Quote::Bracket => {
-                if byte == b']' {
-                    state = Quote::None;
-                }
-            }
-            Quote::Block => {
-                if byte == b'*' && bytes.get(idx + 1) == Some(&b'/') {
-                    state = Quote::None;
-                    idx += 1;
-                }
-            }
-        }
-        idx += 1;
-    }
-    state == Quote::None
-}
-
-/// Index just past the `}` matching the `{` at `open`, using TCL brace rules:
-/// braces nest, but `\{`, `\}`, and `\\` are escapes that do not affect nesting
-/// (no other substitution happens inside braces). `None` if unbalanced.
-fn matching_brace(bytes: &[u8], open: usize) -> Option<usize> {
-    let mut depth = 0usize;
-    let mut i = open;
-    while i < bytes.len() {
-        match bytes[i] {
-            b'\\' => {
-                i += 2; // skip the escaped byte (\{ \} \\ \<newline>)
-                continue;
-            }
-            b'{' => depth += 1,
-            b'}' => {
-                depth -= 1;
-                if depth == 0 {
-                    return Some(i + 1);
-                }
-            }
-            _ => {}
-        }
-        i += 1;
-    }
-    None
-}
-
-/// Extract the SQL scripts from a TCL `.test` file: for each [`SQL_CMDS`] keyword
-/// at a word boundary, take the next brace-delimited group as the script. Bodies
-/// are returned verbatim (TCL does no substitution inside braces, so `$x` and
-/// `[...]` are literal); the caller re-splits each with [`split_sql`]. Forms that
-/// pass the script as a quoted/substituted string (not `{...}`) are skipped.
-fn extract_sql_bodies(input: &str) -> Vec<String> {
-    let bytes = input.as_bytes();
-    let mut out = Vec::new();
-    let mut i = 0;
-    while i < bytes.len() {
-        let kw_len = SQL_CMDS.iter().find_map(|kw| {
-            let k = kw.as_bytes();
-            let boundary_before = i == 0 || !is_word_byte(bytes[i - 1]);
-            let boundary_after = bytes
-                .get(i + k.len())
-                .is_some_and(|&c| c == b' ' || c == b'\t' || c == b'{');
-            (boundary_before && boundary_after && bytes[i..].starts_with(k)).then_some(k.len())
-        });
-        let Some(kw_len) = kw_len else {
-            i += 1;
-            continue;
-        };
-        // Scan past the test name / options to the script's opening brace. Bail
-        // on a double quote (quoted script form we do not handle) or an
-        // unescaped end of line, so we never grab a later command's brace group.
-        let mut j = i + kw_len;
-        let body_open = loop {
-            match bytes.get(j) {
-                Some(b'{') => break Some(j),
-                Some(b'"') | None => break None,
-                Some(b'\n') => {
-                    if j > 0 && bytes[j - 1] == b'\\' {
-                        j += 1;
-                        continue;
-                    }
-                    break None;
-                }
-                Some(_) => j += 1,
-            }
-        };
-        match body_open.and_then(|o| matching_brace(bytes, o).map(|e| (o, e))) {
-            Some((o, e)) => {
-                out.push(input[o + 1..e - 1].to_string());
-                i = e;
-            }
-            None => i += kw_len,
-        }
-    }
-    out
+    Splitter::for_dialect(Dialect::Sqlite).is_balanced(stmt)
 }
 
 fn main() {
@@ -469,7 +149,7 @@ fn main() {
     let mut masked_dropped = 0usize;
     for f in &files {
         let content = fs::read_to_string(f).expect("read test file");
-        for body in extract_sql_bodies(&content) {
+        for body in tcl_sql_bodies(&content) {
             for stmt in split_sql(&body) {
                 total += 1;
                 if !is_balanced(&stmt) {
@@ -500,9 +180,8 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::{
-        extract_sql_bodies, is_balanced, matching_brace, split_sql, SCHEMA_MASKED_INVALID,
-    };
+    use super::{is_balanced, split_sql, SCHEMA_MASKED_INVALID};
+    use sql_ast_benchmark::ingest::tcl_sql_bodies;
 
     #[test]
     fn schema_masked_invalid_list_is_normalized_and_unique() {
@@ -537,7 +216,7 @@ mod tests {
     #[test]
     fn extracts_do_execsql_test_body() {
         let tcl = "do_execsql_test foo-1.0 {\n  SELECT 1;\n} {1}\n";
-        assert_eq!(extract_sql_bodies(tcl), vec!["\n  SELECT 1;\n".to_string()]);
+        assert_eq!(tcl_sql_bodies(tcl), vec!["\n  SELECT 1;\n".to_string()]);
     }
 
     #[test]
@@ -546,21 +225,11 @@ mod tests {
         // `do_execsql_test` must not double-match (word boundary before it).
         let tcl = "execsql {CREATE TABLE t(a)}\ncatchsql {SELECT bad}\n";
         assert_eq!(
-            extract_sql_bodies(tcl),
+            tcl_sql_bodies(tcl),
             vec!["CREATE TABLE t(a)".to_string(), "SELECT bad".to_string()]
         );
         let nested = "do_execsql_test x {SELECT 9}";
-        assert_eq!(extract_sql_bodies(nested), vec!["SELECT 9".to_string()]);
-    }
-
-    #[test]
-    fn brace_matching_honors_backslash_escapes_and_nesting() {
-        // Balanced inner braces (JSON) are spanned; \{ and \} do not nest.
-        let s = b"{ '{\"a\":1}' }";
-        assert_eq!(matching_brace(s, 0), Some(s.len()));
-        let esc = b"{ a \\{ b }";
-        assert_eq!(matching_brace(esc, 0), Some(esc.len()));
-        assert_eq!(matching_brace(b"{ unbalanced ", 0), None);
+        assert_eq!(tcl_sql_bodies(nested), vec!["SELECT 9".to_string()]);
     }
 
     #[test]
@@ -572,7 +241,7 @@ mod tests {
         let tcl = "do_execsql_test json101-10.1 {\n  SELECT json_valid('\" \\  \"');\n} {0}\n\
                    do_execsql_test json101-10.2 {\n  SELECT json_valid('\" \\! \"');\n} {0}\n\
                    do_execsql_test json101-10.3 {\n  SELECT json_valid('\" \\\" \"');\n} {1}\n";
-        let stmts: Vec<String> = extract_sql_bodies(tcl)
+        let stmts: Vec<String> = tcl_sql_bodies(tcl)
             .iter()
             .flat_map(|b| split_sql(b))
             .collect();
@@ -589,7 +258,7 @@ mod tests {
     #[test]
     fn skips_quoted_script_forms() {
         // `execsql "..."` (double-quoted, TCL-substituted) is not a `{...}` body.
-        assert!(extract_sql_bodies("execsql \"SELECT $x\"\n").is_empty());
+        assert!(tcl_sql_bodies("execsql \"SELECT $x\"\n").is_empty());
     }
 
     #[test]
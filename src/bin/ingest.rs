@@ -0,0 +1,85 @@
+//! Add a corpus source: split a local directory of `.sql`/`.test` files into
+//! `datasets/{dialect}/{name}.txt`, record where it came from in `{name}.toml`,
+//! and repack `datasets.tar.zst`.
+//!
+//! Statements are split with the dialect's splitter, normalized to one line, and
+//! deduped against the rest of the dialect's corpus (see `ingest` in the
+//! library). Clone the source at a pinned commit or tag and pass that same ref
+//! as `--commit`:
+//!
+//! ```text
+//! git clone --depth 1 -b v1.2.0 https://github.com/duckdb/duckdb /tmp/duckdb
+//! cargo run --release --bin ingest -- /tmp/duckdb/test/sql duckdb --name duckdb_tests \
+//!   --source https://github.com/duckdb/duckdb --license MIT --commit v1.2.0
+//! ```
+//!
+//! `--no-repack` leaves the archive alone. A reference-graded dialect then needs
+//! its oracle rerun (`cargo run --release -p oracle -- <dialect>`) so the new
+//! statements get labels. A dialect not yet in `Dialect` needs its variant added
+//! first.
+
+use std::path::Path;
+
+use sql_ast_benchmark::datasets::{ensure_corpus, repack_corpus, Dialect};
+use sql_ast_benchmark::ingest::{ingest, Provenance};
+
+const USAGE: &str = "usage: ingest <dir> <dialect> --name <name> --source <url> --license <spdx> --commit <ref> [--no-repack]";
+
+fn fail(msg: &str, code: i32) -> ! {
+    eprintln!("ERROR: {msg}");
+    std::process::exit(code);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+            .unwrap_or_else(|| fail(&format!("missing {name}\n{USAGE}"), 2))
+    };
+    let (Some(src), Some(dialect)) = (args.first(), args.get(1)) else {
+        fail(USAGE, 2);
+    };
+    let dialect = Dialect::from_dir_name(dialect)
+        .unwrap_or_else(|| fail(&format!("unknown dialect `{dialect}`"), 2));
+    let name = flag("--name");
+    let provenance = Provenance {
+        source: flag("--source"),
+        license: flag("--license"),
+        commit: flag("--commit"),
+    };
+
+    // The existing corpus is what new statements are deduped against.
+    if let Err(e) = ensure_corpus() {
+        fail(&format!("could not prepare datasets/: {e}"), 1);
+    }
+    let got = ingest(
+        Path::new(src),
+        Path::new("datasets"),
+        dialect,
+        &name,
+        &provenance,
+    )
+    .unwrap_or_else(|e| fail(&e.to_string(), 1));
+    println!(
+        "{} source files, {} statements split, {} kept ({} dropped as unbalanced, {} already in the {} corpus or repeated).",
+        got.files,
+        got.split,
+        got.kept,
+        got.unbalanced,
+        got.duplicates,
+        dialect.display_name(),
+    );
+    let dir = format!("datasets/{}", dialect.dir_name());
+    println!("wrote {dir}/{name}.txt and {dir}/{name}.toml");
+
+    if args.iter().any(|a| a == "--no-repack") {
+        return;
+    }
+    if let Err(e) = repack_corpus() {
+        fail(&format!("could not repack datasets.tar.zst: {e}"), 1);
+    }
+    println!("repacked datasets.tar.zst");
+}
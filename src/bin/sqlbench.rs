@@ -11,17 +11,35 @@
 //!                              exists, acceptance rate otherwise). `--per-file`
 //!                              prints the per-dataset acceptance matrix instead
 //!                              of per-dialect reference metrics.
+//!   correctness --target <dialect>=<version>
+//!                              grade one dialect against a labeled engine
+//!                              release (`oracle --versions`, `PostgreSQL`
+//!                              only) instead of the pinned engine. Not with
+//!                              `--per-file`.
+//!   correctness --exclude-unsupported
+//!                              leave out of every metric the statements the
+//!                              pinned engine classed as an unsupported
+//!                              feature (not with `--target`, whose releases
+//!                              keep no class, nor with `--per-file`).
 //!   export                     write `web/assets/bench.json.zst` for the explorer.
+//!   fuzz [--inputs N] [--seed S]
+//!                              mutate corpus statements, feed them to every
+//!                              parser, and write minimized panicking inputs
+//!                              to `fuzz/crashers/` and counts to
+//!                              `fuzz/summary.tsv`.
 //!   regen                      run the whole data pipeline (feature scan +
 //!                              depth probe + timing + memory benches +
 //!                              time machine, then export) with one command.
 //!
+//! Parsers declared in `external/parsers.toml` run out of process next to the
+//! compiled-in ones (see `subprocess`).
+//!
 //! The grading logic lives in the library (`report`). This binary is argument
 //! dispatch plus table formatting.
 
 use sql_ast_benchmark::datasets::Dialect;
 use sql_ast_benchmark::report::{self, DialectReport};
-use sql_ast_benchmark::{export, BenchParser, Parser};
+use sql_ast_benchmark::{export, fuzz, oracle_cache, subprocess, BenchParser, Parser};
 
 /// Reference-backed dialects first, then the provenance dialects.
 const ORDER: [Dialect; 13] = [
@@ -71,8 +89,17 @@ fn print_report(r: &DialectReport) {
         } else {
             "pg_query (libpg_query)"
         };
+        let release = r
+            .target_version
+            .as_ref()
+            .map_or_else(String::new, |v| format!("   engine release: {v}"));
+        let unsupported = match (r.unsupported, r.exclude_unsupported) {
+            (0, _) => String::new(),
+            (n, true) => format!("   unsupported (excluded): {n}"),
+            (n, false) => format!("   unsupported (graded): {n}"),
+        };
         println!(
-            "Reference: {}   valid: {}   invalid: {}",
+            "Reference: {}{release}   valid: {}   invalid: {}{unsupported}",
             reference, r.valid_total, r.invalid_total
         );
         println!(
@@ -113,16 +140,53 @@ fn print_report(r: &DialectReport) {
     }
 }
 
-fn run_correctness() {
+/// Split a `--target` value, `<dialect>=<version>`, checking the version is one
+/// the committed labels carry.
+fn parse_target(value: &str) -> Result<(Dialect, String), String> {
+    let (name, version) = value
+        .split_once('=')
+        .ok_or_else(|| format!("--target needs <dialect>=<version>, got `{value}`"))?;
+    let dialect =
+        Dialect::from_dir_name(name).ok_or_else(|| format!("unknown dialect `{name}`"))?;
+    let versions = oracle_cache::engine_versions(dialect);
+    if versions.iter().any(|v| v == version) {
+        return Ok((dialect, version.to_string()));
+    }
+    if versions.is_empty() {
+        Err(format!(
+            "{name} has no per-release labels (run `oracle --versions`)"
+        ))
+    } else {
+        Err(format!(
+            "{name} has no labels for release `{version}`; labeled releases: {}",
+            versions.join(", ")
+        ))
+    }
+}
+
+fn run_correctness(target: Option<(Dialect, String)>, exclude_unsupported: bool) {
     println!("Multi-dialect SQL parser correctness");
     println!("Reference-graded against the real database engine (committed oracle/labels cache) where one exists, acceptance-rate elsewhere.");
     println!("Each parser run in its best-matching dialect.");
 
     let all = BenchParser::all();
-    let dyn_all: Vec<&dyn Parser> = all.iter().map(|p| p as &dyn Parser).collect();
+    let external = subprocess::external_parsers();
+    let dyn_all: Vec<&dyn Parser> = all
+        .iter()
+        .map(|p| p as &dyn Parser)
+        .chain(external.iter().map(|p| p as &dyn Parser))
+        .collect();
+    if let Some((dialect, version)) = target {
+        eprintln!("processing {} at {version}...", dialect.dir_name());
+        if let Some(r) = report::grade_dialect_at(dialect, &dyn_all, Some(&version), false) {
+            print_report(&r);
+        }
+        println!();
+        return;
+    }
     for dialect in ORDER {
         eprintln!("processing {}...", dialect.dir_name());
-        if let Some(r) = report::grade_dialect(dialect, &dyn_all) {
+        if let Some(r) = report::grade_dialect_at(dialect, &dyn_all, None, exclude_unsupported) {
             print_report(&r);
         }
     }
@@ -143,7 +207,12 @@ fn run_coverage() {
     println!("\nPer-file acceptance rate per parser (parser run in matching dialect)");
 
     let all = BenchParser::all();
-    let dyn_all: Vec<&dyn Parser> = all.iter().map(|p| p as &dyn Parser).collect();
+    let external = subprocess::external_parsers();
+    let dyn_all: Vec<&dyn Parser> = all
+        .iter()
+        .map(|p| p as &dyn Parser)
+        .chain(external.iter().map(|p| p as &dyn Parser))
+        .collect();
     for dialect in ORDER {
         let (parsers, stats) = report::coverage_dialect(dialect, &dyn_all);
         if stats.is_empty() {
@@ -182,6 +251,64 @@ fn run_coverage() {
     println!("\n(Reference dialects are graded against the real database engine, run in Docker by the `oracle` crate and cached under oracle/labels.)");
 }
 
+// fuzz (mutation fuzzing for the robustness metric).
+
+/// The value after `flag` in `args`, parsed, or `default` when absent.
+fn flag_value<T: std::str::FromStr>(args: &[String], flag: &str, default: T) -> T {
+    let Some(i) = args.iter().position(|a| a == flag) else {
+        return default;
+    };
+    args.get(i + 1)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| {
+            eprintln!("ERROR: {flag} needs a number");
+            std::process::exit(2);
+        })
+}
+
+fn run_fuzz(args: &[String]) {
+    let inputs = flag_value(args, "--inputs", fuzz::INPUTS);
+    let seed = flag_value(args, "--seed", 0u64);
+    let runs = match fuzz::run(std::path::Path::new("."), inputs, seed) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("ERROR: {e}");
+            std::process::exit(1);
+        }
+    };
+    println!("\nFuzzing: {inputs} mutated inputs per parser and dialect, seed {seed}");
+    let nw = runs
+        .iter()
+        .map(|r| r.parser.len())
+        .max()
+        .unwrap_or(22)
+        .max(22);
+    println!(
+        "{:<nw$}  {:<11}  {:>8}  {:>7}  {:>6}  {:>8}",
+        "parser", "dialect", "inputs", "panics", "hangs", "crashers"
+    );
+    println!(
+        "{}",
+        "-".repeat(nw + 2 + 11 + 2 + 8 + 2 + 7 + 2 + 6 + 2 + 8)
+    );
+    for r in runs.iter().filter(|r| r.panics + r.hangs > 0) {
+        println!(
+            "{:<nw$}  {:<11}  {:>8}  {:>7}  {:>6}  {:>8}",
+            r.parser,
+            r.dialect.dir_name(),
+            r.inputs,
+            r.panics,
+            r.hangs,
+            r.crashers.len()
+        );
+    }
+    println!(
+        "\n(Crashers are in {}/, counts in {}.)",
+        fuzz::CRASHERS_DIR,
+        fuzz::SUMMARY
+    );
+}
+
 // regen (run the whole data pipeline with one command).
 
 /// Run every input producer for `bench.json.zst` in order, then export.
@@ -299,7 +426,14 @@ fn run_regen() {
 fn usage() -> ! {
     eprintln!("usage: sqlbench <subcommand>");
     eprintln!("  correctness [--per-file]   grade parsers over datasets/");
+    eprintln!(
+        "  correctness --target <dialect>=<version>  grade against one labeled engine release"
+    );
+    eprintln!(
+        "  correctness --exclude-unsupported  leave engine-unsupported statements out of every metric"
+    );
     eprintln!("  export                     write web/assets/bench.json.zst for the site");
+    eprintln!("  fuzz [--inputs N] [--seed S]  fuzz every parser, write fuzz/crashers/ and fuzz/summary.tsv");
     eprintln!("  regen                      run feature scan + depth probe + benches + time machine, then export");
     std::process::exit(2);
 }
@@ -316,10 +450,27 @@ fn main() {
                 eprintln!("ERROR: could not prepare datasets/: {e}");
                 std::process::exit(1);
             }
-            if args.iter().any(|a| a == "--per-file") {
+            let target = args.iter().position(|a| a == "--target").map(|i| {
+                let value = args.get(i + 1).map_or("", String::as_str);
+                parse_target(value).unwrap_or_else(|e| {
+                    eprintln!("ERROR: {e}");
+                    std::process::exit(2);
+                })
+            });
+            let exclude_unsupported = args.iter().any(|a| a == "--exclude-unsupported");
+            if exclude_unsupported && target.is_some() {
+                eprintln!("ERROR: --exclude-unsupported cannot be combined with --target (engine releases keep no error class)");
+                std::process::exit(2);
+            }
+            let per_file = args.iter().any(|a| a == "--per-file");
+            if per_file && (target.is_some() || exclude_unsupported) {
+                eprintln!("ERROR: --per-file cannot be combined with --target or --exclude-unsupported (the per-file matrix is acceptance only, with no reference verdict)");
+                std::process::exit(2);
+            }
+            if per_file {
                 run_coverage();
             } else {
-                run_correctness();
+                run_correctness(target, exclude_unsupported);
             }
         }
         Some("export") => {
@@ -332,6 +483,13 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Some("fuzz") => {
+            if let Err(e) = sql_ast_benchmark::datasets::ensure_corpus() {
+                eprintln!("ERROR: could not prepare datasets/: {e}");
+                std::process::exit(1);
+            }
+            run_fuzz(&args);
+        }
         Some("regen") => run_regen(),
         Some("-h" | "--help" | "help") => usage(),
         Some(other) => {
@@ -343,7 +501,7 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::{pct, truncate};
+    use super::{flag_value, parse_target, pct, truncate};
 
     #[test]
     fn pct_handles_zero_base() {
@@ -352,9 +510,27 @@ mod tests {
         assert!((pct(3, 3) - 100.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn flag_value_reads_the_argument_after_the_flag() {
+        let args: Vec<String> = ["fuzz", "--inputs", "50"].map(String::from).to_vec();
+        assert_eq!(flag_value(&args, "--inputs", 7usize), 50);
+        assert_eq!(flag_value(&args, "--seed", 7u64), 7);
+    }
+
     #[test]
     fn truncate_clips_long_names() {
         assert_eq!(truncate("short", 10), "short");
         assert_eq!(truncate("verylongname", 4), "very");
     }
+
+    #[test]
+    fn parse_target_rejects_malformed_and_unlabeled_targets() {
+        assert!(parse_target("postgresql")
+            .unwrap_err()
+            .contains("<dialect>=<version>"));
+        assert!(parse_target("nope=15")
+            .unwrap_err()
+            .contains("unknown dialect"));
+        assert!(parse_target("bigquery=1").is_err());
+    }
 }
@@ -0,0 +1,61 @@
+//! The common subset for head-to-head speed and memory comparisons.
+//!
+//! Each parser's timing and memory distributions are taken over its own
+//! accepted set, so a parser that rejects the hard statements gets an easier
+//! workload. The common subset is the statements every participating parser
+//! accepts: timed by `cargo bench --bench parsing -- --common` and measured by
+//! `membench common`, it gives distributions that compare like with like, at
+//! the cost of leaving out whatever any one parser cannot handle.
+
+use crate::datasets::Dialect;
+use crate::BenchParser;
+
+/// The parsers that take part in `dialect`'s common subset: every compiled-in
+/// parser that supports it.
+#[must_use]
+pub fn participants(dialect: Dialect) -> Vec<BenchParser> {
+    BenchParser::all()
+        .into_iter()
+        .filter(|p| p.supports(dialect))
+        .collect()
+}
+
+/// The statements of `stmts` that every parser in `parsers` accepts, in corpus
+/// order. Empty without participants.
+#[must_use]
+pub fn common_subset<'a>(
+    stmts: &'a [String],
+    dialect: Dialect,
+    parsers: &[BenchParser],
+) -> Vec<&'a str> {
+    if parsers.is_empty() {
+        return Vec::new();
+    }
+    stmts
+        .iter()
+        .filter(|s| parsers.iter().all(|p| p.accepts(s, dialect) == Some(true)))
+        .map(String::as_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::common_subset;
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    #[test]
+    fn the_common_subset_drops_what_any_parser_rejects() {
+        let stmts = vec![
+            "SELECT 1".to_string(),
+            "SELECT FROM WHERE".to_string(),
+            "SELECT a FROM t".to_string(),
+        ];
+        let sp = BenchParser::Sqlparser;
+        assert_eq!(
+            common_subset(&stmts, Dialect::Multi, &[sp]),
+            ["SELECT 1", "SELECT a FROM t"]
+        );
+        assert!(common_subset(&stmts, Dialect::Multi, &[]).is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! SQL construct classifier for the per-feature coverage matrix.
+//!
+//! The per-file coverage matrix says which corpus files a parser handles, not
+//! which SQL features. This tags each statement with the constructs it uses
+//! (CTEs, window functions, `LATERAL`, `MERGE`, upserts, JSON operators and so
+//! on) so grading can report recall per (parser, construct), the question
+//! asked when choosing a parser.
+//!
+//! Constructs are data, one TOML file per construct under [`CONSTRUCTS_DIR`],
+//! loaded like contentious rules ([`crate::contentious`]): a `regex` construct
+//! is matched against the [`mask`]ed statement, and a `structural` one names a
+//! built-in predicate for what a regex over the masked form cannot see. Unlike
+//! contentious rules a statement may carry any number of constructs
+//! ([`Registry::tags`]).
+
+use crate::contentious::mask;
+use crate::datasets::Dialect;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Directory holding the committed construct files, relative to the working
+/// directory.
+pub const CONSTRUCTS_DIR: &str = "constructs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Regex,
+    Structural,
+}
+
+/// One construct file under [`CONSTRUCTS_DIR`], deserialized from TOML.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConstructFile {
+    pub id: String,
+    pub title: String,
+    /// Dir names of the dialects this construct is looked for in, or a single
+    /// `"all"`.
+    pub dialects: Vec<String>,
+    kind: Kind,
+    /// The regex pattern (regex constructs only).
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// The built-in predicate name (structural constructs only).
+    #[serde(default)]
+    pub predicate: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Statements the construct must match (verified in tests).
+    #[serde(default)]
+    pub matches: Vec<String>,
+    /// Statements the construct must not match (verified in tests).
+    #[serde(default)]
+    pub non_matches: Vec<String>,
+}
+
+/// A built-in structural predicate, run against the masked and the original
+/// statement.
+type Predicate = fn(&str, &str) -> bool;
+
+enum Matcher {
+    Regex(Regex),
+    Structural(Predicate),
+}
+
+/// A loaded, compiled construct: its metadata plus its matcher.
+pub struct Construct {
+    pub meta: ConstructFile,
+    matcher: Matcher,
+}
+
+impl Construct {
+    /// Whether this construct is looked for in `dialect`.
+    #[must_use]
+    pub fn applies_to(&self, dialect: Dialect) -> bool {
+        self.meta
+            .dialects
+            .iter()
+            .any(|d| d == "all" || d == dialect.dir_name())
+    }
+
+    /// Whether the statement uses this construct.
+    fn is_match(&self, masked: &str, original: &str) -> bool {
+        match &self.matcher {
+            Matcher::Regex(re) => re.is_match(masked),
+            Matcher::Structural(p) => p(masked, original),
+        }
+    }
+}
+
+/// Map a structural predicate name to its built-in implementation.
+fn structural_predicate(name: &str) -> Option<Predicate> {
+    match name {
+        "routine_body" => Some(routine_body),
+        _ => None,
+    }
+}
+
+/// The loaded construct registry.
+pub struct Registry {
+    pub constructs: Vec<Construct>,
+}
+
+impl Registry {
+    /// Load and compile every `*.toml` construct under `dir`, in file-name
+    /// order (the column order of the matrix).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on a bad file, a bad regex, an unknown predicate, a
+    /// missing `pattern`/`predicate`, or a duplicate id.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| format!("reading {}: {e}", dir.display()))?
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|x| x == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut constructs = Vec::new();
+        let mut ids = HashSet::new();
+        for path in paths {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| format!("reading {}: {e}", path.display()))?;
+            let meta: ConstructFile =
+                toml::from_str(&text).map_err(|e| format!("parsing {}: {e}", path.display()))?;
+            if !ids.insert(meta.id.clone()) {
+                return Err(format!("duplicate construct id `{}`", meta.id));
+            }
+            let matcher = match meta.kind {
+                Kind::Regex => {
+                    let pat = meta.pattern.as_ref().ok_or_else(|| {
+                        format!("construct `{}`: regex construct needs `pattern`", meta.id)
+                    })?;
+                    Matcher::Regex(
+                        Regex::new(pat)
+                            .map_err(|e| format!("construct `{}`: bad regex: {e}", meta.id))?,
+                    )
+                }
+                Kind::Structural => {
+                    let name = meta.predicate.as_ref().ok_or_else(|| {
+                        format!(
+                            "construct `{}`: structural construct needs `predicate`",
+                            meta.id
+                        )
+                    })?;
+                    Matcher::Structural(structural_predicate(name).ok_or_else(|| {
+                        format!("construct `{}`: unknown predicate `{name}`", meta.id)
+                    })?)
+                }
+            };
+            constructs.push(Construct { meta, matcher });
+        }
+        Ok(Self { constructs })
+    }
+
+    /// Indices (into `constructs`) of every construct `sql` uses in `dialect`.
+    #[must_use]
+    pub fn tags(&self, sql: &str, dialect: Dialect) -> Vec<usize> {
+        let masked = mask(sql);
+        self.constructs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.applies_to(dialect) && c.is_match(&masked, sql))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// The process-wide registry, loaded once from [`CONSTRUCTS_DIR`].
+///
+/// # Panics
+///
+/// Panics with the load error if a construct file is malformed, which fails
+/// the export build.
+#[must_use]
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        Registry::load(Path::new(CONSTRUCTS_DIR))
+            .unwrap_or_else(|e| panic!("construct registry: {e}"))
+    })
+}
+
+/// Structural predicate: a `CREATE FUNCTION`, `PROCEDURE` or `TRIGGER` that
+/// carries its body.
+///
+/// The head is read from the masked form, the body from the original, since
+/// masking blanks out a quoted body (`AS '...'`). A dollar-quoted body, a
+/// `BEGIN ... END` block or a SQL-standard `RETURN` count too.
+fn routine_body(masked: &str, original: &str) -> bool {
+    static RES: OnceLock<(Regex, Regex)> = OnceLock::new();
+    let (head, body) = RES.get_or_init(|| {
+        (
+            Regex::new(
+                r"(?i)^\s*create\s+(or\s+replace\s+)?(definer\s*=\s*\S+\s+)?(temp(orary)?\s+)?(function|procedure|trigger)\b",
+            )
+            .unwrap(),
+            Regex::new(r"(?is)\bbegin\b|\$\w*\$|\bas\s*'|\breturn\b").unwrap(),
+        )
+    });
+    head.is_match(masked) && body.is_match(original)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reg() -> Registry {
+        Registry::load(Path::new(CONSTRUCTS_DIR)).expect("load construct registry")
+    }
+
+    #[test]
+    fn every_construct_matches_its_examples_and_skips_non_matches() {
+        let r = reg();
+        assert!(!r.constructs.is_empty(), "expected at least one construct");
+        for c in &r.constructs {
+            for ex in &c.meta.matches {
+                assert!(
+                    c.is_match(&mask(ex), ex),
+                    "construct `{}` should match `{ex}`",
+                    c.meta.id
+                );
+            }
+            for ex in &c.meta.non_matches {
+                assert!(
+                    !c.is_match(&mask(ex), ex),
+                    "construct `{}` should not match `{ex}`",
+                    c.meta.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_statement_carries_every_construct_it_uses() {
+        let r = reg();
+        let ids = |sql: &str| -> Vec<&str> {
+            r.tags(sql, Dialect::Postgresql)
+                .into_iter()
+                .map(|i| r.constructs[i].meta.id.as_str())
+                .collect()
+        };
+        let got = ids(
+            "WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t) \
+             SELECT n, sum(n) OVER (ORDER BY n) FROM t",
+        );
+        for id in ["cte", "recursive-cte", "window-function"] {
+            assert!(got.contains(&id), "{id} missing from {got:?}");
+        }
+        assert!(ids("SELECT 1").is_empty());
+    }
+
+    #[test]
+    fn routine_bodies_are_read_from_the_unmasked_statement() {
+        let body = |sql: &str| routine_body(&mask(sql), sql);
+        assert!(body(
+            "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1 $$ LANGUAGE sql"
+        ));
+        assert!(body(
+            "CREATE OR REPLACE FUNCTION f() RETURNS int AS 'select 1' LANGUAGE sql"
+        ));
+        assert!(body(
+            "CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b; END"
+        ));
+        assert!(!body("DROP FUNCTION f"));
+        assert!(!body("SELECT 'CREATE FUNCTION f() AS $$ $$'"));
+    }
+}
@@ -11,8 +11,14 @@
 //! regex cannot express, such as a repeated identifier). The `regex` crate matches
 //! in guaranteed linear time with no backreferences, so a contributed pattern
 //! cannot run arbitrary code or cause catastrophic backtracking.
+//!
+//! An `engine` rule matches no text at all: it fires on the statements the
+//! reference engine itself put in one [`EngineClass`] (say, a feature the server
+//! recognizes but does not implement), read from the committed labels. Its
+//! coverage is the labels', so it carries no `matches` examples.
 
 use crate::datasets::Dialect;
+use crate::oracle_cache::{self, EngineClass};
 use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashSet;
@@ -55,6 +61,7 @@ impl Category {
 enum Kind {
     Regex,
     Structural,
+    Engine,
 }
 
 /// One rule file under [`RULES_DIR`], deserialized from TOML.
@@ -72,6 +79,9 @@ pub struct RuleFile {
     /// The built-in predicate name (structural rules only).
     #[serde(default)]
     pub predicate: Option<String>,
+    /// The [`EngineClass`] name (engine rules only).
+    #[serde(default)]
+    pub class: Option<String>,
     #[serde(default)]
     pub description: String,
     #[serde(default)]
@@ -90,6 +100,7 @@ type Predicate = fn(&str) -> bool;
 enum Matcher {
     Regex(Regex),
     Structural(Predicate),
+    Engine(EngineClass),
 }
 
 /// A loaded, compiled rule: its metadata plus its matcher.
@@ -109,11 +120,13 @@ impl Rule {
     }
 
     /// Whether this rule matches. Regex rules see the masked form, structural
-    /// rules see the original statement.
-    fn is_match(&self, masked: &str, original: &str) -> bool {
+    /// rules see the original statement, and engine rules look the original up
+    /// in `dialect`'s labels.
+    fn is_match(&self, masked: &str, original: &str, dialect: Dialect) -> bool {
         match &self.matcher {
             Matcher::Regex(re) => re.is_match(masked),
             Matcher::Structural(p) => p(original),
+            Matcher::Engine(c) => oracle_cache::reference_class(original, dialect) == Some(*c),
         }
     }
 }
@@ -138,8 +151,9 @@ impl Registry {
     /// # Errors
     ///
     /// Returns an error (rather than panicking) on a bad file, a bad regex, an
-    /// unknown predicate, a missing `pattern`/`predicate`, or a duplicate id, so
-    /// callers can fail the build or a test with a precise message.
+    /// unknown predicate or class, a missing `pattern`/`predicate`/`class`, an
+    /// engine rule with examples, or a duplicate id, so callers can fail the
+    /// build or a test with a precise message.
     pub fn load(dir: &Path) -> Result<Self, String> {
         let mut paths: Vec<_> = std::fs::read_dir(dir)
             .map_err(|e| format!("reading {}: {e}", dir.display()))?
@@ -178,6 +192,7 @@ impl Registry {
                             format!("rule `{}`: unknown predicate `{name}`", meta.id)
                         })?)
                     }
+                    Kind::Engine => engine_matcher(&meta)?,
                 };
             rules.push(Rule { meta, matcher });
         }
@@ -191,8 +206,26 @@ impl Registry {
         let masked = mask(sql);
         self.rules
             .iter()
-            .find(|r| r.applies_to(dialect) && r.is_match(&masked, sql))
+            .find(|r| r.applies_to(dialect) && r.is_match(&masked, sql, dialect))
+    }
+}
+
+/// The matcher of an engine rule: its `class`, which must name an
+/// [`EngineClass`], with no examples to verify against text.
+fn engine_matcher(meta: &RuleFile) -> Result<Matcher, String> {
+    let name = meta
+        .class
+        .as_ref()
+        .ok_or_else(|| format!("rule `{}`: engine rule needs `class`", meta.id))?;
+    let class = EngineClass::from_name(name)
+        .ok_or_else(|| format!("rule `{}`: unknown engine class `{name}`", meta.id))?;
+    if !meta.matches.is_empty() || !meta.non_matches.is_empty() {
+        return Err(format!(
+            "rule `{}`: engine rules are checked against the labels, not examples",
+            meta.id
+        ));
     }
+    Ok(Matcher::Engine(class))
 }
 
 /// The process-wide registry, loaded once from [`RULES_DIR`].
@@ -369,7 +402,7 @@ mod tests {
             for ex in &rule.meta.matches {
                 let masked = mask(ex);
                 assert!(
-                    rule.is_match(&masked, ex),
+                    rule.is_match(&masked, ex, dialect),
                     "rule `{}` should match `{ex}`",
                     rule.meta.id
                 );
@@ -377,7 +410,7 @@ mod tests {
             for ex in &rule.meta.non_matches {
                 let masked = mask(ex);
                 assert!(
-                    !rule.is_match(&masked, ex),
+                    !rule.is_match(&masked, ex, dialect),
                     "rule `{}` should not match `{ex}`",
                     rule.meta.id
                 );
@@ -442,7 +475,7 @@ mod tests {
                     if crate::reference_accepts(&s, d) != Some(true) {
                         continue;
                     }
-                    if rule.is_match(&mask(&s), &s) {
+                    if rule.is_match(&mask(&s), &s, d) {
                         hits += 1;
                     }
                 }
@@ -455,6 +488,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn engine_rules_need_a_known_class_and_no_examples() {
+        let load = |tag: &str, extra: &str| {
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos();
+            let dir = std::env::temp_dir().join(format!("sqlbench_rules_{tag}_{nanos}"));
+            std::fs::create_dir_all(&dir).unwrap();
+            let rule = format!(
+                "id = \"pg-unsupported\"\ntitle = \"t\"\ncategory = \"engine-specific\"\n\
+                 dialects = [\"postgresql\"]\nkind = \"engine\"\n{extra}"
+            );
+            std::fs::write(dir.join("rule.toml"), rule).unwrap();
+            let r = Registry::load(&dir);
+            let _ = std::fs::remove_dir_all(&dir);
+            r
+        };
+        let ok = load("ok", "class = \"unsupported\"\n").unwrap();
+        assert!(matches!(
+            ok.rules[0].matcher,
+            Matcher::Engine(EngineClass::Unsupported)
+        ));
+        assert!(load("none", "")
+            .err()
+            .unwrap_or_default()
+            .contains("needs `class`"));
+        assert!(load("bad", "class = \"odd\"\n")
+            .err()
+            .unwrap_or_default()
+            .contains("unknown engine class"));
+        assert!(load("ex", "class = \"limit\"\nmatches = [\"SELECT 1\"]\n")
+            .err()
+            .unwrap_or_default()
+            .contains("not examples"));
+    }
+
     #[test]
     fn duplicate_columns_predicate() {
         assert!(duplicate_columns(
@@ -4,8 +4,10 @@
 //!
 //! The corpus ships as `datasets.tar.zst`, organised as
 //! `datasets/{dialect}/{name}.txt`, and [`ensure_corpus`] extracts it on demand.
-//! This module only models each subdirectory's dialect. The original
-//! fetch/extraction machinery has been removed (see git history).
+//! This module models each subdirectory's dialect. The original
+//! fetch/extraction machinery has been removed (see git history); a new source
+//! is added from a local checkout with the `ingest` binary
+//! ([`crate::ingest`]), which ends with [`repack_corpus`].
 
 use std::io;
 use std::path::Path;
@@ -36,6 +38,29 @@ pub fn ensure_corpus() -> io::Result<()> {
     }
 }
 
+/// Rewrite `datasets.tar.zst` from the unpacked `datasets/`.
+///
+/// # Errors
+/// Returns an error if `datasets/` is missing or `tar --zstd` fails.
+pub fn repack_corpus() -> io::Result<()> {
+    if !Path::new("datasets").is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "datasets/ not found",
+        ));
+    }
+    let status = std::process::Command::new("tar")
+        .args(["--zstd", "-cf", "datasets.tar.zst", "datasets"])
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(
+            "`tar --zstd -cf datasets.tar.zst datasets` failed",
+        ))
+    }
+}
+
 /// A SQL dialect, matching a subdirectory of `datasets/`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Dialect {
@@ -0,0 +1,323 @@
+//! Deterministic single-token edits for the edit-reparse axis.
+//!
+//! The timing benches parse each statement cold, but an editor reparses the
+//! same statement over and over as it is typed into. This module derives the
+//! edits that workload is made of from a statement's text, independently of
+//! any parser, so every parser reparses the same edited text:
+//!
+//! - [`EditKind::Rename`]: the last plain identifier gains a suffix (`t` to `t_1`).
+//! - [`EditKind::Literal`]: the last number gains a digit (`10` to `100`), or
+//!   failing that the last string gains a character (`'a'` to `'ax'`).
+//! - [`EditKind::Predicate`]: a `1 = 1 AND` conjunct is inserted after the
+//!   last `WHERE`.
+//!
+//! The edits are chosen to keep a well-formed statement well-formed, but they
+//! are made on the text alone and cannot always: a renamed type or function
+//! name may be one the parser only knows by name, and a `WHERE CURRENT OF`
+//! clause has no predicate to extend (so it gets no predicate edit). The
+//! benchmark therefore drops an edit the parser rejects rather than timing its
+//! error path (see [`BenchParser::edit_reparse`]). A statement gets at most one
+//! edit of each kind, and none of a kind it has no site for.
+//!
+//! [`EditReparse`] is one timed reparse: through the parser's incremental API
+//! where it has one (tree-sitter reuses the old tree), otherwise a cold parse
+//! of the edited text.
+
+use crate::datasets::Dialect;
+use crate::{incremental, BenchParser};
+use sqlparser::keywords::ALL_KEYWORDS;
+use std::ops::Range;
+
+/// Which part of the statement an [`Edit`] touches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKind {
+    Rename,
+    Literal,
+    Predicate,
+}
+
+impl EditKind {
+    pub const ALL: [Self; 3] = [Self::Rename, Self::Literal, Self::Predicate];
+
+    /// Short name, used in file names and table headers.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rename => "rename",
+            Self::Literal => "literal",
+            Self::Predicate => "predicate",
+        }
+    }
+}
+
+/// Replace the bytes in `range` with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Edit {
+    pub kind: EditKind,
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+impl Edit {
+    /// `sql` with the edit applied.
+    #[must_use]
+    pub fn apply(&self, sql: &str) -> String {
+        let mut out = String::with_capacity(sql.len() + self.replacement.len());
+        out.push_str(&sql[..self.range.start]);
+        out.push_str(&self.replacement);
+        out.push_str(&sql[self.range.end..]);
+        out
+    }
+}
+
+/// A lexical token, as far as choosing an edit site needs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tok {
+    Word,
+    Number,
+    Str,
+}
+
+/// Words, numbers and single-quoted strings in `sql`, with their byte ranges.
+/// Comments, quoted identifiers, prefixed strings (`E'..'`, `x'..'`) and
+/// placeholders (`$1`) are skipped.
+fn tokens(sql: &str) -> Vec<(Tok, Range<usize>)> {
+    let b = sql.as_bytes();
+    let word = |x: u8| x.is_ascii_alphanumeric() || x == b'_' || !x.is_ascii();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        let start = i;
+        let c = b[i];
+        i += 1;
+        if c == b'-' && b.get(i) == Some(&b'-') {
+            while i < b.len() && b[i] != b'\n' {
+                i += 1;
+            }
+        } else if c == b'/' && b.get(i) == Some(&b'*') {
+            i = sql[i + 1..].find("*/").map_or(b.len(), |e| i + 1 + e + 2);
+        } else if matches!(c, b'\'' | b'"' | b'`') {
+            i = past_quoted(b, i, c);
+            if c == b'\'' {
+                out.push((Tok::Str, start..i));
+            }
+        } else if c == b'$' {
+            while i < b.len() && word(b[i]) {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() {
+            while i < b.len() && b[i].is_ascii_digit() {
+                i += 1;
+            }
+            if b.get(i) == Some(&b'.') && b.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+                while i < b.len() && b[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            // `1e5`, `0x1F` and `1.` are left alone.
+            if b.get(i).is_some_and(|&x| word(x) || x == b'.') {
+                while i < b.len() && (word(b[i]) || b[i] == b'.') {
+                    i += 1;
+                }
+            } else {
+                out.push((Tok::Number, start..i));
+            }
+        } else if word(c) {
+            while i < b.len() && word(b[i]) {
+                i += 1;
+            }
+            match b.get(i) {
+                Some(b'\'') => i = past_quoted(b, i + 1, b'\''),
+                Some(b'$') => {}
+                _ => out.push((Tok::Word, start..i)),
+            }
+        }
+    }
+    out
+}
+
+/// The index just past a `quote`-delimited token whose body starts at `i`. A
+/// doubled quote inside is an escaped one.
+fn past_quoted(b: &[u8], mut i: usize, quote: u8) -> usize {
+    while i < b.len() {
+        if b[i] == quote {
+            if b.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+pub(crate) fn is_keyword(word: &str) -> bool {
+    ALL_KEYWORDS
+        .binary_search(&word.to_ascii_uppercase().as_str())
+        .is_ok()
+}
+
+/// Byte range of the literal an [`EditKind::Literal`] edit changes: the last
+/// number, or failing that the last string. Also where the placeholder probes
+/// ([`crate::placeholder`]) put their bind parameter.
+pub(crate) fn last_literal(sql: &str) -> Option<Range<usize>> {
+    last_literal_in(&tokens(sql))
+}
+
+fn last_literal_in(toks: &[(Tok, Range<usize>)]) -> Option<Range<usize>> {
+    let last = |kind: Tok| {
+        toks.iter()
+            .rev()
+            .find(|(t, r)| *t == kind && (kind != Tok::Str || r.len() >= 2))
+            .map(|(_, r)| r.clone())
+    };
+    last(Tok::Number).or_else(|| last(Tok::Str))
+}
+
+/// The edits `sql` admits, at most one per [`EditKind`], in [`EditKind::ALL`]
+/// order.
+#[must_use]
+pub fn edits(sql: &str) -> Vec<Edit> {
+    let toks = tokens(sql);
+    let text = |r: &Range<usize>| &sql[r.clone()];
+    let last = |pred: &dyn Fn(Tok, &str) -> bool| {
+        toks.iter()
+            .rev()
+            .find(|(t, r)| pred(*t, text(r)))
+            .map(|(_, r)| r.clone())
+    };
+    let mut out = Vec::new();
+    if let Some(r) = last(&|t, s| t == Tok::Word && !is_keyword(s)) {
+        out.push(Edit {
+            kind: EditKind::Rename,
+            replacement: format!("{}_1", text(&r)),
+            range: r,
+        });
+    }
+    let literal = last_literal_in(&toks).map(|r| {
+        let lit = text(&r);
+        let replacement = if lit.starts_with('\'') {
+            format!("{}x'", &lit[..lit.len() - 1])
+        } else {
+            format!("{lit}0")
+        };
+        (replacement, r)
+    });
+    if let Some((replacement, range)) = literal {
+        out.push(Edit {
+            kind: EditKind::Literal,
+            range,
+            replacement,
+        });
+    }
+    // `WHERE CURRENT OF cursor` names a row, not a condition.
+    let where_at = toks
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, (t, r))| *t == Tok::Word && text(r).eq_ignore_ascii_case("WHERE"));
+    let predicate = where_at
+        .filter(|(i, _)| {
+            toks.get(i + 1)
+                .is_none_or(|(_, r)| !text(r).eq_ignore_ascii_case("CURRENT"))
+        })
+        .map(|(_, (_, r))| r.clone());
+    if let Some(r) = predicate {
+        out.push(Edit {
+            kind: EditKind::Predicate,
+            replacement: format!("{} 1 = 1 AND", text(&r)),
+            range: r,
+        });
+    }
+    out
+}
+
+/// One edited statement ready for timed reparses.
+pub enum EditReparse {
+    /// The old tree, edited and handed back to the parser.
+    Incremental(incremental::Reparse),
+    /// A cold parse of the edited text.
+    Cold {
+        parser: BenchParser,
+        edited: String,
+        dialect: Dialect,
+    },
+}
+
+impl EditReparse {
+    /// One timed iteration. Returns whether the edited statement parsed.
+    pub fn run(&mut self) -> bool {
+        match self {
+            Self::Incremental(r) => r.run(),
+            Self::Cold {
+                parser,
+                edited,
+                dialect,
+            } => parser.parse_once(edited, *dialect),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edits, EditKind};
+
+    fn applied(sql: &str) -> Vec<(EditKind, String)> {
+        edits(sql).iter().map(|e| (e.kind, e.apply(sql))).collect()
+    }
+
+    #[test]
+    fn each_kind_edits_its_last_site() {
+        assert_eq!(
+            applied("SELECT a FROM t WHERE b = 10"),
+            vec![
+                (
+                    EditKind::Rename,
+                    "SELECT a FROM t WHERE b_1 = 10".to_string()
+                ),
+                (
+                    EditKind::Literal,
+                    "SELECT a FROM t WHERE b = 100".to_string()
+                ),
+                (
+                    EditKind::Predicate,
+                    "SELECT a FROM t WHERE 1 = 1 AND b = 10".to_string()
+                ),
+            ]
+        );
+        // No number: the last string literal changes instead.
+        assert_eq!(
+            applied("select * from t where name = 'it''s'")[1].1,
+            "select * from t where name = 'it''sx'"
+        );
+    }
+
+    #[test]
+    fn quoted_commented_and_keyword_text_is_never_renamed() {
+        let sql = r#"SELECT "Col" FROM t -- trailing x"#;
+        assert_eq!(
+            applied(sql),
+            vec![(
+                EditKind::Rename,
+                r#"SELECT "Col" FROM t_1 -- trailing x"#.to_string()
+            )]
+        );
+        // Only keywords: nothing to rename, and no WHERE for a predicate.
+        assert!(edits("SELECT 1")
+            .iter()
+            .all(|e| e.kind == EditKind::Literal));
+        // Numbers glued to identifiers (`1e5`, `t1`) are not literals to extend.
+        assert!(edits("SELECT t1, 1e5")
+            .iter()
+            .all(|e| e.kind == EditKind::Rename));
+    }
+
+    #[test]
+    fn where_current_of_gets_no_predicate() {
+        assert!(edits("UPDATE t SET a = 1 WHERE CURRENT OF c")
+            .iter()
+            .all(|e| e.kind != EditKind::Predicate));
+    }
+}
@@ -5,17 +5,28 @@
 //!
 //! Reuses the threaded grading and per-file coverage in [`crate::report`], the
 //! perf percentiles in `target/bench_dist/summary.csv`, the raw timings (for
-//! downsampled eCDF points), and the shared [`viz`] schema. Run via `sqlbench
-//! export` after `cargo bench` has produced the timing data.
+//! downsampled eCDF points and size-scaling fits), the lex-only and edit-reparse timings, the
+//! common-subset (head-to-head) timings and memory, the placeholder and
+//! error-recovery probes, recall per SQL construct, recall and
+//! parse time per statement kind, the pairwise parser disagreements, and the shared [`viz`] schema. Run via `sqlbench export` after `cargo bench` has
+//! produced the timing data.
 
 use crate::datasets::Dialect;
-use crate::report::{self, DialectReport};
-use crate::{bench_dist, contentious, stats, BenchParser, Parser};
+use crate::edit::EditKind;
+use crate::report::{self, DialectReport, ParserStat};
+use crate::stmt_kind::StatementKind;
+use crate::{
+    bench_dist, constructs, contentious, fuzz, gaps, placeholder, recovery, stats, subprocess,
+    BenchParser, Parser,
+};
 use std::cmp::Ordering;
 use std::path::Path;
 use viz::{
-    Bundle, CoverageFile, CoverageMatrix, DialectData, MemDist, ParserBatch, ParserFailures,
-    ParserMem, ParserMetrics, ParserPerf, RuleMeta,
+    Bundle, ConstructColumn, ConstructMatrix, CoverageFile, CoverageMatrix, DialectData,
+    DisagreementSample, EditKindTime, FailureCluster, KindPerf, KindRecall, KnownGap, MemDist,
+    PairDisagreement, ParserBatch, ParserConstructs, ParserEdit, ParserFailureClusters,
+    ParserFailures, ParserFuzz, ParserLex, ParserMem, ParserMetrics, ParserPerf,
+    ParserPlaceholders, ParserRecovery, PlaceholderMatrix, RuleMeta, Scaling,
 };
 
 /// Output path (relative to repo root, where `cargo run` runs from).
@@ -31,6 +42,9 @@ const FAIL_CAP: usize = 1000;
 /// Rejected statements shown inline as a preview on each parser page.
 const FAIL_PREVIEW: usize = 10;
 
+/// Equal-count size bins in each parser's time-against-size curve.
+const SCALING_BINS: usize = 24;
+
 /// Reference-backed dialects first, then provenance, matching the CLI order.
 const ORDER: [Dialect; 13] = [
     Dialect::Postgresql,
@@ -52,7 +66,7 @@ fn pct(n: usize, base: usize) -> Option<f64> {
     (base != 0).then(|| 100.0 * n as f64 / base as f64)
 }
 
-/// One parsed `summary.csv` row (all 14 columns).
+/// One parsed `summary.csv` row (the first 14 columns).
 struct PerfRow {
     dialect: String,
     parser: String,
@@ -149,6 +163,39 @@ fn metrics(report: &DialectReport) -> Vec<ParserMetrics> {
             attempted: s.attempted,
             panicked: s.panicked,
             panic_pct: pct(s.panicked, s.attempted),
+            ast_agreement_pct: pct(s.ast_agreed, s.ast_compared),
+            error_location_pct: pct(s.error_located, s.rejected_invalid),
+            error_position_pct: pct(s.error_near, s.error_scored),
+            span_accuracy_pct: pct(s.spans_correct, s.spans_checked),
+            split_precision_pct: s
+                .split
+                .and_then(|t| pct(t.overall().correct, t.overall().returned)),
+            split_recall_pct: s
+                .split
+                .and_then(|t| pct(t.overall().correct, t.overall().expected)),
+            split_compound_recall_pct: s
+                .split
+                .and_then(|t| pct(t.compound.correct, t.compound.expected)),
+            split_delimiter_recall_pct: s
+                .split
+                .and_then(|t| pct(t.delimiter.correct, t.delimiter.expected)),
+            by_kind: kind_recall(report, s),
+        })
+        .collect()
+}
+
+/// One parser's recall per statement kind, over the kinds with at least one
+/// valid statement.
+fn kind_recall(report: &DialectReport, s: &ParserStat) -> Vec<KindRecall> {
+    StatementKind::ALL
+        .iter()
+        .zip(report.kind_valid.iter().zip(&s.kind_accepted))
+        .filter(|(_, (&valid, _))| valid > 0)
+        .map(|(k, (&valid, &accepted))| KindRecall {
+            kind: k.label().to_string(),
+            valid,
+            accepted,
+            recall_pct: pct(accepted, valid),
         })
         .collect()
 }
@@ -164,7 +211,15 @@ fn perf_for(dir: &str, rows: &[PerfRow]) -> Vec<ParserPerf> {
                 .into_iter()
                 .map(|(x, y)| [x, y])
                 .collect();
-            perf_row_to_perf(r, stats::std_dev(&raw), ecdf)
+            let mut perf = perf_row_to_perf(r, stats::std_dev(&raw), ecdf);
+            perf.by_kind = StatementKind::ALL
+                .iter()
+                .filter_map(|&k| {
+                    kind_perf_from(k, &bench_dist::load_kind(dir, &r.parser, k.name()))
+                })
+                .collect();
+            perf.scaling = scaling_from(&bench_dist::load_sized(dir, &r.parser));
+            perf
         })
         .collect();
     v.sort_by(|a, b| a.median.partial_cmp(&b.median).unwrap_or(Ordering::Equal));
@@ -191,7 +246,56 @@ fn perf_row_to_perf(r: &PerfRow, std: f64, ecdf: Vec<[f64; 2]>) -> ParserPerf {
         std,
         roundtrip_pct: r.roundtrip_pct,
         ecdf,
+        by_kind: Vec::new(),
+        scaling: None,
+    }
+}
+
+/// Fit parse time against statement size. `None` with fewer than two sized
+/// timings (older bench output carries no sizes).
+fn scaling_from(sized: &[bench_dist::SizedTime]) -> Option<Scaling> {
+    let sized: Vec<_> = sized
+        .iter()
+        .filter(|t| t.bytes > 0 && t.tokens > 0)
+        .collect();
+    if sized.len() < 2 {
+        return None;
     }
+    let per = |size: fn(&bench_dist::SizedTime) -> usize| -> Vec<f64> {
+        let mut v: Vec<f64> = sized.iter().map(|t| t.ns / size(t) as f64).collect();
+        v.sort_by(f64::total_cmp);
+        v
+    };
+    let by_bytes: Vec<(f64, f64)> = sized.iter().map(|t| (t.bytes as f64, t.ns)).collect();
+    let by_tokens: Vec<(f64, f64)> = sized.iter().map(|t| (t.tokens as f64, t.ns)).collect();
+    let log_log: Vec<(f64, f64)> = by_bytes.iter().map(|&(b, ns)| (b.ln(), ns.ln())).collect();
+    let slope = |pts: &[(f64, f64)]| stats::linear_fit(pts).map(|(m, _)| m);
+    let mut ns_per_token = dist_from(&per(|t| t.tokens));
+    // Only the per-byte eCDF is drawn, so this one would be dead weight in
+    // the bundle.
+    ns_per_token.ecdf.clear();
+    Some(Scaling {
+        n: sized.len(),
+        ns_per_byte: dist_from(&per(|t| t.bytes)),
+        ns_per_token,
+        slope_ns_per_byte: slope(&by_bytes),
+        slope_ns_per_token: slope(&by_tokens),
+        exponent: slope(&log_log),
+        curve: stats::binned_medians(&by_bytes, SCALING_BINS),
+    })
+}
+
+/// Percentiles of one statement kind's ascending-sorted parse times. `None`
+/// when the parser accepted no statement of the kind.
+fn kind_perf_from(kind: StatementKind, sorted: &[f64]) -> Option<KindPerf> {
+    (!sorted.is_empty()).then(|| KindPerf {
+        kind: kind.label().to_string(),
+        n: sorted.len(),
+        p10: stats::quantile(sorted, 0.10),
+        median: stats::quantile(sorted, 0.50),
+        p90: stats::quantile(sorted, 0.90),
+        p99: stats::quantile(sorted, 0.99),
+    })
 }
 
 /// Build a byte distribution from an ascending-sorted sample (empty -> zeros).
@@ -202,15 +306,18 @@ fn dist_from(sorted: &[f64]) -> MemDist {
 
 /// Per-parser memory distributions for a dialect, read from `target/mem_dist`.
 /// Parsers with no measured file (e.g. the `libpg_query` bindings) are omitted.
-fn mem_for(dir: &str, parsers: &[BenchParser]) -> Vec<ParserMem> {
+/// With `common`, the distributions over the dialect's common subset
+/// ([`crate::common`]) instead of each parser's own accepted set.
+fn mem_for(dir: &str, parsers: &[BenchParser], common: bool) -> Vec<ParserMem> {
+    let tag = if common { "common." } else { "" };
     let mut out = Vec::new();
     for p in parsers {
         let name = p.name();
-        let peak = bench_dist::load_mem(dir, name, "peak");
+        let peak = bench_dist::load_mem(dir, name, &format!("{tag}peak"));
         if peak.is_empty() {
             continue;
         }
-        let retained = bench_dist::load_mem(dir, name, "retained");
+        let retained = bench_dist::load_mem(dir, name, &format!("{tag}retained"));
         out.push(ParserMem {
             parser: name.to_string(),
             n: peak.len(),
@@ -221,6 +328,216 @@ fn mem_for(dir: &str, parsers: &[BenchParser]) -> Vec<ParserMem> {
     out
 }
 
+/// Per-parser timing over a dialect's common subset ([`crate::common`]),
+/// sorted fastest-median first. The round-trip rate is carried over from the
+/// parser's own-set row in `perf`, and parsers without a common-subset timing
+/// file are omitted.
+fn perf_common_for(
+    dir: &str,
+    n_total: usize,
+    parsers: &[BenchParser],
+    perf: &[ParserPerf],
+) -> Vec<ParserPerf> {
+    let mut v: Vec<ParserPerf> = parsers
+        .iter()
+        .filter_map(|p| {
+            let times = bench_dist::load_common(dir, p.name());
+            (!times.is_empty()).then(|| {
+                let roundtrip = perf
+                    .iter()
+                    .find(|r| r.parser == p.name())
+                    .and_then(|r| r.roundtrip_pct);
+                stats::perf_from(
+                    p.name().to_string(),
+                    n_total,
+                    times.len(),
+                    roundtrip,
+                    &times,
+                )
+            })
+        })
+        .collect();
+    v.sort_by(|a, b| a.median.partial_cmp(&b.median).unwrap_or(Ordering::Equal));
+    v
+}
+
+/// The placeholder-style matrix for a dialect. `None` without templates.
+fn placeholders_for(dialect: Dialect, parsers: &[&dyn Parser]) -> Option<PlaceholderMatrix> {
+    let (templates, rows) = placeholder::placeholders_dialect(dialect, parsers);
+    (templates > 0).then(|| PlaceholderMatrix {
+        styles: placeholder::Style::ALL
+            .iter()
+            .map(|s| s.label().to_string())
+            .collect(),
+        templates,
+        parsers: rows
+            .into_iter()
+            .map(|r| ParserPlaceholders {
+                parser: r.parser.family.to_string(),
+                accepted_pct: r.accepted.iter().map(|&n| pct(n, r.base)).collect(),
+                base: r.base,
+            })
+            .collect(),
+    })
+}
+
+/// Recall per construct for a dialect, over the constructs its valid
+/// statements use at all.
+fn constructs_for(report: &DialectReport) -> Option<ConstructMatrix> {
+    let registry = constructs::registry();
+    let used: Vec<usize> = (0..registry.constructs.len())
+        .filter(|&i| report.construct_valid.get(i).is_some_and(|&n| n > 0))
+        .collect();
+    if used.is_empty() {
+        return None;
+    }
+    Some(ConstructMatrix {
+        constructs: used
+            .iter()
+            .map(|&i| {
+                let meta = &registry.constructs[i].meta;
+                ConstructColumn {
+                    id: meta.id.clone(),
+                    title: meta.title.clone(),
+                    description: meta.description.clone(),
+                    valid: report.construct_valid[i],
+                }
+            })
+            .collect(),
+        parsers: report
+            .parsers
+            .iter()
+            .zip(&report.stats)
+            .map(|(p, s)| ParserConstructs {
+                parser: p.family.to_string(),
+                recall_pct: used
+                    .iter()
+                    .map(|&i| {
+                        let accepted = s.construct_accepted.get(i).copied().unwrap_or(0);
+                        pct(accepted, report.construct_valid[i])
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+/// Per-parser error recovery for a dialect, with N/A rows for the parsers
+/// that cannot recover.
+fn recovery_for(dialect: Dialect, parsers: &[&dyn Parser]) -> Vec<ParserRecovery> {
+    recovery::recovery_dialect(dialect, parsers)
+        .into_iter()
+        .map(|r| ParserRecovery {
+            parser: r.parser.family.to_string(),
+            scripts: r.stat.map_or(0, |s| s.scripts),
+            recovered_pct: r.stat.and_then(|s| pct(s.recovered, s.expected)),
+            gave_up_pct: r.stat.and_then(|s| pct(s.gave_up, s.scripts)),
+        })
+        .collect()
+}
+
+/// Every pair of parsers in a dialect with the statements one accepts and the
+/// other rejects ([`report::disagreements_dialect`]).
+fn disagreements_for(dialect: Dialect, parsers: &[&dyn Parser]) -> Vec<PairDisagreement> {
+    report::disagreements_dialect(dialect, parsers)
+        .iter()
+        .map(pair_disagreement)
+        .collect()
+}
+
+fn pair_disagreement(d: &report::Disagreement) -> PairDisagreement {
+    let samples = |v: &[report::DisagreementSample]| {
+        v.iter()
+            .map(|x| DisagreementSample {
+                sql: x.sql.clone(),
+                reference: x.reference,
+            })
+            .collect()
+    };
+    PairDisagreement {
+        a: d.a.family.to_string(),
+        b: d.b.family.to_string(),
+        only_a: d.only_a,
+        only_b: d.only_b,
+        both: d.both,
+        samples_a: samples(&d.samples_a),
+        samples_b: samples(&d.samples_b),
+    }
+}
+
+/// Per-parser lex-only timings for a dialect, read from `target/bench_dist`.
+/// Parsers without a standalone lexer have no file and are omitted.
+fn lex_for(dir: &str, parsers: &[BenchParser]) -> Vec<ParserLex> {
+    let mut out = Vec::new();
+    for p in parsers {
+        let times = bench_dist::load_lex(dir, p.name());
+        if times.is_empty() {
+            continue;
+        }
+        out.push(ParserLex {
+            parser: p.name().to_string(),
+            n: times.len(),
+            time: dist_from(&times),
+        });
+    }
+    out
+}
+
+/// Per-parser edit-reparse timings for a dialect, read from the per-kind files
+/// in `target/bench_dist`, sorted fastest-median first. Parsers with no timed
+/// edit are omitted.
+fn edit_for(dir: &str, parsers: &[BenchParser]) -> Vec<ParserEdit> {
+    let mut out: Vec<ParserEdit> = parsers
+        .iter()
+        .filter_map(|p| {
+            let by_kind: Vec<(EditKind, Vec<f64>)> = EditKind::ALL
+                .iter()
+                .map(|&k| (k, bench_dist::load_edit(dir, p.name(), k.name())))
+                .collect();
+            edit_from(p.name(), p.incremental(), &by_kind)
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        a.time
+            .median
+            .partial_cmp(&b.time.median)
+            .unwrap_or(Ordering::Equal)
+    });
+    out
+}
+
+/// Pool per-kind ascending-sorted samples into a [`ParserEdit`]. `None` when
+/// every sample is empty.
+fn edit_from(
+    parser: &str,
+    incremental: bool,
+    by_kind: &[(EditKind, Vec<f64>)],
+) -> Option<ParserEdit> {
+    let mut all: Vec<f64> = by_kind
+        .iter()
+        .flat_map(|(_, t)| t.iter().copied())
+        .collect();
+    if all.is_empty() {
+        return None;
+    }
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    Some(ParserEdit {
+        parser: parser.to_string(),
+        incremental,
+        n: all.len(),
+        time: dist_from(&all),
+        kinds: by_kind
+            .iter()
+            .filter(|(_, t)| !t.is_empty())
+            .map(|(k, t)| EditKindTime {
+                kind: k.name().to_string(),
+                n: t.len(),
+                median: stats::quantile(t, 0.50),
+            })
+            .collect(),
+    })
+}
+
 /// One row of the batch time summary (`batch_dist/summary.csv`):
 /// `dialect,parser,n_eligible,k,n_correct,accuracy_pct,ns_per_stmt`. The last two
 /// may be blank (no batch parsed correctly).
@@ -371,6 +688,62 @@ fn build_coverage_matrix(cols: Vec<String>, files: &[report::FileCoverage]) -> C
     }
 }
 
+/// Per-parser fuzzing totals from [`fuzz::SUMMARY`], with the
+/// number of crasher files under [`fuzz::CRASHERS_DIR`]. Empty before the
+/// first `sqlbench fuzz`.
+fn fuzz_for(parsers: &[BenchParser]) -> Vec<ParserFuzz> {
+    let Ok(content) = std::fs::read_to_string(fuzz::SUMMARY) else {
+        return Vec::new();
+    };
+    let totals = parse_fuzz_summary(&content);
+    parsers
+        .iter()
+        .filter_map(|p| {
+            let &(_, inputs, panics, hangs) = totals.iter().find(|t| t.0 == p.name())?;
+            let dir = Path::new(fuzz::CRASHERS_DIR).join(stats::slug(p.name()));
+            let crashers = std::fs::read_dir(dir).map_or(0, |d| {
+                d.filter_map(Result::ok)
+                    .filter(|e| e.path().extension().is_some_and(|x| x == "sql"))
+                    .count()
+            });
+            Some(ParserFuzz {
+                parser: p.name().to_string(),
+                inputs,
+                panics,
+                hangs,
+                crashers,
+                failures_per_million: pct(panics + hangs, inputs).map(|x| x * 10_000.0),
+            })
+        })
+        .collect()
+}
+
+/// `(parser, inputs, panics, hangs)` summed over dialects from a fuzz summary.
+/// A summary written before hangs were counted has no `hangs` column (zero).
+fn parse_fuzz_summary(content: &str) -> Vec<(String, usize, usize, usize)> {
+    let mut out: Vec<(String, usize, usize, usize)> = Vec::new();
+    for line in content.lines().skip(1) {
+        let f: Vec<&str> = line.split('\t').collect();
+        let (Some(parser), Some(Ok(inputs)), Some(Ok(panics))) = (
+            f.first(),
+            f.get(2).map(|v| v.parse::<usize>()),
+            f.get(3).map(|v| v.parse::<usize>()),
+        ) else {
+            continue;
+        };
+        let hangs = f.get(4).and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+        match out.iter_mut().find(|t| t.0 == *parser) {
+            Some(t) => {
+                t.1 += inputs;
+                t.2 += panics;
+                t.3 += hangs;
+            }
+            None => out.push(((*parser).to_string(), inputs, panics, hangs)),
+        }
+    }
+    out
+}
+
 fn now_utc() -> String {
     std::process::Command::new("date")
         .args(["-u", "+%Y-%m-%dT%H:%M:%SZ"])
@@ -403,23 +776,33 @@ fn git_short() -> Option<String> {
 /// and return the per-parser previews + download paths for the JSON bundle.
 ///
 /// The TSV has a header and one statement per row, with embedded tabs/newlines
-/// escaped so each statement stays on a single line.
-fn failures_for(dir: &str, parsers: &[&dyn Parser]) -> Vec<ParserFailures> {
+/// escaped so each statement stays on a single line. Each parser's error
+/// clusters ([`report::cluster_failures`]) come back alongside.
+fn failures_for(
+    dir: &str,
+    parsers: &[&dyn Parser],
+) -> (Vec<ParserFailures>, Vec<ParserFailureClusters>) {
     let Some(dialect) = Dialect::from_dir_name(dir) else {
-        return Vec::new();
+        return (Vec::new(), Vec::new());
     };
     let mut out = Vec::new();
+    let mut clusters = Vec::new();
     for f in report::failures_dialect(dialect, parsers) {
         let name = f.parser.family;
+        clusters.push(clusters_for(&f, dialect));
         if f.rejected.is_empty() {
             out.push(ParserFailures {
                 parser: name.to_string(),
                 rejected_total: 0,
                 expected_total: f.total,
+                known_total: 0,
+                stale_total: 0,
                 preview_html: Vec::new(),
                 preview_reasons: Vec::new(),
                 preview_sql: Vec::new(),
                 preview_tags: Vec::new(),
+                preview_minimized: Vec::new(),
+                preview_issues: Vec::new(),
                 download: None,
             });
             continue;
@@ -455,34 +838,105 @@ fn failures_for(dir: &str, parsers: &[&dyn Parser]) -> Vec<ParserFailures> {
                     .map(|r| r.meta.id.clone())
             })
             .collect::<Vec<_>>();
+        let preview_minimized = f
+            .minimized
+            .iter()
+            .take(FAIL_PREVIEW)
+            .cloned()
+            .collect::<Vec<_>>();
+        // The known upstream issue for every rejection ([`gaps`]): its URL
+        // goes in the download, its id on the previewed rows.
+        let known: Vec<Option<&gaps::Gap>> = f
+            .rejected
+            .iter()
+            .zip(&f.reasons)
+            .map(|(s, r)| gaps::registry().known(&f.parser, dialect, s, r))
+            .collect();
+        let issues: Vec<Option<String>> = known
+            .iter()
+            .map(|g| g.map(|g| g.meta.url.clone()))
+            .collect();
         let file = format!("{dir}__{}.tsv.zst", stats::slug(name));
-        match write_failure_tsv(&file, &f.rejected, &f.reasons) {
-            Ok(()) => out.push(ParserFailures {
-                parser: name.to_string(),
-                rejected_total: f.rejected.len(),
-                expected_total: f.total,
-                preview_html: preview,
-                preview_reasons,
-                preview_sql,
-                preview_tags,
-                download: Some(format!("failures/{file}")),
-            }),
-            Err(e) => {
-                eprintln!("warning: could not write failures/{file}: {e}");
-                out.push(ParserFailures {
-                    parser: name.to_string(),
-                    rejected_total: f.rejected.len(),
-                    expected_total: f.total,
-                    preview_html: preview,
-                    preview_reasons,
-                    preview_sql,
-                    preview_tags,
-                    download: None,
-                });
-            }
-        }
+        let download =
+            match write_failure_tsv(&file, &f.rejected, &f.reasons, &f.minimized, &issues) {
+                Ok(()) => Some(format!("failures/{file}")),
+                Err(e) => {
+                    eprintln!("warning: could not write failures/{file}: {e}");
+                    None
+                }
+            };
+        out.push(ParserFailures {
+            parser: name.to_string(),
+            rejected_total: f.rejected.len(),
+            expected_total: f.total,
+            known_total: known.iter().flatten().count(),
+            stale_total: known
+                .iter()
+                .flatten()
+                .filter(|g| g.fixed_by(f.parser.version))
+                .count(),
+            preview_html: preview,
+            preview_reasons,
+            preview_sql,
+            preview_tags,
+            preview_minimized,
+            preview_issues: known
+                .iter()
+                .take(FAIL_PREVIEW)
+                .map(|g| g.map(|g| g.meta.id.clone()))
+                .collect(),
+            download,
+        });
+    }
+    (out, clusters)
+}
+
+/// Display metadata for every [`gaps`] entry, with whether the benchmarked
+/// build of its parser is at or past the version it was fixed in.
+fn known_gaps_for(parsers: &[&dyn Parser]) -> Vec<KnownGap> {
+    gaps::registry()
+        .gaps
+        .iter()
+        .map(|g| KnownGap {
+            id: g.meta.id.clone(),
+            title: g.meta.title.clone(),
+            parser: g.meta.parser.clone(),
+            url: g.meta.url.clone(),
+            status: g.meta.status.as_str().to_string(),
+            fixed_in: g.meta.fixed_in.clone(),
+            fixed_by_current: parsers
+                .iter()
+                .any(|p| p.id().family == g.meta.parser && g.fixed_by(p.id().version)),
+        })
+        .collect()
+}
+
+/// The export form of one parser's failure clusters.
+fn clusters_for(f: &report::ParserFailures, dialect: Dialect) -> ParserFailureClusters {
+    ParserFailureClusters {
+        parser: f.parser.family.to_string(),
+        rejected_total: f.rejected.len(),
+        clusters: f
+            .clusters
+            .iter()
+            .map(|c| FailureCluster {
+                message: c.signature.message.clone(),
+                head: c.signature.head.clone(),
+                shape: c.signature.shape.clone(),
+                count: c.count,
+                exemplar: f.rejected[c.exemplar].clone(),
+                exemplar_reason: f.reasons[c.exemplar].clone(),
+                issue: gaps::registry()
+                    .known(
+                        &f.parser,
+                        dialect,
+                        &f.rejected[c.exemplar],
+                        &f.reasons[c.exemplar],
+                    )
+                    .map(|g| g.meta.id.clone()),
+            })
+            .collect(),
     }
-    out
 }
 
 /// The shared syntax and theme sets, loaded once. Loading the bundled defaults
@@ -534,12 +988,19 @@ fn html_escape(s: &str) -> String {
 }
 
 /// Write up to [`FAIL_CAP`] rejected statements, each with the parser's error
-/// message, to a zstd-compressed two-column TSV under [`FAILURES_DIR`]. Tabs and
-/// newlines are escaped to keep one statement per row.
-fn write_failure_tsv(file: &str, rejected: &[String], reasons: &[String]) -> std::io::Result<()> {
+/// message, any minimized reproducer and any known upstream issue, to a
+/// zstd-compressed four-column TSV under [`FAILURES_DIR`]. Tabs and newlines
+/// are escaped to keep one statement per row.
+fn write_failure_tsv(
+    file: &str,
+    rejected: &[String],
+    reasons: &[String],
+    minimized: &[Option<String>],
+    issues: &[Option<String>],
+) -> std::io::Result<()> {
     std::fs::create_dir_all(FAILURES_DIR)?;
     let path = Path::new(FAILURES_DIR).join(file);
-    let tsv = format_failure_tsv(rejected, reasons, FAIL_CAP);
+    let tsv = format_failure_tsv(rejected, reasons, minimized, issues, FAIL_CAP);
 
     let raw = std::fs::File::create(&path)?;
     let mut enc = zstd::stream::Encoder::new(raw, 19)?;
@@ -548,22 +1009,40 @@ fn write_failure_tsv(file: &str, rejected: &[String], reasons: &[String]) -> std
     Ok(())
 }
 
-/// Build the TSV body for a failure download: a `statement\treason` header then
-/// up to `cap` rows. Each row is the rejected statement, a tab, then the parser's
-/// error message, with backslashes, tabs, and newlines escaped in both columns so
-/// every row stays on a single line. `reasons` is aligned with `rejected`. A
-/// missing reason is written as an empty cell.
-fn format_failure_tsv(rejected: &[String], reasons: &[String], cap: usize) -> String {
+/// Build the TSV body for a failure download: a
+/// `statement\treason\tminimized\tissue` header then up to `cap` rows.
+///
+/// Each row is the rejected statement, the parser's error message, the
+/// minimized reproducer, and the URL of the known upstream issue
+/// ([`crate::gaps`]), with backslashes, tabs, and newlines escaped in every
+/// column so each row stays on a single line. `reasons`, `minimized` and
+/// `issues` are aligned with `rejected`. A missing value is written as an
+/// empty cell.
+fn format_failure_tsv(
+    rejected: &[String],
+    reasons: &[String],
+    minimized: &[Option<String>],
+    issues: &[Option<String>],
+    cap: usize,
+) -> String {
     fn escape(s: &str) -> String {
         s.replace('\\', "\\\\")
             .replace('\t', "\\t")
             .replace('\n', "\\n")
     }
-    let mut tsv = String::from("statement\treason\n");
+    let mut tsv = String::from("statement\treason\tminimized\tissue\n");
     for (i, s) in rejected.iter().take(cap).enumerate() {
         tsv.push_str(&escape(s));
         tsv.push('\t');
         tsv.push_str(&escape(reasons.get(i).map_or("", String::as_str)));
+        tsv.push('\t');
+        tsv.push_str(&escape(
+            minimized.get(i).and_then(Option::as_deref).unwrap_or(""),
+        ));
+        tsv.push('\t');
+        tsv.push_str(&escape(
+            issues.get(i).and_then(Option::as_deref).unwrap_or(""),
+        ));
         tsv.push('\n');
     }
     tsv
@@ -575,8 +1054,15 @@ fn format_failure_tsv(rejected: &[String], reasons: &[String], cap: usize) -> St
 /// Returns an error if serialization or writing the output file fails.
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let parsers = BenchParser::all();
+    // Out-of-process parsers from the manifest join grading, coverage and
+    // failures. They have no timing or memory rows (see `subprocess`).
+    let external = subprocess::external_parsers();
     // The grading/coverage/failure functions are generic over `&dyn Parser`.
-    let dyn_parsers: Vec<&dyn Parser> = parsers.iter().map(|p| p as &dyn Parser).collect();
+    let dyn_parsers: Vec<&dyn Parser> = parsers
+        .iter()
+        .map(|p| p as &dyn Parser)
+        .chain(external.iter().map(|p| p as &dyn Parser))
+        .collect();
     let summary = read_summary();
     if summary.is_empty() {
         eprintln!(
@@ -600,6 +1086,14 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             continue;
         };
         eprintln!("exported {}", d.dir_name());
+        let (failures, failure_clusters) = failures_for(d.dir_name(), &dyn_parsers);
+        let perf = perf_for(d.dir_name(), &summary);
+        let perf_common = perf_common_for(
+            d.dir_name(),
+            report.valid_total + report.invalid_total,
+            &parsers,
+            &perf,
+        );
         dialects.push(DialectData {
             dir_name: d.dir_name().to_string(),
             display_name: d.display_name().to_string(),
@@ -608,18 +1102,31 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             invalid_total: report.invalid_total,
             contentious_valid: report.contentious_valid,
             correctness: metrics(&report),
-            perf: perf_for(d.dir_name(), &summary),
+            perf,
+            perf_common,
             coverage: coverage_for(d, &dyn_parsers),
-            failures: failures_for(d.dir_name(), &dyn_parsers),
-            memory: mem_for(d.dir_name(), &parsers),
+            failures,
+            failure_clusters,
+            memory: mem_for(d.dir_name(), &parsers, false),
+            memory_common: mem_for(d.dir_name(), &parsers, true),
             batch: batch_for(d.dir_name(), &batch_perf, &batch_mem),
+            placeholders: placeholders_for(d, &dyn_parsers),
+            constructs: constructs_for(&report),
+            recovery: recovery_for(d, &dyn_parsers),
+            lex: lex_for(d.dir_name(), &parsers),
+            edit: edit_for(d.dir_name(), &parsers),
+            disagreements: disagreements_for(d, &dyn_parsers),
         });
     }
 
     let bundle = Bundle {
         generated_utc: now_utc(),
         git_commit: git_short(),
-        parsers: parsers.iter().map(|p| p.name().to_string()).collect(),
+        parsers: dyn_parsers
+            .iter()
+            .map(|p| p.id().family.to_string())
+            .collect(),
+        out_of_process: external.iter().map(|p| p.id().family.to_string()).collect(),
         dialects,
         contentious_rules: contentious::registry()
             .rules
@@ -632,6 +1139,8 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 references: r.meta.references.clone(),
             })
             .collect(),
+        known_gaps: known_gaps_for(&dyn_parsers),
+        fuzz: fuzz_for(&parsers),
     };
 
     // Compact JSON, zstd-compressed: the viewer embeds and decompresses it in
@@ -655,8 +1164,9 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 #[cfg(test)]
 mod tests {
     use super::{
-        batch_for, build_coverage_matrix, format_failure_tsv, git_short, metrics, now_utc,
-        parse_batch_mem, parse_batch_perf, parse_summary, pct, perf_row_to_perf, PerfRow,
+        batch_for, build_coverage_matrix, edit_from, format_failure_tsv, git_short, kind_perf_from,
+        metrics, now_utc, pair_disagreement, parse_batch_mem, parse_batch_perf, parse_fuzz_summary,
+        parse_summary, pct, perf_row_to_perf, scaling_from, PerfRow,
     };
     use crate::datasets::Dialect;
     use crate::report::{DialectReport, FileCoverage};
@@ -688,6 +1198,66 @@ mod tests {
         assert_eq!(m[0].recall_pct, Some(80.0));
         assert_eq!(m[0].false_positive_pct, Some(25.0));
         assert_eq!(m[0].accept_pct, None); // None on reference dialects
+        assert_eq!(m[0].ast_agreement_pct, None); // nothing compared
+    }
+
+    #[test]
+    fn metrics_ast_agreement_is_share_of_compared() {
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let mut report = DialectReport::empty(Dialect::Postgresql, &parsers);
+        report.stats[0].ast_compared = 8;
+        report.stats[0].ast_agreed = 6;
+        assert_eq!(metrics(&report)[0].ast_agreement_pct, Some(75.0));
+    }
+
+    #[test]
+    fn metrics_error_position_is_share_of_scored() {
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let mut report = DialectReport::empty(Dialect::Postgresql, &parsers);
+        report.stats[0].rejected_invalid = 10;
+        report.stats[0].error_located = 8;
+        report.stats[0].error_scored = 4;
+        report.stats[0].error_near = 3;
+        let m = metrics(&report);
+        assert_eq!(m[0].error_location_pct, Some(80.0));
+        assert_eq!(m[0].error_position_pct, Some(75.0));
+    }
+
+    #[test]
+    fn metrics_span_accuracy_is_none_without_spans() {
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let mut report = DialectReport::empty(Dialect::Multi, &parsers);
+        assert_eq!(metrics(&report)[0].span_accuracy_pct, None);
+        report.stats[0].spans_checked = 20;
+        report.stats[0].spans_correct = 19;
+        assert_eq!(metrics(&report)[0].span_accuracy_pct, Some(95.0));
+    }
+
+    #[test]
+    fn metrics_split_pools_plain_and_compound_but_not_delimiter() {
+        use crate::split::{SplitStat, Tally};
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let mut report = DialectReport::empty(Dialect::Mysql, &parsers);
+        assert_eq!(metrics(&report)[0].split_recall_pct, None);
+        let tally = |returned, correct, expected| Tally {
+            returned,
+            correct,
+            expected,
+        };
+        report.stats[0].split = Some(SplitStat {
+            plain: tally(30, 30, 30),
+            compound: tally(12, 6, 10),
+            delimiter: tally(0, 0, 10),
+        });
+        let m = &metrics(&report)[0];
+        assert_eq!(m.split_precision_pct, Some(36.0 / 42.0 * 100.0));
+        assert_eq!(m.split_recall_pct, Some(90.0));
+        assert_eq!(m.split_compound_recall_pct, Some(60.0));
+        assert_eq!(m.split_delimiter_recall_pct, Some(0.0));
     }
 
     #[test]
@@ -704,6 +1274,123 @@ mod tests {
         assert_eq!(m[0].accept_pct, Some(75.0));
     }
 
+    #[test]
+    fn metrics_break_recall_down_by_statement_kind() {
+        use crate::stmt_kind::StatementKind;
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let mut report = DialectReport::empty(Dialect::Postgresql, &parsers);
+        report.kind_valid[StatementKind::Select.index()] = 8;
+        report.kind_valid[StatementKind::Alter.index()] = 2;
+        report.stats[0].kind_accepted[StatementKind::Select.index()] = 6;
+        let m = &metrics(&report)[0];
+        // Kinds absent from the corpus are dropped.
+        let kinds: Vec<&str> = m.by_kind.iter().map(|k| k.kind.as_str()).collect();
+        assert_eq!(kinds, ["SELECT", "ALTER"]);
+        assert_eq!(m.by_kind[0].recall_pct, Some(75.0));
+        assert_eq!(m.by_kind[1].recall_pct, Some(0.0));
+    }
+
+    #[test]
+    fn kind_perf_takes_percentiles_of_the_kind_sample() {
+        use crate::stmt_kind::StatementKind;
+        let times: Vec<f64> = (1..=100).map(f64::from).collect();
+        let k = kind_perf_from(StatementKind::Drop, &times).expect("timed statements");
+        assert_eq!(k.kind, "DROP");
+        assert_eq!(k.n, 100);
+        assert!((50.0..=51.0).contains(&k.median));
+        assert!((99.0..=100.0).contains(&k.p99));
+        assert!(kind_perf_from(StatementKind::Drop, &[]).is_none());
+    }
+
+    #[test]
+    fn scaling_separates_linear_from_quadratic_parsers() {
+        use crate::bench_dist::SizedTime;
+        let sized = |f: fn(f64) -> f64| -> Vec<SizedTime> {
+            (1..=200_u32)
+                .map(|i| SizedTime {
+                    ns: f(f64::from(i * 10)),
+                    bytes: i as usize * 10,
+                    tokens: i as usize * 2,
+                })
+                .collect()
+        };
+        let linear = scaling_from(&sized(|b| 4.0 * b)).expect("linear fit");
+        assert!((linear.exponent.unwrap() - 1.0).abs() < 1e-6);
+        assert!((linear.slope_ns_per_byte.unwrap() - 4.0).abs() < 1e-6);
+        assert!((linear.slope_ns_per_token.unwrap() - 20.0).abs() < 1e-6);
+        assert!((linear.ns_per_byte.median - 4.0).abs() < 1e-6);
+        assert!(linear.ns_per_token.ecdf.is_empty());
+        assert_eq!(linear.curve.len(), super::SCALING_BINS);
+        let quadratic = scaling_from(&sized(|b| b * b)).expect("quadratic fit");
+        assert!((quadratic.exponent.unwrap() - 2.0).abs() < 1e-6);
+        // Statements all of one size fit no line: no slope, rather than a flat 0.
+        let same = vec![
+            SizedTime {
+                ns: 7.0,
+                bytes: 10,
+                tokens: 2,
+            };
+            10
+        ];
+        let same = scaling_from(&same).expect("sized timings");
+        assert_eq!(
+            (
+                same.slope_ns_per_byte,
+                same.slope_ns_per_token,
+                same.exponent
+            ),
+            (None, None, None)
+        );
+        // Old bench output has no sizes.
+        assert!(scaling_from(&[]).is_none());
+    }
+
+    #[test]
+    fn edit_pools_kinds_and_keeps_per_kind_medians() {
+        use crate::edit::EditKind;
+        let by_kind = vec![
+            (EditKind::Rename, vec![10.0, 20.0, 30.0]),
+            (EditKind::Literal, vec![]),
+            (EditKind::Predicate, vec![40.0]),
+        ];
+        let e = edit_from("tree-sitter-sql", true, &by_kind).expect("timed edits");
+        assert!(e.incremental);
+        assert_eq!(e.n, 4);
+        assert!((e.time.min - 10.0).abs() < f64::EPSILON);
+        assert!((e.time.max - 40.0).abs() < f64::EPSILON);
+        // Kinds with no timed edit are dropped.
+        let kinds: Vec<&str> = e.kinds.iter().map(|k| k.kind.as_str()).collect();
+        assert_eq!(kinds, ["rename", "predicate"]);
+        assert!((e.kinds[0].median - 20.0).abs() < f64::EPSILON);
+        assert!(edit_from("x", false, &[(EditKind::Rename, vec![])]).is_none());
+    }
+
+    #[test]
+    fn disagreements_keep_both_sides_and_their_labels() {
+        let sample = |sql: &str, reference| crate::report::DisagreementSample {
+            sql: sql.to_string(),
+            reference,
+        };
+        let d = crate::report::Disagreement {
+            a: BenchParser::Sqlparser.id(),
+            b: BenchParser::PgQuery.id(),
+            only_a: 3,
+            only_b: 1,
+            both: 20,
+            samples_a: vec![sample("SELECT 1 1", Some(false))],
+            samples_b: vec![sample("SELECT $1", Some(true))],
+        };
+        let p = pair_disagreement(&d);
+        assert_eq!(
+            (p.a.as_str(), p.b.as_str()),
+            ("sqlparser-rs", "pg_query.rs")
+        );
+        assert_eq!((p.only_a, p.only_b, p.both), (3, 1, 20));
+        assert_eq!(p.samples_a[0].reference, Some(false));
+        assert_eq!(p.samples_b[0].sql, "SELECT $1");
+    }
+
     #[test]
     fn perf_row_maps_percentile_columns_in_order() {
         let p = perf_row_to_perf(&perf_row("sqlparser-rs"), 1.5, vec![[1.0, 0.5]]);
@@ -736,6 +1423,19 @@ mod tests {
         assert_eq!(cm.files.len(), 2);
     }
 
+    #[test]
+    fn fuzz_summary_sums_dialects_per_parser() {
+        let tsv = "parser\tdialect\tinputs\tpanics\thangs\n\
+                   a\tpostgresql\t100\t1\t0\n\
+                   b\tpostgresql\t100\t0\t2\n\
+                   a\tmysql\t50\t2\n\
+                   short line\n";
+        assert_eq!(
+            parse_fuzz_summary(tsv),
+            [("a".to_string(), 150, 3, 0), ("b".to_string(), 100, 0, 2)]
+        );
+    }
+
     #[test]
     fn now_utc_is_nonempty_iso_or_unix() {
         let s = now_utc();
@@ -789,27 +1489,35 @@ mod tests {
     fn tsv_has_header_and_one_row_per_statement() {
         let rows = vec!["SELECT 1".to_string(), "SELECT 2".to_string()];
         let reasons = vec!["boom".to_string(), "bang".to_string()];
-        let tsv = format_failure_tsv(&rows, &reasons, 1000);
+        let minimized = vec![Some("SELECT".to_string())];
+        let issues = vec![None, Some("https://example.invalid/1".to_string())];
+        let tsv = format_failure_tsv(&rows, &reasons, &minimized, &issues, 1000);
         let lines: Vec<&str> = tsv.lines().collect();
-        assert_eq!(lines[0], "statement\treason");
+        assert_eq!(lines[0], "statement\treason\tminimized\tissue");
         assert_eq!(lines.len(), 3); // header + 2 rows
-        assert_eq!(lines[1], "SELECT 1\tboom");
+        assert_eq!(lines[1], "SELECT 1\tboom\tSELECT\t");
+        // Past the minimized list the reproducer cell is empty.
+        assert_eq!(lines[2], "SELECT 2\tbang\t\thttps://example.invalid/1");
     }
 
     #[test]
     fn tsv_escapes_tabs_newlines_backslashes_in_both_columns() {
         let rows = vec!["a\tb\nc\\d".to_string()];
         let reasons = vec!["e\tf".to_string()];
-        let tsv = format_failure_tsv(&rows, &reasons, 1000);
-        // Statement and reason both stay on a single line with escapes.
-        assert_eq!(tsv, "statement\treason\na\\tb\\nc\\\\d\te\\tf\n");
+        let minimized = vec![Some("a\tb".to_string())];
+        let tsv = format_failure_tsv(&rows, &reasons, &minimized, &[], 1000);
+        // Every column stays on a single line with escapes.
+        assert_eq!(
+            tsv,
+            "statement\treason\tminimized\tissue\na\\tb\\nc\\\\d\te\\tf\ta\\tb\t\n"
+        );
     }
 
     #[test]
     fn tsv_writes_empty_cell_for_a_missing_reason() {
         let rows = vec!["SELECT 1".to_string()];
-        let tsv = format_failure_tsv(&rows, &[], 1000);
-        assert_eq!(tsv, "statement\treason\nSELECT 1\t\n");
+        let tsv = format_failure_tsv(&rows, &[], &[None], &[], 1000);
+        assert_eq!(tsv, "statement\treason\tminimized\tissue\nSELECT 1\t\t\t\n");
     }
 
     #[test]
@@ -883,7 +1591,7 @@ mod tests {
     fn tsv_respects_the_cap() {
         let rows: Vec<String> = (0..2000).map(|i| format!("SELECT {i}")).collect();
         let reasons: Vec<String> = (0..2000).map(|i| format!("err {i}")).collect();
-        let tsv = format_failure_tsv(&rows, &reasons, 1000);
+        let tsv = format_failure_tsv(&rows, &reasons, &[], &[], 1000);
         // header + cap rows
         assert_eq!(tsv.lines().count(), 1001);
     }
@@ -0,0 +1,446 @@
+//! Mutation fuzzing for the robustness metric.
+//!
+//! The corpus panic rate ([`crate::ParseOutcome::Panicked`] counted in
+//! [`crate::report::grade_chunk`]) only sees the SQL people wrote. This feeds
+//! every [`BenchParser`] corpus statements mutated at the token level
+//! ([`Mutation`]): tokens deleted, duplicated or swapped, fragments spliced in
+//! from another statement, and subexpressions nested deep in parentheses. The
+//! mutations are drawn from the same seeded [`SplitMix64`] the batch sampler
+//! uses, so a run is reproducible from its seed.
+//!
+//! Each panicking input is shrunk to a minimal one that still panics with the
+//! same message ([`crate::reduce`]), deduplicated by parser and message
+//! ([`signature`]), and written under [`CRASHERS_DIR`] with its panic message.
+//! An input the parser has not finished after [`TIMEOUT`] is a hang, counted
+//! as a failure alongside the panics and kept unminimized (each reduction
+//! step would cost a full timeout). The hung thread cannot be stopped, so the
+//! first hang also ends fuzzing of that (parser, dialect). Input, panic and hang counts per (parser,
+//! dialect) go to [`SUMMARY`], which the export turns into a "fuzz failures
+//! per million inputs" number. Run via `sqlbench fuzz`.
+
+use crate::batch::{seed_for, SplitMix64};
+use crate::datasets::Dialect;
+use crate::reduce::{lex, reduce_sql, REDUCE_BUDGET};
+use crate::report::{self, WORKER_STACK};
+use crate::stats::slug;
+use crate::{BenchParser, ParseOutcome, Parser};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+/// Directory of minimized crashers, one subdirectory per parser.
+pub const CRASHERS_DIR: &str = "fuzz/crashers";
+
+/// Per-(parser, dialect) input, panic and hang counts.
+pub const SUMMARY: &str = "fuzz/summary.tsv";
+
+/// Default mutated inputs per (parser, dialect).
+pub const INPUTS: usize = 5_000;
+
+/// Deepest parenthesis nesting a [`Mutation::Nest`] adds. Grading threads
+/// have [`WORKER_STACK`] of headroom, and a stack overflow aborts the whole
+/// run, so the depth stays well inside it.
+pub const NEST_MAX: usize = 200;
+
+/// How long one parse may run before the input counts as a hang. Corpus
+/// statements parse in well under a millisecond, so this only trips on a
+/// parser that loops or backtracks without bound.
+pub const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A token-level mutation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    /// Drop one token.
+    Delete,
+    /// Repeat one token.
+    Duplicate,
+    /// Exchange two tokens.
+    Swap,
+    /// Replace a run of tokens with a run from another statement.
+    Splice,
+    /// Wrap one token in up to [`NEST_MAX`] pairs of parentheses.
+    Nest,
+}
+
+impl Mutation {
+    pub const ALL: [Self; 5] = [
+        Self::Delete,
+        Self::Duplicate,
+        Self::Swap,
+        Self::Splice,
+        Self::Nest,
+    ];
+}
+
+/// Apply `m` to `toks` (non-empty), drawing from `donor` for a splice.
+fn apply(m: Mutation, toks: &mut Vec<String>, donor: &[&str], rng: &mut SplitMix64) {
+    let i = rng.below(toks.len());
+    match m {
+        Mutation::Delete => {
+            toks.remove(i);
+        }
+        Mutation::Duplicate => toks.insert(i, toks[i].clone()),
+        Mutation::Swap => {
+            let j = rng.below(toks.len());
+            toks.swap(i, j);
+        }
+        Mutation::Splice => {
+            if donor.is_empty() {
+                return;
+            }
+            let end = i + rng.below(toks.len() - i + 1);
+            let from = rng.below(donor.len());
+            let to = from + rng.below(donor.len() - from + 1);
+            let fragment = donor[from..to].iter().map(ToString::to_string);
+            toks.splice(i..end, fragment);
+        }
+        Mutation::Nest => {
+            let depth = 1 + rng.below(NEST_MAX);
+            toks[i] = format!("{}{}{}", "(".repeat(depth), toks[i], ")".repeat(depth));
+        }
+    }
+}
+
+/// One to three random mutations of `sql`, splicing from `donor`.
+#[must_use]
+pub(crate) fn mutate(sql: &str, donor: &str, rng: &mut SplitMix64) -> String {
+    let mut toks: Vec<String> = lex(sql).into_iter().map(ToString::to_string).collect();
+    let donor = lex(donor);
+    for _ in 0..=rng.below(3) {
+        if toks.is_empty() {
+            break;
+        }
+        apply(
+            Mutation::ALL[rng.below(Mutation::ALL.len())],
+            &mut toks,
+            &donor,
+            rng,
+        );
+    }
+    toks.join(" ")
+}
+
+/// A panic message with the parts that vary by input blurred: first line
+/// only, every digit run as `#`, at most 160 characters. Two crashers with
+/// the same parser and signature are the same bug.
+#[must_use]
+pub fn signature(message: &str) -> String {
+    let mut out = String::new();
+    let mut in_digits = false;
+    for c in message.lines().next().unwrap_or("").chars().take(160) {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                out.push('#');
+            }
+            in_digits = true;
+        } else {
+            out.push(c);
+            in_digits = false;
+        }
+    }
+    out
+}
+
+/// A minimized input that panics a parser, or an input it hangs on.
+pub struct Crasher {
+    pub parser: &'static str,
+    pub dialect: Dialect,
+    pub sql: String,
+    pub message: String,
+    /// The parser did not return within [`TIMEOUT`] (`message` says so).
+    pub hang: bool,
+}
+
+impl Crasher {
+    /// Stable file name from the parser and the panic [`signature`], so the
+    /// same bug found again overwrites its file.
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        let key = format!("{}\n{}", self.parser, signature(&self.message));
+        format!("{:016x}.sql", seed_for(&key))
+    }
+
+    /// The crasher file: the panic message (or hang) and dialect as comments,
+    /// then the SQL.
+    #[must_use]
+    pub fn contents(&self) -> String {
+        let message = self.message.lines().collect::<Vec<_>>().join(" ");
+        let what = if self.hang { "hang" } else { "panic" };
+        format!(
+            "-- parser: {}\n-- dialect: {}\n-- {what}: {message}\n{}\n",
+            self.parser,
+            self.dialect.dir_name(),
+            self.sql
+        )
+    }
+}
+
+/// One parser's fuzzing in one dialect.
+pub struct FuzzRun {
+    pub parser: &'static str,
+    pub dialect: Dialect,
+    pub inputs: usize,
+    pub panics: usize,
+    /// Inputs the parser had not finished after [`TIMEOUT`]: at most one, as
+    /// fuzzing stops at the first.
+    pub hangs: usize,
+    /// One minimized crasher per distinct panic signature, plus the hang.
+    pub crashers: Vec<Crasher>,
+}
+
+/// Runs parses on a worker thread so an input the parser never finishes can be
+/// abandoned after [`TIMEOUT`]. A hung parse cannot be interrupted, so its
+/// thread is left to spin until the process exits. To keep such threads from
+/// piling up, the watchdog never starts another: after the first hang every
+/// call returns `None` without parsing.
+struct Watchdog {
+    parser: BenchParser,
+    dialect: Dialect,
+    worker: Option<(Sender<String>, Receiver<ParseOutcome>)>,
+    /// The input whose parse was abandoned, once one has been.
+    hung: Option<String>,
+}
+
+impl Watchdog {
+    const fn new(parser: BenchParser, dialect: Dialect) -> Self {
+        Self {
+            parser,
+            dialect,
+            worker: None,
+            hung: None,
+        }
+    }
+
+    /// The parser's outcome on `sql`, or `None` if it did not return in time
+    /// (or an earlier input already hung).
+    fn outcome(&mut self, sql: &str) -> Option<ParseOutcome> {
+        if self.hung.is_some() {
+            return None;
+        }
+        let (tx, rx) = self.worker.take().unwrap_or_else(|| self.spawn());
+        tx.send(sql.to_string()).ok()?;
+        let Ok(outcome) = rx.recv_timeout(TIMEOUT) else {
+            self.hung = Some(sql.to_string());
+            return None;
+        };
+        self.worker = Some((tx, rx));
+        Some(outcome)
+    }
+
+    fn spawn(&self) -> (Sender<String>, Receiver<ParseOutcome>) {
+        let (parser, dialect) = (self.parser, self.dialect);
+        let (in_tx, in_rx) = channel::<String>();
+        let (out_tx, out_rx) = channel();
+        std::thread::Builder::new()
+            .stack_size(WORKER_STACK)
+            .spawn(move || {
+                for sql in in_rx {
+                    if out_tx.send(parser.parse_outcome(&sql, dialect)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("spawn fuzz worker");
+        (in_tx, out_rx)
+    }
+}
+
+/// Fuzz `parser` in `dialect` with `inputs` mutations of `corpus`.
+#[must_use]
+pub fn fuzz_parser(
+    parser: BenchParser,
+    dialect: Dialect,
+    corpus: &[String],
+    inputs: usize,
+    seed: u64,
+) -> FuzzRun {
+    let name = parser.name();
+    let mut rng = SplitMix64::new(seed_for(&format!(
+        "fuzz/{seed}/{name}/{}",
+        dialect.dir_name()
+    )));
+    let mut run = FuzzRun {
+        parser: name,
+        dialect,
+        inputs: 0,
+        panics: 0,
+        hangs: 0,
+        crashers: Vec::new(),
+    };
+    if corpus.is_empty() {
+        return run;
+    }
+    let mut dog = Watchdog::new(parser, dialect);
+    let mut found = HashSet::new();
+    for _ in 0..inputs {
+        let base = &corpus[rng.below(corpus.len())];
+        let donor = &corpus[rng.below(corpus.len())];
+        let sql = mutate(base, donor, &mut rng);
+        run.inputs += 1;
+        if let Some(ParseOutcome::Panicked(message)) = dog.outcome(&sql) {
+            run.panics += 1;
+            let sig = signature(&message);
+            if found.insert(sig.clone()) {
+                // A reduction step that hangs stops the reduction there.
+                let sql = reduce_sql(&sql, REDUCE_BUDGET, |s| {
+                    matches!(dog.outcome(s),
+                        Some(ParseOutcome::Panicked(m)) if signature(&m) == sig)
+                });
+                run.crashers.push(Crasher {
+                    parser: name,
+                    dialect,
+                    sql,
+                    message,
+                    hang: false,
+                });
+            }
+        }
+        // Each hang leaves a spinning thread behind that slows every later
+        // parse, so the first one ends this (parser, dialect).
+        if let Some(sql) = dog.hung.take() {
+            run.hangs += 1;
+            run.crashers.push(Crasher {
+                parser: name,
+                dialect,
+                sql,
+                message: format!("no result after {}s", TIMEOUT.as_secs()),
+                hang: true,
+            });
+            break;
+        }
+    }
+    run
+}
+
+/// Fuzz every parser in every dialect with a corpus, one [`WORKER_STACK`]
+/// thread per parser, and write the crashers and [`SUMMARY`] under `root`.
+///
+/// # Errors
+/// Fails if the crasher files or the summary cannot be written.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics outside a parse.
+#[allow(clippy::needless_collect)] // handles must all spawn before any join
+pub fn run(root: &Path, inputs: usize, seed: u64) -> std::io::Result<Vec<FuzzRun>> {
+    let mut runs = Vec::new();
+    for dialect in Dialect::ALL {
+        let corpus = report::load_dialect(dialect);
+        if corpus.is_empty() {
+            continue;
+        }
+        eprintln!("fuzzing {}...", dialect.dir_name());
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = BenchParser::all()
+                .into_iter()
+                .filter(|p| p.supports(dialect))
+                .map(|p| {
+                    let corpus = &corpus;
+                    std::thread::Builder::new()
+                        .stack_size(WORKER_STACK)
+                        .spawn_scoped(scope, move || fuzz_parser(p, dialect, corpus, inputs, seed))
+                        .expect("spawn worker")
+                })
+                .collect();
+            runs.extend(
+                handles
+                    .into_iter()
+                    .map(|h| h.join().expect("fuzz thread panicked")),
+            );
+        });
+    }
+    write(root, &runs)?;
+    Ok(runs)
+}
+
+/// Write each distinct crasher (the first found wins across dialects) and the
+/// summary table.
+fn write(root: &Path, runs: &[FuzzRun]) -> std::io::Result<()> {
+    let mut written = HashSet::new();
+    for c in runs.iter().flat_map(|r| &r.crashers) {
+        let dir = root.join(CRASHERS_DIR).join(slug(c.parser));
+        let file = c.file_name();
+        if written.insert((c.parser, file.clone())) {
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(dir.join(file), c.contents())?;
+        }
+    }
+    std::fs::write(root.join(SUMMARY), format_summary(runs))
+}
+
+/// The [`SUMMARY`] table: a header, then `parser, dialect, inputs, panics,
+/// hangs`.
+#[must_use]
+pub fn format_summary(runs: &[FuzzRun]) -> String {
+    let mut out = String::from("parser\tdialect\tinputs\tpanics\thangs\n");
+    for r in runs {
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}",
+            r.parser,
+            r.dialect.dir_name(),
+            r.inputs,
+            r.panics,
+            r.hangs
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mutate, signature, Crasher, Watchdog};
+    use crate::batch::SplitMix64;
+    use crate::datasets::Dialect;
+    use crate::{BenchParser, ParseOutcome};
+
+    #[test]
+    fn mutation_is_reproducible_from_the_seed() {
+        let run = |seed| {
+            let mut rng = SplitMix64::new(seed);
+            (0..20)
+                .map(|_| mutate("SELECT a FROM t WHERE b = 1", "SELECT 2", &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn signatures_blur_numbers_and_keep_the_first_line() {
+        assert_eq!(
+            signature("byte index 17 is out of range of 12\nbacktrace"),
+            "byte index # is out of range of #"
+        );
+        let c = |message: &str| Crasher {
+            parser: "p",
+            dialect: Dialect::Postgresql,
+            sql: String::new(),
+            message: message.to_string(),
+            hang: false,
+        };
+        assert_eq!(c("at 3").file_name(), c("at 40").file_name());
+        assert_ne!(c("at 3").file_name(), c("other").file_name());
+    }
+
+    #[test]
+    fn the_watchdog_hands_back_each_outcome() {
+        let mut dog = Watchdog::new(BenchParser::Sqlparser, Dialect::Postgresql);
+        assert!(matches!(
+            dog.outcome("SELECT 1"),
+            Some(ParseOutcome::Accepted)
+        ));
+        assert!(matches!(
+            dog.outcome("SELECT 1 FROM"),
+            Some(ParseOutcome::Rejected(_))
+        ));
+    }
+    #[test]
+    fn the_watchdog_parses_nothing_after_a_hang() {
+        let mut dog = Watchdog::new(BenchParser::Sqlparser, Dialect::Postgresql);
+        dog.hung = Some("SELECT".to_string());
+        assert!(dog.outcome("SELECT 1").is_none());
+        assert!(dog.worker.is_none());
+    }
+}
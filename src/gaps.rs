@@ -0,0 +1,375 @@
+//! Known gaps: rejections already reported to a parser's upstream.
+//!
+//! A parser author reading the failures view wants to know which rejections
+//! are new. Each entry is one TOML file under [`GAPS_DIR`], written like a
+//! contentious rule ([`crate::contentious`]): it names a parser family, the
+//! dialects it applies in, an upstream issue or PR, and a [`Status`]. It
+//! recognizes its rejections by a `pattern` (a regex over the
+//! [`contentious::mask`]ed statement), an `error` (a regex over the parser's
+//! error message), or both, in which case both must match.
+//!
+//! Export tags each rejected statement with the first entry that matches it
+//! ([`Registry::known`]). An entry marked fixed whose statements still fail in
+//! a build at or past the fixing version is flagged ([`Gap::fixed_by`]), since
+//! either the fix regressed or the entry is wrong.
+
+use crate::contentious;
+use crate::datasets::Dialect;
+use crate::ParserId;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Directory holding the committed entry files, relative to the working directory.
+pub const GAPS_DIR: &str = "gaps";
+
+/// Where an upstream report stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Reported and not yet fixed.
+    Open,
+    /// Fixed upstream in the entry's `fixed_in` version.
+    Fixed,
+    /// Upstream declined to support it.
+    Wontfix,
+}
+
+impl Status {
+    /// The lowercase wire form, also used in the entry files and the export.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Fixed => "fixed",
+            Self::Wontfix => "wontfix",
+        }
+    }
+}
+
+/// One entry file under [`GAPS_DIR`], deserialized from TOML.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GapFile {
+    pub id: String,
+    pub title: String,
+    /// The parser family the report is against (a [`ParserId::family`]).
+    pub parser: String,
+    /// Dir names of the dialects this entry applies in, or a single `"all"`.
+    pub dialects: Vec<String>,
+    /// Regex over the masked statement.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Regex over the parser's error message.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The upstream issue or PR.
+    pub url: String,
+    pub status: Status,
+    /// The first release with the fix (`fixed` entries only).
+    #[serde(default)]
+    pub fixed_in: Option<String>,
+    /// Statements `pattern` must match (verified in tests).
+    #[serde(default)]
+    pub matches: Vec<String>,
+    /// Statements `pattern` must not match (verified in tests).
+    #[serde(default)]
+    pub non_matches: Vec<String>,
+    /// Error messages `error` must match (verified in tests).
+    #[serde(default)]
+    pub error_matches: Vec<String>,
+}
+
+/// A loaded entry: its metadata plus its compiled patterns.
+pub struct Gap {
+    pub meta: GapFile,
+    pattern: Option<Regex>,
+    error: Option<Regex>,
+}
+
+impl Gap {
+    /// Whether this entry is about `parser` in `dialect`.
+    #[must_use]
+    pub fn applies_to(&self, parser: &str, dialect: Dialect) -> bool {
+        self.meta.parser == parser
+            && self
+                .meta
+                .dialects
+                .iter()
+                .any(|d| d == "all" || d == dialect.dir_name())
+    }
+
+    /// Whether the masked statement and the error message match every pattern
+    /// the entry declares.
+    fn is_match(&self, masked: &str, reason: &str) -> bool {
+        self.pattern.as_ref().is_none_or(|re| re.is_match(masked))
+            && self.error.as_ref().is_none_or(|re| re.is_match(reason))
+    }
+
+    /// Whether the entry says `version` has the fix, so a rejection it still
+    /// matches should not be happening.
+    #[must_use]
+    pub fn fixed_by(&self, version: &str) -> bool {
+        self.meta.status == Status::Fixed
+            && self
+                .meta
+                .fixed_in
+                .as_deref()
+                .is_some_and(|f| version_at_least(version, f))
+    }
+}
+
+/// Whether dotted version `v` is at or past `min`, comparing numeric
+/// components in order (missing ones count as zero, a non-numeric suffix
+/// such as `-rc1` is ignored).
+fn version_at_least(v: &str, min: &str) -> bool {
+    let parts = |s: &str| -> Vec<u64> {
+        s.trim_start_matches('v')
+            .split('.')
+            .map(|p| {
+                let digits: String = p.chars().take_while(char::is_ascii_digit).collect();
+                digits.parse().unwrap_or(0)
+            })
+            .collect()
+    };
+    let (have, want) = (parts(v), parts(min));
+    let at = |xs: &[u64], i: usize| xs.get(i).copied().unwrap_or(0);
+    (0..have.len().max(want.len()))
+        .map(|i| at(&have, i).cmp(&at(&want, i)))
+        .find(|o| o.is_ne())
+        .is_none_or(std::cmp::Ordering::is_gt)
+}
+
+/// The loaded gap registry.
+pub struct Registry {
+    pub gaps: Vec<Gap>,
+}
+
+impl Registry {
+    /// Load and compile every `*.toml` entry under `dir`, in file-name order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on a bad file, a bad regex, an entry with neither
+    /// `pattern` nor `error`, a `fixed` entry without `fixed_in` (or another
+    /// status with one), or a duplicate id.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(|e| format!("reading {}: {e}", dir.display()))?
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|x| x == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut gaps = Vec::new();
+        let mut ids = HashSet::new();
+        for path in paths {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| format!("reading {}: {e}", path.display()))?;
+            let meta: GapFile =
+                toml::from_str(&text).map_err(|e| format!("parsing {}: {e}", path.display()))?;
+            if !ids.insert(meta.id.clone()) {
+                return Err(format!("duplicate gap id `{}`", meta.id));
+            }
+            if meta.pattern.is_none() && meta.error.is_none() {
+                return Err(format!("gap `{}`: needs `pattern` or `error`", meta.id));
+            }
+            if (meta.status == Status::Fixed) != meta.fixed_in.is_some() {
+                return Err(format!(
+                    "gap `{}`: `fixed_in` goes with status `fixed` and nothing else",
+                    meta.id
+                ));
+            }
+            let compile = |pat: &Option<String>| {
+                pat.as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .map_err(|e| format!("gap `{}`: bad regex: {e}", meta.id))
+            };
+            let pattern = compile(&meta.pattern)?;
+            let error = compile(&meta.error)?;
+            gaps.push(Gap {
+                meta,
+                pattern,
+                error,
+            });
+        }
+        Ok(Self { gaps })
+    }
+
+    /// The first entry for `parser` in `dialect` that matches the rejection of
+    /// `sql` with `reason`, if any.
+    #[must_use]
+    pub fn known(
+        &self,
+        parser: &ParserId,
+        dialect: Dialect,
+        sql: &str,
+        reason: &str,
+    ) -> Option<&Gap> {
+        let masked = contentious::mask(sql);
+        self.gaps
+            .iter()
+            .find(|g| g.applies_to(parser.family, dialect) && g.is_match(&masked, reason))
+    }
+}
+
+/// The process-wide registry, loaded once from [`GAPS_DIR`].
+///
+/// # Panics
+///
+/// Panics with the load error if an entry is malformed, which fails the
+/// export build.
+#[must_use]
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        Registry::load(Path::new(GAPS_DIR)).unwrap_or_else(|e| panic!("gap registry: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contentious, version_at_least, Registry, GAPS_DIR};
+    use crate::datasets::Dialect;
+    use crate::ParserId;
+    use std::path::Path;
+
+    /// Unique scratch directory holding the given entry files.
+    fn temp_registry(tag: &str, files: &[(&str, &str)]) -> Result<Registry, String> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("sqlbench_gaps_{tag}_{nanos}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, text) in files {
+            std::fs::write(dir.join(name), text).unwrap();
+        }
+        let r = Registry::load(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        r
+    }
+
+    const WINDOW: &str = r#"
+id = "window-clause"
+title = "Named WINDOW clause"
+parser = "p"
+dialects = ["postgresql"]
+pattern = '(?i)\bwindow\s+\w+\s+as\b'
+url = "https://example.invalid/issues/1"
+status = "fixed"
+fixed_in = "0.10.0"
+"#;
+
+    fn id(family: &'static str, version: &'static str) -> ParserId {
+        ParserId {
+            family,
+            version,
+            released: "",
+        }
+    }
+
+    #[test]
+    fn committed_entries_load_and_match_their_examples() {
+        let r = Registry::load(Path::new(GAPS_DIR)).expect("load gap registry");
+        assert!(!r.gaps.is_empty(), "no entries under {GAPS_DIR}/");
+        for g in &r.gaps {
+            let dialect = g
+                .meta
+                .dialects
+                .iter()
+                .find_map(|d| Dialect::from_dir_name(d));
+            assert!(
+                dialect.is_some() || g.meta.dialects.iter().any(|d| d == "all"),
+                "gap `{}` declares no resolvable dialect",
+                g.meta.id
+            );
+            if let Some(re) = &g.pattern {
+                for ex in &g.meta.matches {
+                    assert!(
+                        re.is_match(&contentious::mask(ex)),
+                        "gap `{}` should match `{ex}`",
+                        g.meta.id
+                    );
+                }
+                for ex in &g.meta.non_matches {
+                    assert!(
+                        !re.is_match(&contentious::mask(ex)),
+                        "gap `{}` should not match `{ex}`",
+                        g.meta.id
+                    );
+                }
+            }
+            if let Some(re) = &g.error {
+                for ex in &g.meta.error_matches {
+                    assert!(
+                        re.is_match(ex),
+                        "gap `{}` should match error `{ex}`",
+                        g.meta.id
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn entries_are_scoped_to_their_parser_and_dialect() {
+        let r = temp_registry("scope", &[("w.toml", WINDOW)]).unwrap();
+        let sql = "SELECT a FROM t WINDOW w AS ()";
+        assert!(r
+            .known(&id("p", "0.9.0"), Dialect::Postgresql, sql, "boom")
+            .is_some());
+        assert!(r
+            .known(&id("q", "0.9.0"), Dialect::Postgresql, sql, "boom")
+            .is_none());
+        assert!(r
+            .known(&id("p", "0.9.0"), Dialect::Mysql, sql, "boom")
+            .is_none());
+        // The pattern sees the masked statement: a string literal cannot match.
+        assert!(r
+            .known(
+                &id("p", "0.9.0"),
+                Dialect::Postgresql,
+                "SELECT 'window w as'",
+                "boom"
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn a_fixed_entry_flags_builds_at_or_past_the_fix() {
+        let r = temp_registry("fixed", &[("w.toml", WINDOW)]).unwrap();
+        let g = &r.gaps[0];
+        assert!(!g.fixed_by("0.9.9"));
+        assert!(g.fixed_by("0.10.0"));
+        assert!(g.fixed_by("0.62.0"));
+        assert!(g.fixed_by("1.0"));
+        assert!(version_at_least("0.10.0", "0.9.12"));
+        assert!(!version_at_least("0.3.11", "0.3.12-rc1"));
+    }
+
+    #[test]
+    fn malformed_entries_are_rejected() {
+        let no_fixed_in = WINDOW.replace("fixed_in = \"0.10.0\"\n", "");
+        assert!(temp_registry("nofix", &[("w.toml", &no_fixed_in)]).is_err());
+        let no_pattern = WINDOW.replace("pattern = '(?i)\\bwindow\\s+\\w+\\s+as\\b'\n", "");
+        assert!(temp_registry("nopat", &[("w.toml", &no_pattern)]).is_err());
+        assert!(temp_registry("dup", &[("a.toml", WINDOW), ("b.toml", WINDOW)]).is_err());
+        let error_only = no_pattern.replace(
+            "status = \"fixed\"\nfixed_in = \"0.10.0\"\n",
+            "status = \"open\"\nerror = 'found: WINDOW'\n",
+        );
+        let r = temp_registry("err", &[("w.toml", &error_only)]).unwrap();
+        assert!(r
+            .known(
+                &id("p", "1.0.0"),
+                Dialect::Postgresql,
+                "SELECT 1",
+                "Expected end, found: WINDOW"
+            )
+            .is_some());
+    }
+}
@@ -0,0 +1,88 @@
+//! Incremental reparse after an edit.
+//!
+//! An editor keeps the previous tree and, on each keystroke, tells the parser
+//! which bytes changed so it can reuse the untouched subtrees. tree-sitter is
+//! built around that workflow, so for tree-sitter-sql the edit-reparse axis
+//! (see [`crate::edit`]) times this path instead of a cold parse: the
+//! statement's tree is built once, then each timed iteration edits a copy and
+//! reparses the edited text against it.
+
+use crate::edit::Edit;
+use tree_sitter::{InputEdit, Point, Tree};
+
+/// A statement ready for timed incremental reparses: the tree of the original
+/// text, the edited text, and the edit that turns one into the other.
+pub struct Reparse {
+    parser: tree_sitter::Parser,
+    old: Tree,
+    edited: String,
+    edit: InputEdit,
+}
+
+impl Reparse {
+    /// Parse `sql` and prepare `edit` against its tree. `None` if the parse is
+    /// cancelled.
+    #[must_use]
+    pub fn new(sql: &str, edit: &Edit) -> Option<Self> {
+        let mut parser = crate::tree_sitter_parser();
+        let old = parser.parse(sql, None)?;
+        let edited = edit.apply(sql);
+        let new_end = edit.range.start + edit.replacement.len();
+        let edit = InputEdit {
+            start_byte: edit.range.start,
+            old_end_byte: edit.range.end,
+            new_end_byte: new_end,
+            start_position: point_at(sql, edit.range.start),
+            old_end_position: point_at(sql, edit.range.end),
+            new_end_position: point_at(&edited, new_end),
+        };
+        Some(Self {
+            parser,
+            old,
+            edited,
+            edit,
+        })
+    }
+
+    /// One timed iteration: apply the edit to a copy of the old tree (a
+    /// reference-count bump) and reparse the edited text against it. Returns
+    /// whether the new tree is error-free.
+    pub fn run(&mut self) -> bool {
+        let mut tree = self.old.clone();
+        tree.edit(&self.edit);
+        self.parser
+            .parse(&self.edited, Some(&tree))
+            .is_some_and(|t| !t.root_node().has_error())
+    }
+}
+
+/// Row and byte column of `byte` in `text`, as tree-sitter counts them.
+fn point_at(text: &str, byte: usize) -> Point {
+    let before = &text[..byte];
+    let row = before.bytes().filter(|&b| b == b'\n').count();
+    let column = before.rfind('\n').map_or(byte, |nl| byte - nl - 1);
+    Point { row, column }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{point_at, Reparse};
+    use crate::edit::edits;
+
+    #[test]
+    fn an_edited_tree_reparses_to_the_edited_statement() {
+        let sql = "SELECT a FROM t WHERE b = 1";
+        for edit in edits(sql) {
+            let mut r = Reparse::new(sql, &edit).expect("parse");
+            assert!(r.run(), "{:?} edit should reparse cleanly", edit.kind);
+        }
+    }
+
+    #[test]
+    fn points_count_rows_and_byte_columns() {
+        let sql = "SELECT a\nFROM t";
+        assert_eq!(point_at(sql, 3).row, 0);
+        assert_eq!(point_at(sql, 14).row, 1);
+        assert_eq!(point_at(sql, 14).column, 5);
+    }
+}
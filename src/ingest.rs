@@ -0,0 +1,944 @@
+//! Corpus ingestion: a directory of engine test files in, one corpus file out.
+//!
+//! A new source arrives as a tree of `.sql` scripts and `.test` files (`SQLite`'s
+//! TCL suites, or sqllogictest records as `DuckDB` and others ship them). The
+//! `ingest` binary runs [`ingest`] over it: pull the SQL out of each file, cut
+//! it into statements with the dialect's [`Splitter`], normalize each to one
+//! line, drop unbalanced fragments and anything the dialect's corpus already
+//! holds (compared by [`statement_hash`]), then write
+//! `datasets/{dialect}/{name}.txt` with its [`Provenance`] next to it as
+//! `{name}.toml`.
+//!
+//! The splitter generalizes the ones `build_sqlite_suite` and
+//! `build_proc_suites` were written with, which now call it with their original
+//! rules: top-level `;` only, with the compound statements each dialect's
+//! [`Blocks`] rule names kept whole, plus whatever its client adds on top of
+//! `;` ([`Directives`]).
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::datasets::Dialect;
+use crate::oracle_cache::statement_hash;
+use crate::report::load_dialect_files_from;
+
+/// SQL*Plus client commands: a line starting with one, outside a statement, is
+/// not SQL and is dropped.
+pub const SQLPLUS_COMMANDS: &[&str] = &[
+    "PROMPT",
+    "SET ",
+    "DEFINE",
+    "UNDEFINE",
+    "SPOOL",
+    "WHENEVER",
+    "CONNECT",
+    "ALTER SESSION",
+    "COLUMN ",
+    "ACCEPT ",
+    "PAUSE",
+    "EXEC ",
+    "EXECUTE ",
+    "VARIABLE ",
+    "VAR ",
+    "PRINT ",
+    "SHOW ",
+    "BREAK",
+    "COMPUTE ",
+    "TTITLE",
+    "BTITLE",
+    "STORE ",
+    "SAVE ",
+    "HOST",
+    "CLEAR ",
+    "TIMING",
+    "START ",
+    "ACCEPT",
+];
+
+/// TCL test commands whose first brace-delimited argument is a SQL script.
+/// `do_*_test` take a test name first, `execsql`/`catchsql` take the script
+/// directly; in both cases the first `{...}` group after the keyword is the SQL.
+const TCL_SQL_CMDS: [&str; 5] = [
+    "do_execsql_test",
+    "do_catchsql_test",
+    "do_eqp_test",
+    "execsql",
+    "catchsql",
+];
+
+/// Script-level terminators a dialect's client adds on top of `;`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directives {
+    /// `;` only.
+    None,
+    /// The mysql client's `DELIMITER <token>` line, which swaps the terminator
+    /// so a routine body can hold `;`.
+    Delimiter,
+    /// Spark's golden-test `--QUERY-DELIMITER-START` / `--QUERY-DELIMITER-END`
+    /// markers: everything between a pair is one statement.
+    QueryDelimiter,
+    /// SQL*Plus: a PL/SQL block (`DECLARE`, `BEGIN`, `CREATE PROCEDURE`, ...)
+    /// runs until a line holding only `/`, and [`SQLPLUS_COMMANDS`], `REM` and
+    /// `@file` lines are dropped.
+    SqlPlus,
+    /// sqlcmd's `GO` batch separator line.
+    Go,
+}
+
+/// Which compound statements keep the `;` inside them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blocks {
+    /// None: every top-level `;` ends a statement (Spark's golden tests mark
+    /// their compound statements with directives instead).
+    None,
+    /// `CASE ... END` and the `BEGIN ... END` body of a `CREATE TRIGGER`.
+    Trigger,
+    /// As `Trigger`, plus procedure and function bodies, in which `END IF`,
+    /// `END LOOP`, `END WHILE`, `END REPEAT` and `END CASE` close nothing.
+    Routine,
+}
+
+/// How one dialect's scripts are cut into statements.
+///
+/// Quoting decides where a `;` is literal text. Every dialect gets `'...'`,
+/// `"..."` and `` `...` `` with doubling escapes and `--` / `/* */` comments;
+/// the flags add the rest.
+#[allow(clippy::struct_excessive_bools)] // independent quoting switches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Splitter {
+    /// `[...]` quotes an identifier (`SQLite`, T-SQL, Spark's test inputs).
+    pub brackets: bool,
+    /// A backslash escapes the next character inside `'...'` and `"..."`.
+    pub backslash: bool,
+    /// `$tag$ ... $tag$` quoting (`PostgreSQL` and its descendants).
+    pub dollar: bool,
+    /// No quote or comment runs past the end of its line, and a `/* */`
+    /// comment leaves no space behind, as the Spark corpus was first read.
+    pub per_line: bool,
+    pub blocks: Blocks,
+    pub directives: Directives,
+}
+
+impl Splitter {
+    /// The rules for `dialect`'s scripts. `multi` mixes sources, so it gets
+    /// the common core only.
+    #[must_use]
+    pub const fn for_dialect(dialect: Dialect) -> Self {
+        let plain = Self {
+            brackets: false,
+            backslash: false,
+            dollar: false,
+            per_line: false,
+            blocks: Blocks::Routine,
+            directives: Directives::None,
+        };
+        match dialect {
+            Dialect::Postgresql | Dialect::Redshift | Dialect::Duckdb => Self {
+                dollar: true,
+                ..plain
+            },
+            Dialect::Mysql => Self {
+                backslash: true,
+                directives: Directives::Delimiter,
+                ..plain
+            },
+            Dialect::Clickhouse | Dialect::Hive | Dialect::Bigquery => Self {
+                backslash: true,
+                ..plain
+            },
+            Dialect::SparkSql => Self {
+                brackets: true,
+                per_line: true,
+                blocks: Blocks::None,
+                directives: Directives::QueryDelimiter,
+                ..plain
+            },
+            Dialect::Sqlite => Self {
+                brackets: true,
+                blocks: Blocks::Trigger,
+                ..plain
+            },
+            Dialect::Tsql => Self {
+                brackets: true,
+                directives: Directives::Go,
+                ..plain
+            },
+            Dialect::Oracle => Self {
+                directives: Directives::SqlPlus,
+                ..plain
+            },
+            Dialect::Trino | Dialect::Multi => plain,
+        }
+    }
+
+    /// Split script text into normalized one-line statements, comments removed.
+    ///
+    /// A `;` ends a statement only at top level: not inside a quote or comment
+    /// or a compound statement the [`Blocks`] rule keeps whole. Directive lines
+    /// are honored as [`Directives`] describes.
+    #[must_use]
+    pub fn split(self, input: &str) -> Vec<String> {
+        let mut scan = Scan::new(self, input);
+        while scan.i < scan.chars.len() {
+            scan.step();
+        }
+        scan.flush();
+        scan.out
+    }
+
+    /// Whether `stmt` closes every quote and block comment it opens.
+    ///
+    /// A one-line corpus statement must be balanced: an unbalanced one is not
+    /// valid SQL, and an oracle that batches statements line by line (`SQLite`'s
+    /// `EXPLAIN` batch) would read one unterminated quote as swallowing every
+    /// line after it.
+    #[must_use]
+    pub fn is_balanced(self, stmt: &str) -> bool {
+        let chars: Vec<char> = stmt.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '-' if chars.get(i + 1) == Some(&'-') => return true,
+                '/' if chars.get(i + 1) == Some(&'*') => match comment_end(&chars, i) {
+                    Some(end) => i = end,
+                    None => return false,
+                },
+                _ if self.opens_quote(&chars, i) => match self.quote_end(&chars, i) {
+                    Some(end) => i = end,
+                    None => return false,
+                },
+                _ => i += 1,
+            }
+        }
+        true
+    }
+
+    /// Whether a quoted literal or identifier starts at `chars[i]`.
+    fn opens_quote(self, chars: &[char], i: usize) -> bool {
+        match chars[i] {
+            '\'' | '"' | '`' => true,
+            '[' => self.brackets,
+            '$' => self.dollar && dollar_tag(chars, i).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Index just past the quote opened at `chars[i]`, `None` if it never closes.
+    fn quote_end(self, chars: &[char], i: usize) -> Option<usize> {
+        let open = chars[i];
+        if open == '$' {
+            let tag = &chars[i..dollar_tag(chars, i)?];
+            let body = i + tag.len();
+            return (body..=chars.len().saturating_sub(tag.len()))
+                .find(|&j| chars[j..].starts_with(tag))
+                .map(|j| j + tag.len());
+        }
+        let close = if open == '[' { ']' } else { open };
+        let mut j = i + 1;
+        while j < chars.len() {
+            let c = chars[j];
+            if c == '\\' && self.backslash && open != '`' && open != '[' {
+                j += 2;
+                continue;
+            }
+            if c == close {
+                // Doubling escape ('' "" ``); brackets have none.
+                if close != ']' && chars.get(j + 1) == Some(&close) {
+                    j += 2;
+                    continue;
+                }
+                return Some(j + 1);
+            }
+            j += 1;
+        }
+        None
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Index just past the word starting at `chars[i]`.
+fn word_end(chars: &[char], i: usize) -> usize {
+    chars[i..]
+        .iter()
+        .position(|&c| !is_word(c))
+        .map_or(chars.len(), |p| i + p)
+}
+
+/// Index just past the `*/` closing the comment opened at `chars[i]`.
+fn comment_end(chars: &[char], i: usize) -> Option<usize> {
+    (i + 2..chars.len().saturating_sub(1))
+        .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+        .map(|j| j + 2)
+}
+
+/// Index just past a `$tag$` opener at `chars[i]`: an empty or identifier tag,
+/// not glued to a preceding word and not a `$1` parameter.
+fn dollar_tag(chars: &[char], i: usize) -> Option<usize> {
+    if i > 0 && (is_word(chars[i - 1]) || chars[i - 1] == '$') {
+        return None;
+    }
+    let end = word_end(chars, i + 1);
+    let starts_with_digit = chars.get(i + 1).is_some_and(char::is_ascii_digit);
+    (chars.get(end) == Some(&'$') && !starts_with_digit).then_some(end + 1)
+}
+
+/// Collapse whitespace runs, including any inside quoted literals, so the
+/// statement is one line (string contents do not affect parse benchmarking).
+#[must_use]
+pub fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The splitter's running state over one script.
+struct Scan {
+    rules: Splitter,
+    chars: Vec<char>,
+    i: usize,
+    buf: String,
+    out: Vec<String>,
+    /// The current terminator, `;` unless a `DELIMITER` line changed it.
+    terminator: Vec<char>,
+    case_depth: usize,
+    block_depth: usize,
+    /// A `TRIGGER`/`PROCEDURE`/`FUNCTION` word was seen, so `BEGIN` opens a body.
+    body: bool,
+    /// A SQL*Plus PL/SQL block: only a `/` line ends it.
+    plsql: bool,
+    /// Inside a `--QUERY-DELIMITER` region.
+    region: bool,
+}
+
+impl Scan {
+    fn new(rules: Splitter, input: &str) -> Self {
+        Self {
+            rules,
+            chars: input.chars().collect(),
+            i: 0,
+            buf: String::new(),
+            out: Vec::new(),
+            terminator: vec![';'],
+            case_depth: 0,
+            block_depth: 0,
+            body: false,
+            plsql: false,
+            region: false,
+        }
+    }
+
+    fn step(&mut self) {
+        let i = self.i;
+        if (i == 0 || self.chars[i - 1] == '\n') && self.directive_line() {
+            return;
+        }
+        let chars = &self.chars;
+        let c = chars[i];
+        // Quotes and comments end with the text, or with the line when per-line.
+        let limit = if self.rules.per_line {
+            chars[i..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(chars.len(), |p| i + p)
+        } else {
+            chars.len()
+        };
+        if c == '-' && chars.get(i + 1) == Some(&'-') {
+            self.i = chars[i..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(chars.len(), |p| i + p);
+            self.push_space();
+            return;
+        }
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            self.i = comment_end(&chars[..limit], i).unwrap_or(limit);
+            if !self.rules.per_line {
+                self.push_space();
+            }
+            return;
+        }
+        let top_level =
+            !self.region && !self.plsql && self.case_depth == 0 && self.block_depth == 0;
+        if top_level && chars[i..].starts_with(&self.terminator) {
+            self.i += self.terminator.len();
+            self.flush();
+            return;
+        }
+        if self.rules.opens_quote(chars, i) {
+            let end = self.rules.quote_end(&chars[..limit], i).unwrap_or(limit);
+            self.buf.extend(&chars[i..end]);
+            self.i = end;
+            return;
+        }
+        if is_word(c) {
+            self.word();
+            return;
+        }
+        if c.is_whitespace() {
+            self.push_space();
+        } else {
+            self.buf.push(c);
+        }
+        self.i += 1;
+    }
+
+    /// Handle a client directive on the line starting at `self.i`, skipping it.
+    fn directive_line(&mut self) -> bool {
+        let end = self.chars[self.i..]
+            .iter()
+            .position(|&c| c == '\n')
+            .map_or(self.chars.len(), |p| self.i + p);
+        let line: String = self.chars[self.i..end].iter().collect();
+        let line = line.trim();
+        let upper = line.to_ascii_uppercase();
+        let handled = match self.rules.directives {
+            Directives::None => false,
+            Directives::Delimiter => {
+                let token = upper.strip_prefix("DELIMITER ").map(|_| line[10..].trim());
+                if let Some(token) = token.filter(|t| !t.is_empty()) {
+                    self.flush();
+                    self.terminator = token.chars().collect();
+                }
+                token.is_some()
+            }
+            Directives::QueryDelimiter => {
+                // Text pending before a start marker joins the region.
+                let start = line.starts_with("--QUERY-DELIMITER-START");
+                let marker = start || line.starts_with("--QUERY-DELIMITER-END");
+                if marker {
+                    if !start {
+                        self.flush();
+                    }
+                    self.region = start;
+                }
+                marker
+            }
+            Directives::SqlPlus if line == "/" => {
+                self.flush();
+                true
+            }
+            Directives::SqlPlus => {
+                self.buf.trim().is_empty()
+                    && (line.starts_with('@')
+                        || upper == "REM"
+                        || upper.starts_with("REM ")
+                        || SQLPLUS_COMMANDS.iter().any(|d| upper.starts_with(d)))
+            }
+            Directives::Go => {
+                let go = upper == "GO"
+                    || upper
+                        .strip_prefix("GO ")
+                        .is_some_and(|n| n.trim().bytes().all(|b| b.is_ascii_digit()));
+                if go {
+                    self.flush();
+                }
+                go
+            }
+        };
+        if handled {
+            self.i = end;
+        }
+        handled
+    }
+
+    /// Copy the word at `self.i` and apply its effect on block tracking.
+    fn word(&mut self) {
+        let start = self.i;
+        let statement_start = self.buf.trim().is_empty();
+        self.i = word_end(&self.chars, start);
+        let word: String = self.chars[start..self.i].iter().collect();
+        self.buf.push_str(&word);
+        let sqlplus = self.rules.directives == Directives::SqlPlus;
+        let upper = word.to_ascii_uppercase();
+        if sqlplus
+            && matches!(
+                upper.as_str(),
+                "PROCEDURE" | "FUNCTION" | "PACKAGE" | "TRIGGER" | "TYPE"
+            )
+            && self.buf.to_ascii_uppercase().starts_with("CREATE")
+        {
+            self.plsql = true;
+        }
+        let blocks = self.rules.blocks;
+        match upper.as_str() {
+            "DECLARE" | "BEGIN" if sqlplus && statement_start => self.plsql = true,
+            _ if blocks == Blocks::None => {}
+            "TRIGGER" => self.body = true,
+            "PROCEDURE" | "PROC" | "FUNCTION" if blocks == Blocks::Routine => self.body = true,
+            "CASE" => self.case_depth += 1,
+            "END" => self.end(),
+            // A bare BEGIN (a transaction) opens nothing; only a routine or
+            // trigger body, or a block nested in one, does.
+            "BEGIN" if self.body || self.block_depth > 0 => self.block_depth += 1,
+            _ => {}
+        }
+    }
+
+    /// Close what an `END` closes. Under [`Blocks::Routine`], `END IF`, `END
+    /// LOOP`, `END WHILE`, `END REPEAT` and `END CASE` end a control statement,
+    /// not a block: the second word is taken along so `CASE` is not read as an
+    /// opener.
+    fn end(&mut self) {
+        let gap = self.chars[self.i..]
+            .iter()
+            .take_while(|&&c| c == ' ' || c == '\t')
+            .count();
+        let next_start = self.i + gap;
+        let next_end = word_end(&self.chars, next_start);
+        let next: String = self.chars[next_start..next_end].iter().collect();
+        let upper = next.to_ascii_uppercase();
+        let control = matches!(upper.as_str(), "IF" | "LOOP" | "WHILE" | "REPEAT" | "CASE");
+        if control && self.rules.blocks == Blocks::Routine {
+            if upper == "CASE" {
+                self.case_depth = self.case_depth.saturating_sub(1);
+            }
+            self.push_space();
+            self.buf.push_str(&next);
+            self.i = next_end;
+        } else if self.case_depth > 0 {
+            self.case_depth -= 1;
+        } else if self.block_depth > 0 {
+            self.block_depth -= 1;
+        }
+    }
+
+    /// Push a single normalizing space (collapse runs, skip leading).
+    fn push_space(&mut self) {
+        if !self.buf.is_empty() && !self.buf.ends_with(' ') {
+            self.buf.push(' ');
+        }
+    }
+
+    fn flush(&mut self) {
+        let s = normalize(&self.buf);
+        if !s.is_empty() {
+            self.out.push(s);
+        }
+        self.buf.clear();
+        self.case_depth = 0;
+        self.block_depth = 0;
+        self.body = false;
+        self.plsql = false;
+    }
+}
+
+const fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Index just past the `}` matching the `{` at `open`, using TCL brace rules:
+/// braces nest, but `\{`, `\}`, and `\\` are escapes that do not affect nesting
+/// (no other substitution happens inside braces). `None` if unbalanced.
+fn matching_brace(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                i += 2; // skip the escaped byte (\{ \} \\ \<newline>)
+                continue;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Extract the SQL scripts from a TCL `.test` file.
+///
+/// For each SQL command keyword at a word boundary, the next brace-delimited
+/// group is the script. Bodies are returned verbatim (TCL does no substitution inside
+/// braces, so `$x` and `[...]` are literal) for the caller to split. Forms that
+/// pass the script as a quoted/substituted string (not `{...}`) are skipped.
+#[must_use]
+pub fn tcl_sql_bodies(input: &str) -> Vec<String> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let kw_len = TCL_SQL_CMDS.iter().find_map(|kw| {
+            let k = kw.as_bytes();
+            let boundary_before = i == 0 || !is_word_byte(bytes[i - 1]);
+            let boundary_after = bytes
+                .get(i + k.len())
+                .is_some_and(|&c| c == b' ' || c == b'\t' || c == b'{');
+            (boundary_before && boundary_after && bytes[i..].starts_with(k)).then_some(k.len())
+        });
+        let Some(kw_len) = kw_len else {
+            i += 1;
+            continue;
+        };
+        // Scan past the test name / options to the script's opening brace. Bail
+        // on a double quote (quoted script form we do not handle) or an
+        // unescaped end of line, so we never grab a later command's brace group.
+        let mut j = i + kw_len;
+        let body_open = loop {
+            match bytes.get(j) {
+                Some(b'{') => break Some(j),
+                Some(b'"') | None => break None,
+                Some(b'\n') => {
+                    if j > 0 && bytes[j - 1] == b'\\' {
+                        j += 1;
+                        continue;
+                    }
+                    break None;
+                }
+                Some(_) => j += 1,
+            }
+        };
+        match body_open.and_then(|o| matching_brace(bytes, o).map(|e| (o, e))) {
+            Some((o, e)) => {
+                out.push(input[o + 1..e - 1].to_string());
+                i = e;
+            }
+            None => i += kw_len,
+        }
+    }
+    out
+}
+
+/// Extract the SQL of each sqllogictest record: the lines after a `statement`
+/// or `query` header, up to a blank line or the `----` result marker.
+///
+/// A `statement error` record expects the engine to reject its SQL, so it is
+/// skipped rather than added as a valid statement.
+#[must_use]
+pub fn slt_sql_bodies(input: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut lines = input.lines();
+    while let Some(line) = lines.next() {
+        let header = line.trim_start();
+        if !(header.starts_with("statement ") || header.starts_with("query ")) {
+            continue;
+        }
+        let body: Vec<&str> = lines
+            .by_ref()
+            .take_while(|l| !l.trim().is_empty() && l.trim() != "----")
+            .collect();
+        let expects_error = header.split_whitespace().nth(1) == Some("error");
+        if !body.is_empty() && !expects_error {
+            out.push(body.join("\n"));
+        }
+    }
+    out
+}
+
+/// The SQL scripts in one source file: a `.test` file is read as TCL if it
+/// holds any SQL command and as sqllogictest otherwise; anything else is a
+/// plain script.
+#[must_use]
+pub fn sql_bodies(path: &Path, content: &str) -> Vec<String> {
+    if path.extension().is_some_and(|x| x == "test") {
+        let tcl = tcl_sql_bodies(content);
+        if tcl.is_empty() {
+            slt_sql_bodies(content)
+        } else {
+            tcl
+        }
+    } else {
+        vec![content.to_string()]
+    }
+}
+
+/// Every `.sql` and `.test` file under `dir`, recursively, in path order.
+#[must_use]
+pub fn source_files(dir: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(d) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&d) else {
+            continue;
+        };
+        for p in entries.flatten().map(|e| e.path()) {
+            if p.is_dir() {
+                stack.push(p);
+            } else if p.extension().is_some_and(|x| x == "sql" || x == "test") {
+                out.push(p);
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+/// Where an ingested corpus file came from, stored next to it as `{name}.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Where the files were taken from, usually the upstream repository URL.
+    pub source: String,
+    /// The source's license, as an SPDX identifier.
+    pub license: String,
+    /// The upstream commit or release tag the files were taken at.
+    pub commit: String,
+}
+
+/// What one [`ingest`] run did.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Ingested {
+    /// Source files read.
+    pub files: usize,
+    /// Statements split out of them.
+    pub split: usize,
+    /// Statements dropped for an unclosed quote or comment.
+    pub unbalanced: usize,
+    /// Statements dropped as already in the dialect's corpus or seen earlier
+    /// in this run.
+    pub duplicates: usize,
+    /// Statements written, in source order.
+    pub kept: usize,
+}
+
+/// Ingest the `.sql`/`.test` files under `src` as `{root}/{dialect}/{name}.txt`,
+/// with `provenance` written to `{name}.toml` beside it.
+///
+/// Statements are deduped by [`statement_hash`] against every other corpus file
+/// of the dialect; an existing `{name}.txt` is replaced, so rerunning after an
+/// upstream bump refreshes the file rather than deduping it away.
+///
+/// # Errors
+/// Returns an error if `name` is not a plain file stem, if no new statement is
+/// left to write, or if reading a source or writing the output fails.
+pub fn ingest(
+    src: &Path,
+    root: &Path,
+    dialect: Dialect,
+    name: &str,
+    provenance: &Provenance,
+) -> io::Result<Ingested> {
+    if name.is_empty() || !name.bytes().all(|b| is_word_byte(b) || b == b'-') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("corpus name `{name}` must be letters, digits, `_` or `-`"),
+        ));
+    }
+    let file = format!("{name}.txt");
+    let mut seen: HashSet<u64> = load_dialect_files_from(root, dialect)
+        .into_iter()
+        .filter(|(f, _)| *f != file)
+        .flat_map(|(_, stmts)| stmts)
+        .map(|s| statement_hash(&s))
+        .collect();
+    let splitter = Splitter::for_dialect(dialect);
+    let files = source_files(src);
+    let mut report = Ingested {
+        files: files.len(),
+        ..Ingested::default()
+    };
+    let mut kept = Vec::new();
+    for f in &files {
+        let content = fs::read_to_string(f)?;
+        for stmt in sql_bodies(f, &content)
+            .iter()
+            .flat_map(|b| splitter.split(b))
+        {
+            report.split += 1;
+            if !splitter.is_balanced(&stmt) {
+                report.unbalanced += 1;
+            } else if seen.insert(statement_hash(&stmt)) {
+                kept.push(stmt);
+            } else {
+                report.duplicates += 1;
+            }
+        }
+    }
+    if kept.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "no new {} statements under {}",
+                dialect.dir_name(),
+                src.display()
+            ),
+        ));
+    }
+    report.kept = kept.len();
+    let dir = root.join(dialect.dir_name());
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(&file), format!("{}\n", kept.join("\n")))?;
+    let record = toml::to_string(provenance).map_err(io::Error::other)?;
+    fs::write(dir.join(format!("{name}.toml")), record)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ingest, matching_brace, sql_bodies, Ingested, Provenance, Splitter};
+    use crate::datasets::Dialect;
+    use std::fs;
+    use std::path::Path;
+
+    fn split(dialect: Dialect, sql: &str) -> Vec<String> {
+        Splitter::for_dialect(dialect).split(sql)
+    }
+
+    #[test]
+    fn quoting_follows_the_dialect() {
+        let pg = "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql; SELECT $1;";
+        assert_eq!(
+            split(Dialect::Postgresql, pg),
+            [
+                "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql",
+                "SELECT $1"
+            ]
+        );
+        let mysql = r"SELECT 'it\'s; here'; SELECT 2";
+        assert_eq!(
+            split(Dialect::Mysql, mysql),
+            [r"SELECT 'it\'s; here'", "SELECT 2"]
+        );
+        // Without backslash escapes the first `'` after `\` closes the literal.
+        assert_eq!(split(Dialect::Sqlite, "SELECT '\\'; SELECT 2").len(), 2);
+        assert_eq!(
+            split(Dialect::Tsql, "SELECT [a;b] FROM t"),
+            ["SELECT [a;b] FROM t"]
+        );
+    }
+
+    #[test]
+    fn routine_bodies_stay_whole_but_control_ends_close_nothing() {
+        let sql = "DELIMITER //\nCREATE PROCEDURE p() BEGIN IF x THEN SELECT 1; END IF; \
+                   CASE y WHEN 1 THEN SELECT 2; END CASE; END//\nDELIMITER ;\nSELECT 3;";
+        assert_eq!(
+            split(Dialect::Mysql, sql),
+            [
+                "CREATE PROCEDURE p() BEGIN IF x THEN SELECT 1; END IF; \
+                 CASE y WHEN 1 THEN SELECT 2; END CASE; END",
+                "SELECT 3"
+            ]
+        );
+        let tsql = "CREATE PROCEDURE p AS BEGIN SELECT 1; END\nGO\nSELECT 2\nGO";
+        assert_eq!(
+            split(Dialect::Tsql, tsql),
+            ["CREATE PROCEDURE p AS BEGIN SELECT 1; END", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn sqlite_and_spark_keep_their_original_rules() {
+        // Only a trigger opens a body in SQLite, and `END IF` closes it.
+        let sql = "CREATE PROCEDURE p BEGIN SELECT 1; END; \
+                   CREATE TRIGGER r BEGIN SELECT 2; END IF; SELECT 3";
+        assert_eq!(
+            split(Dialect::Sqlite, sql),
+            [
+                "CREATE PROCEDURE p BEGIN SELECT 1",
+                "END",
+                "CREATE TRIGGER r BEGIN SELECT 2; END IF",
+                "SELECT 3"
+            ]
+        );
+        // Spark: no blocks or backslash escapes, `[` quotes, quotes and comments
+        // end with their line, and text before a region marker joins the region.
+        let spark = "SELECT/*c*/1; SELECT [a;b], 'x\\'; BEGIN SELECT 'open\n;\n\
+                     SELECT\n--QUERY-DELIMITER-START\n1;\n--QUERY-DELIMITER-END\n";
+        assert_eq!(
+            split(Dialect::SparkSql, spark),
+            [
+                "SELECT1",
+                "SELECT [a;b], 'x\\'",
+                "BEGIN SELECT 'open",
+                "SELECT 1;"
+            ]
+        );
+    }
+
+    #[test]
+    fn sqlplus_blocks_end_at_a_slash_line() {
+        let sql = "PROMPT loading\nREM note\nCREATE PROCEDURE p IS v NUMBER;\nBEGIN\n  v := 1;\nEND;\n/\nSELECT 1 FROM dual;";
+        assert_eq!(
+            split(Dialect::Oracle, sql),
+            [
+                "CREATE PROCEDURE p IS v NUMBER; BEGIN v := 1; END;",
+                "SELECT 1 FROM dual"
+            ]
+        );
+    }
+
+    #[test]
+    fn balance_follows_the_dialect_quoting() {
+        let sqlite = Splitter::for_dialect(Dialect::Sqlite);
+        assert!(sqlite.is_balanced("SELECT '\\' /* c */"));
+        assert!(!sqlite.is_balanced("SELECT [a"));
+        let pg = Splitter::for_dialect(Dialect::Postgresql);
+        assert!(pg.is_balanced("SELECT $q$ it's $q$"));
+        assert!(!pg.is_balanced("SELECT $q$ open"));
+        assert!(!Splitter::for_dialect(Dialect::Mysql).is_balanced(r"SELECT 'a\'"));
+    }
+
+    #[test]
+    fn brace_matching_honors_backslash_escapes_and_nesting() {
+        // Balanced inner braces (JSON) are spanned; \{ and \} do not nest.
+        let s = b"{ '{\"a\":1}' }";
+        assert_eq!(matching_brace(s, 0), Some(s.len()));
+        let esc = b"{ a \\{ b }";
+        assert_eq!(matching_brace(esc, 0), Some(esc.len()));
+        assert_eq!(matching_brace(b"{ unbalanced ", 0), None);
+    }
+
+    #[test]
+    fn test_files_are_read_as_tcl_or_sqllogictest() {
+        let path = Path::new("x.test");
+        let tcl = "do_execsql_test t-1 {\n  SELECT 1;\n} {1}\n";
+        assert_eq!(sql_bodies(path, tcl), ["\n  SELECT 1;\n"]);
+        let slt = "# c\nstatement ok\nCREATE TABLE t (a int)\n\nstatement error\nSELEC 1\n\n\
+                   query I\nSELECT a\nFROM t\n----\n1\n";
+        assert_eq!(
+            sql_bodies(path, slt),
+            ["CREATE TABLE t (a int)", "SELECT a\nFROM t"]
+        );
+        assert_eq!(sql_bodies(Path::new("x.sql"), "SELECT 1"), ["SELECT 1"]);
+    }
+
+    #[test]
+    fn ingest_dedupes_against_the_corpus_and_records_provenance() {
+        let tmp = std::env::temp_dir().join(format!("ingest-{}", std::process::id()));
+        let (src, root) = (tmp.join("src"), tmp.join("datasets"));
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::create_dir_all(root.join("duckdb")).unwrap();
+        fs::write(root.join("duckdb/old.txt"), "SELECT 1\n").unwrap();
+        fs::write(src.join("a.sql"), "SELECT 1; SELECT  2;\nSELECT 'x").unwrap();
+        fs::write(
+            src.join("nested/b.test"),
+            "statement ok\nSELECT 2\n\nquery I\nSELECT 3\n----\n3\n",
+        )
+        .unwrap();
+        let prov = Provenance {
+            source: "https://example.org/suite".into(),
+            license: "MIT".into(),
+            commit: "v1.0".into(),
+        };
+        let got = ingest(&src, &root, Dialect::Duckdb, "suite", &prov).unwrap();
+        assert_eq!(
+            got,
+            Ingested {
+                files: 2,
+                split: 5,
+                unbalanced: 1,
+                duplicates: 2,
+                kept: 2,
+            }
+        );
+        let written = fs::read_to_string(root.join("duckdb/suite.txt")).unwrap();
+        assert_eq!(written, "SELECT 2\nSELECT 3\n");
+        let record = fs::read_to_string(root.join("duckdb/suite.toml")).unwrap();
+        assert_eq!(toml::from_str::<Provenance>(&record).unwrap(), prov);
+        // A rerun replaces its own file instead of deduping against it.
+        assert!(ingest(&src, &root, Dialect::Duckdb, "suite", &prov).is_ok());
+        assert!(ingest(&src, &root, Dialect::Duckdb, "../x", &prov).is_err());
+        fs::remove_dir_all(&tmp).unwrap();
+    }
+}
@@ -3,18 +3,22 @@ use databend_common_ast::parser::{
 };
 use orql::parser as orql_parser;
 use polyglot_sql::{parse as polyglot_parse, DialectType, Generator as PolyglotGenerator};
-use qusql_parse::{parse_statements, Issues, Level, ParseOptions, SQLDialect};
+use qusql_parse::{parse_statements, Issues, Level, ParseOptions, SQLDialect, Spanned};
 use sqlparser::dialect::PostgreSqlDialect;
 use sqlparser::parser::Parser as SqlparserParser;
+use sqlparser::tokenizer::{Token as SqlToken, Tokenizer as SqlTokenizer};
 
 use crate::datasets::Dialect;
+use crate::diagnostics::{ErrorLocation, ParseError};
 use fallible_iterator::FallibleIterator as _;
 use sqlglot_rust::Dialect as SqlglotDialect;
+use sqlite3_parser::lexer::Scanner;
 use sqlparser::dialect::{
     BigQueryDialect, ClickHouseDialect, DatabricksDialect, Dialect as SqlparserDialect,
     DuckDbDialect, GenericDialect, HiveDialect, MsSqlDialect, MySqlDialect, OracleDialect,
     RedshiftSqlDialect, SQLiteDialect,
 };
+use std::ops::Range;
 
 // Multi-dialect benchmark layer. Each parser runs in its best-matching dialect.
 // One it does not model returns `None` (N/A). Correctness uses reference where
@@ -123,8 +127,9 @@ pub enum ParseOutcome {
     Unsupported,
     /// Parsed successfully.
     Accepted,
-    /// Rejected with the parser's own error message.
-    Rejected(String),
+    /// Rejected with the parser's own error message and, where it reports one,
+    /// the error location.
+    Rejected(ParseError),
     /// Aborted with a caught panic, carrying the panic message.
     Panicked(String),
 }
@@ -134,7 +139,7 @@ pub enum ParseOutcome {
 /// Several parsers use `todo!()`/`panic!`/`unreachable!` on unimplemented paths,
 /// so this keeps the worker alive and records that the abort was a panic, which
 /// the empirical panic-rate metric counts separately from honest `Err` returns.
-fn catch_outcome(f: impl FnOnce() -> Result<(), String>) -> ParseOutcome {
+fn catch_outcome(f: impl FnOnce() -> Result<(), ParseError>) -> ParseOutcome {
     match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
         Ok(Ok(())) => ParseOutcome::Accepted,
         Ok(Err(e)) => ParseOutcome::Rejected(e),
@@ -146,7 +151,7 @@ fn catch_outcome(f: impl FnOnce() -> Result<(), String>) -> ParseOutcome {
 // `catch_outcome` wrapping them can tell a panic from an honest `Err`. They are
 // only ever called inside `catch_outcome`.
 
-fn qusql_raw(sql: &str, d: SQLDialect) -> Result<(), String> {
+fn qusql_raw(sql: &str, d: SQLDialect) -> Result<(), ParseError> {
     let opts = ParseOptions::new()
         .dialect(d)
         .arguments(qusql_parse::SQLArguments::Dollar);
@@ -156,27 +161,71 @@ fn qusql_raw(sql: &str, d: SQLDialect) -> Result<(), String> {
         .get()
         .iter()
         .find(|i| i.level == Level::Error)
-        .map_or(Ok(()), |e| Err(e.message.to_string()))
+        .map_or(Ok(()), |e| {
+            Err(ParseError::at(
+                e.message.to_string(),
+                Some(ErrorLocation::Offset(e.span.start)),
+            ))
+        })
 }
 
-fn databend_raw(sql: &str, d: DatabendDialect) -> Result<(), String> {
-    let tokens = databend_tokenize(sql).map_err(|e| e.to_string())?;
-    databend_parse(&tokens, d)
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+/// A `pg_query` rejection located by its "at or near" token. A repeated token is
+/// disambiguated by re-parsing prefixes: the real error point is the last one
+/// whose prefix still only fails at end of input.
+fn pg_error(sql: &str, message: String) -> ParseError {
+    let location = diagnostics::near_token(&message, sql, |prefix| match pg_query::parse(prefix) {
+        Ok(_) => true,
+        Err(e) => e.to_string().contains("at end of input"),
+    });
+    ParseError::at(message, location)
 }
 
-fn sqlite3_raw(sql: &str) -> Result<(), String> {
+fn databend_raw(sql: &str, d: DatabendDialect) -> Result<(), ParseError> {
+    // databend's error carries the span of the offending token.
+    let located = |e: databend_common_ast::ParseError| {
+        let location = e.0.map(|span| ErrorLocation::Offset(span.start as usize));
+        ParseError::at(e.to_string(), location)
+    };
+    let tokens = databend_tokenize(sql).map_err(located)?;
+    databend_parse(&tokens, d).map(|_| ()).map_err(located)
+}
+
+fn sqlite3_raw(sql: &str) -> Result<(), ParseError> {
     let mut parser = sqlite3_parser::lexer::sql::Parser::new(sql.as_bytes());
     loop {
         match parser.next() {
             Ok(Some(_)) => {}
             Ok(None) => return Ok(()),
-            Err(e) => return Err(e.to_string()),
+            Err(e) => return Err(sqlite3_error(&e)),
         }
     }
 }
 
+/// A lemon-rs rejection at the `(line, column)` its scanner was on.
+fn sqlite3_error(e: &sqlite3_parser::lexer::sql::Error) -> ParseError {
+    use sqlite3_parser::lexer::sql::Error as E;
+    let position = match e {
+        E::UnrecognizedToken(p)
+        | E::UnterminatedLiteral(p)
+        | E::UnterminatedBracket(p)
+        | E::UnterminatedBlockComment(p)
+        | E::BadVariableName(p)
+        | E::BadNumber(p)
+        | E::ExpectedEqualsSign(p)
+        | E::MalformedBlobLiteral(p)
+        | E::MalformedHexInteger(p)
+        | E::ParserError(_, p) => *p,
+        _ => None,
+    };
+    let location = position.and_then(|(line, column)| {
+        Some(ErrorLocation::LineCol {
+            line: usize::try_from(line).ok()?,
+            column,
+        })
+    });
+    ParseError::at(e.to_string(), location)
+}
+
 fn sqlite3_reprint(sql: &str) -> Option<String> {
     std::panic::catch_unwind(|| {
         let mut parser = sqlite3_parser::lexer::sql::Parser::new(sql.as_bytes());
@@ -197,17 +246,42 @@ fn sqlite3_reprint(sql: &str) -> Option<String> {
     .unwrap_or(None)
 }
 
-fn turso_raw(sql: &str) -> Result<(), String> {
+fn turso_raw(sql: &str) -> Result<(), ParseError> {
     let mut parser = turso_parser::parser::Parser::new(sql.as_bytes());
     loop {
         match parser.next_cmd() {
             Ok(Some(_)) => {}
             Ok(None) => return Ok(()),
-            Err(e) => return Err(e.to_string()),
+            Err(e) => return Err(turso_error(sql, &e)),
         }
     }
 }
 
+/// A turso rejection at the byte span its lexer or parser labels.
+fn turso_error(sql: &str, e: &turso_parser::error::Error) -> ParseError {
+    use turso_parser::error::Error as E;
+    let offset = match e {
+        E::UnrecognizedToken(span)
+        | E::UnterminatedLiteral(span)
+        | E::UnterminatedBracket(span)
+        | E::UnterminatedBlockComment(span)
+        | E::BadVariableName(span)
+        | E::BadNumber(span)
+        | E::BadFractionalPart(span)
+        | E::BadExponentPart(span)
+        | E::ExpectedEqualsSign(span)
+        | E::MalformedBlobLiteral(span)
+        | E::MalformedHexInteger(span)
+        | E::ParseUnexpectedToken {
+            parsed_offset: span,
+            ..
+        } => Some(span.offset()),
+        E::ParseUnexpectedEOF => Some(sql.len()),
+        _ => None,
+    };
+    ParseError::at(e.to_string(), offset.map(ErrorLocation::Offset))
+}
+
 fn turso_reprint(sql: &str) -> Option<String> {
     std::panic::catch_unwind(|| {
         let mut parser = turso_parser::parser::Parser::new(sql.as_bytes());
@@ -228,6 +302,50 @@ fn turso_reprint(sql: &str) -> Option<String> {
     .unwrap_or(None)
 }
 
+/// A tree-sitter parser loaded with the tree-sitter-sql grammar. The grammar
+/// crate is built against the same runtime, so loading it cannot fail.
+pub(crate) fn tree_sitter_parser() -> tree_sitter::Parser {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_sequel::LANGUAGE.into())
+        .expect("grammar and runtime ABI versions match");
+    parser
+}
+
+/// tree-sitter never fails a parse: it recovers by wrapping what it could not
+/// place in `ERROR` nodes and inserting zero-width `MISSING` ones. A statement
+/// counts as accepted when its tree has neither, and the error is located at
+/// the first one in source order.
+fn tree_sitter_raw(sql: &str) -> Result<(), ParseError> {
+    let tree = tree_sitter_parser()
+        .parse(sql, None)
+        .ok_or_else(|| ParseError::new("parse cancelled"))?;
+    tree_sitter_error(tree.root_node()).map_or(Ok(()), Err)
+}
+
+/// The first `ERROR` or `MISSING` node under `node`, as a located rejection.
+/// Descends iteratively, since `has_error` marks every ancestor of one.
+fn tree_sitter_error(mut node: tree_sitter::Node<'_>) -> Option<ParseError> {
+    loop {
+        if node.is_error() || node.is_missing() {
+            let message = if node.is_missing() {
+                format!("missing {}", node.kind())
+            } else {
+                "syntax error".to_string()
+            };
+            return Some(ParseError::at(
+                message,
+                Some(ErrorLocation::Offset(node.start_byte())),
+            ));
+        }
+        let mut cursor = node.walk();
+        let next = node
+            .children(&mut cursor)
+            .find(tree_sitter::Node::has_error);
+        node = next?;
+    }
+}
+
 fn sqlglot_reprint(sql: &str, d: Dialect) -> Option<String> {
     std::panic::catch_unwind(|| {
         let stmts = sqlglot_rust::parser::parse_statements(sql, sqlglot_dialect(d)).ok()?;
@@ -260,6 +378,81 @@ fn sqlparser_reprint(sql: &str, d: Dialect) -> Option<String> {
     )
 }
 
+/// sqlparser-rs driven one statement at a time, for the statement positions
+/// its `parse_sql` does not report. A statement that fails to parse, or runs
+/// on past its terminator, loses the whole script unless `recover`, in which
+/// case the loop carries on past it (see [`sqlparser_skip`]). A tokenizer
+/// error always loses the whole script.
+fn sqlparser_statements(sql: &str, d: Dialect, recover: bool) -> Vec<Range<usize>> {
+    let dialect = sqlparser_dialect(d);
+    let Ok(mut parser) = SqlparserParser::new(&*dialect).try_with_sql(sql) else {
+        return Vec::new();
+    };
+    let offset = |t: &sqlparser::tokenizer::TokenWithSpan| {
+        if t.token == SqlToken::EOF {
+            return sql.len();
+        }
+        let (line, column) = (t.span.start.line, t.span.start.column);
+        usize::try_from(line)
+            .ok()
+            .zip(usize::try_from(column).ok())
+            .and_then(|(line, column)| ErrorLocation::LineCol { line, column }.offset(sql))
+            .unwrap_or(sql.len())
+    };
+    let mut out = Vec::new();
+    loop {
+        while parser.consume_token(&SqlToken::SemiColon) {}
+        let first = parser.peek_token();
+        if first.token == SqlToken::EOF {
+            break out;
+        }
+        let start = parser.index();
+        let parsed = parser.parse_statement().is_ok();
+        let next = parser.peek_token();
+        if parsed && matches!(next.token, SqlToken::SemiColon | SqlToken::EOF) {
+            out.push(offset(&first)..offset(&next));
+            continue;
+        }
+        if !recover {
+            break Vec::new();
+        }
+        sqlparser_skip(&mut parser, start);
+    }
+}
+
+/// Move `parser` past a statement that started at token `start` and failed.
+///
+/// If the parser had already consumed a `;` before failing, the next statement
+/// starts after the last one it consumed: skipping on to the next `;` would
+/// swallow that statement too. Otherwise everything up to the next `;` goes.
+fn sqlparser_skip(parser: &mut SqlparserParser<'_>, start: usize) {
+    let seek = |parser: &mut SqlparserParser<'_>, to: usize| {
+        while parser.index() > to {
+            parser.prev_token();
+        }
+        while parser.index() < to {
+            parser.next_token_no_skip();
+        }
+    };
+    let failed_at = parser.index();
+    seek(parser, start);
+    let mut resume = None;
+    while parser.index() < failed_at {
+        if parser.next_token().token == SqlToken::SemiColon {
+            resume = Some(parser.index());
+        }
+    }
+    match resume {
+        Some(at) => seek(parser, at),
+        None => {
+            while !matches!(
+                parser.next_token().token,
+                SqlToken::SemiColon | SqlToken::EOF
+            ) {}
+        }
+    }
+}
+
 fn polyglot_reprint(sql: &str, d: Dialect) -> Option<String> {
     std::panic::catch_unwind(|| {
         let exprs = polyglot_parse(sql, polyglot_dialect(d)).ok()?;
@@ -316,6 +509,7 @@ pub enum BenchParser {
     Sqlglot,
     Sqlite3,
     Turso,
+    TreeSitter,
 }
 
 impl BenchParser {
@@ -333,6 +527,7 @@ impl BenchParser {
             Self::Sqlglot,
             Self::Sqlite3,
             Self::Turso,
+            Self::TreeSitter,
         ]
     }
 
@@ -350,6 +545,7 @@ impl BenchParser {
             Self::Sqlglot => "sqlglot-rust",
             Self::Sqlite3 => "sqlite3-parser",
             Self::Turso => "turso_parser",
+            Self::TreeSitter => "tree-sitter-sql",
         }
     }
 
@@ -368,6 +564,7 @@ impl BenchParser {
             Self::Sqlglot => "0.10.1",
             Self::Sqlite3 => "0.16.0",
             Self::Turso => "0.6.1",
+            Self::TreeSitter => "0.3.11",
         }
     }
 
@@ -395,7 +592,8 @@ impl BenchParser {
         match self.parse_outcome(sql, dialect) {
             ParseOutcome::Unsupported => None,
             ParseOutcome::Accepted => Some(Ok(())),
-            ParseOutcome::Rejected(e) | ParseOutcome::Panicked(e) => Some(Err(e)),
+            ParseOutcome::Rejected(e) => Some(Err(e.message)),
+            ParseOutcome::Panicked(e) => Some(Err(e)),
         }
     }
 
@@ -411,15 +609,17 @@ impl BenchParser {
             Self::Sqlparser => catch_outcome(|| {
                 SqlparserParser::parse_sql(&*sqlparser_dialect(dialect), sql)
                     .map(|_| ())
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| ParseError::new(e.to_string()))
+            }),
+            Self::PgQuery if dialect == Dialect::Postgresql => catch_outcome(|| {
+                pg_query::parse(sql)
+                    .map(|_| ())
+                    .map_err(|e| pg_error(sql, e.to_string()))
             }),
-            Self::PgQuery if dialect == Dialect::Postgresql => {
-                catch_outcome(|| pg_query::parse(sql).map(|_| ()).map_err(|e| e.to_string()))
-            }
             Self::PgQuerySummary if dialect == Dialect::Postgresql => catch_outcome(|| {
                 pg_query::summary(sql, -1)
                     .map(|_| ())
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| pg_error(sql, e.to_string()))
             }),
             Self::Qusql => qusql_dialect(dialect).map_or(ParseOutcome::Unsupported, |d| {
                 catch_outcome(|| qusql_raw(sql, d))
@@ -427,7 +627,7 @@ impl BenchParser {
             Self::Polyglot => catch_outcome(|| {
                 polyglot_parse(sql, polyglot_dialect(dialect))
                     .map(|_| ())
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| ParseError::new(e.to_string()))
             }),
             Self::Databend => databend_dialect_of(dialect).map_or(ParseOutcome::Unsupported, |d| {
                 catch_outcome(|| databend_raw(sql, d))
@@ -435,15 +635,17 @@ impl BenchParser {
             Self::Orql if dialect == Dialect::Oracle => catch_outcome(|| {
                 orql_parser::parse(sql)
                     .map(|_| ())
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| ParseError::new(e.to_string()))
             }),
             Self::Sqlglot => catch_outcome(|| {
                 sqlglot_rust::parser::parse_statements(sql, sqlglot_dialect(dialect))
                     .map(|_| ())
-                    .map_err(|e| e.to_string())
+                    .map_err(|e| ParseError::new(e.to_string()))
             }),
             Self::Sqlite3 if dialect == Dialect::Sqlite => catch_outcome(|| sqlite3_raw(sql)),
             Self::Turso if dialect == Dialect::Sqlite => catch_outcome(|| turso_raw(sql)),
+            // One dialect-agnostic grammar, graded on every corpus dialect.
+            Self::TreeSitter => catch_outcome(|| tree_sitter_raw(sql)),
             // Single-dialect parsers asked about a dialect they do not model.
             Self::PgQuery | Self::PgQuerySummary | Self::Orql | Self::Sqlite3 | Self::Turso => {
                 ParseOutcome::Unsupported
@@ -505,6 +707,9 @@ impl BenchParser {
                     }
                 }
             }
+            Self::TreeSitter => tree_sitter_parser()
+                .parse(sql, None)
+                .is_some_and(|t| !t.root_node().has_error()),
         }
     }
 
@@ -583,6 +788,19 @@ impl BenchParser {
                     }
                 }
             }),
+            // Error-recovering, like qusql: a full count only when error-free.
+            Self::TreeSitter => Some(tree_sitter_parser().parse(sql, None).map_or(0, |t| {
+                let root = t.root_node();
+                if root.has_error() {
+                    return 0;
+                }
+                let mut cursor = root.walk();
+                let n = root
+                    .named_children(&mut cursor)
+                    .filter(|c| c.kind() == "statement")
+                    .count();
+                n
+            })),
         }
     }
 
@@ -704,6 +922,19 @@ impl BenchParser {
                 drop(parser);
                 Some(r)
             }
+            // The tree-sitter runtime allocates in C, but `membench` points its
+            // allocator at the Rust one, so the parse is counted like any other.
+            Self::TreeSitter => {
+                let before = mem::live();
+                mem::reset_peak();
+                let mut parser = tree_sitter_parser();
+                let tree = parser.parse(sql, None);
+                black_box(&tree);
+                let r = snap(before);
+                drop(tree);
+                drop(parser);
+                Some(r)
+            }
         }
     }
 
@@ -772,6 +1003,294 @@ impl BenchParser {
                 .is_some_and(|second| first == second),
         )
     }
+
+    /// The parser's tree for `sql` lowered into the shared [`norm::NormTree`],
+    /// for the AST-equivalence axis. `None` if the parser does not model
+    /// `dialect`, rejects `sql`, or exposes no tree (the `pg_query` summary,
+    /// orql, whose only dialect has no reference tree to compare with, and
+    /// tree-sitter-sql, whose untyped concrete syntax tree has no `Debug` form).
+    #[must_use]
+    pub fn normalized(self, sql: &str, dialect: Dialect) -> Option<norm::NormTree> {
+        std::panic::catch_unwind(|| self.debug_tree(sql, dialect))
+            .ok()
+            .flatten()
+            .map(|t| norm::lower(&t))
+    }
+
+    /// Byte spans of the identifiers and literals in the parser's tree, for the
+    /// span-fidelity check. `None` if the parser attaches no spans to its AST,
+    /// does not model `dialect`, or rejects `sql`.
+    #[must_use]
+    pub fn spans(self, sql: &str, dialect: Dialect) -> Option<Vec<spans::TokenSpan>> {
+        if !matches!(self, Self::Sqlparser | Self::Qusql | Self::Databend) {
+            return None;
+        }
+        std::panic::catch_unwind(|| self.debug_tree(sql, dialect))
+            .ok()
+            .flatten()
+            .map(|t| spans::from_debug(&t, sql))
+    }
+
+    /// Run only the parser's lexer over `sql` and return the token count, for
+    /// the lex-time axis. `None` if the parser has no standalone lexer or does
+    /// not model `dialect`. Like [`Self::parse_once`] there is no panic
+    /// protection. A lex error ends the count early for the streaming lexers
+    /// (lemon-rs, turso) and makes it 0 for sqlparser-rs and databend, which
+    /// return all the tokens or only the error. Counts are each lexer's own
+    /// (sqlparser-rs's whitespace and comment tokens are dropped to match the
+    /// others, databend's closing end-of-input token is kept), so compare them
+    /// within a parser, not across parsers.
+    #[must_use]
+    pub fn tokenize(self, sql: &str, dialect: Dialect) -> Option<usize> {
+        match self {
+            Self::Sqlparser => Some(
+                SqlTokenizer::new(&*sqlparser_dialect(dialect), sql)
+                    .tokenize()
+                    .map_or(0, |t| {
+                        t.iter()
+                            .filter(|t| !matches!(t, SqlToken::Whitespace(_)))
+                            .count()
+                    }),
+            ),
+            Self::Databend => {
+                databend_dialect_of(dialect).map(|_| databend_tokenize(sql).map_or(0, |t| t.len()))
+            }
+            Self::Sqlite3 => (dialect == Dialect::Sqlite).then(|| {
+                let mut scanner = Scanner::new(sqlite3_parser::lexer::sql::Tokenizer::new());
+                let mut n = 0;
+                loop {
+                    match scanner.scan(sql.as_bytes()) {
+                        Ok((_, Some(_), _)) => n += 1,
+                        Ok((_, None, _)) | Err(_) => break n,
+                    }
+                }
+            }),
+            Self::Turso => (dialect == Dialect::Sqlite).then(|| {
+                turso_parser::lexer::Lexer::new(sql.as_bytes())
+                    .map_while(Result::ok)
+                    .count()
+            }),
+            _ => None,
+        }
+    }
+
+    /// Byte ranges of the statements the parser finds in the multi-statement
+    /// script `sql`, for the splitter axis. `None` if the parser does not
+    /// model `dialect` or does not say where its statements are: turso streams
+    /// commands without an offset, and the rest only count them. sqlparser-rs
+    /// is driven statement by statement, and lemon-rs's statements end where
+    /// its scanner stands after each command. A script that fails to parse
+    /// yields no statements. Only the boundaries between the
+    /// ranges are scored, so whether a range includes its terminator or the
+    /// whitespace around it does not matter.
+    #[must_use]
+    pub fn split(self, sql: &str, dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        match self {
+            Self::Sqlparser => Some(sqlparser_statements(sql, dialect, false)),
+            Self::PgQuery => (dialect == Dialect::Postgresql).then(|| {
+                pg_query::parse(sql).map_or_else(
+                    |_| Vec::new(),
+                    |r| {
+                        r.protobuf
+                            .stmts
+                            .iter()
+                            .map(|s| {
+                                let start = usize::try_from(s.stmt_location).unwrap_or(0);
+                                // A zero length runs to the end of the script.
+                                let end = match usize::try_from(s.stmt_len) {
+                                    Ok(0) | Err(_) => sql.len(),
+                                    Ok(len) => start + len,
+                                };
+                                start..end
+                            })
+                            .collect()
+                    },
+                )
+            }),
+            Self::Qusql => qusql_dialect(dialect).map(|d| {
+                let opts = ParseOptions::new()
+                    .dialect(d)
+                    .arguments(qusql_parse::SQLArguments::Dollar);
+                let mut issues = Issues::new(sql);
+                let stmts = parse_statements(sql, &mut issues, &opts);
+                if issues.get().iter().any(|i| i.level == Level::Error) {
+                    Vec::new()
+                } else {
+                    stmts.iter().map(Spanned::span).collect()
+                }
+            }),
+            Self::Sqlite3 => (dialect == Dialect::Sqlite).then(|| {
+                let mut parser = sqlite3_parser::lexer::sql::Parser::new(sql.as_bytes());
+                let mut ranges = Vec::new();
+                let mut start = 0;
+                loop {
+                    match parser.next() {
+                        Ok(Some(_)) => {
+                            let end = parser.offset();
+                            ranges.push(start..end);
+                            start = end;
+                        }
+                        Ok(None) => break ranges,
+                        Err(_) => break Vec::new(),
+                    }
+                }
+            }),
+            Self::TreeSitter => Some(tree_sitter_parser().parse(sql, None).map_or_else(
+                Vec::new,
+                |t| {
+                    let root = t.root_node();
+                    if root.has_error() {
+                        return Vec::new();
+                    }
+                    let mut cursor = root.walk();
+                    let ranges = root
+                        .named_children(&mut cursor)
+                        .filter(|c| c.kind() == "statement")
+                        .map(|c| c.byte_range())
+                        .collect();
+                    ranges
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// Byte ranges of the statements the parser still returns from a script
+    /// with an error in it, for the recovery axis. `None` if the parser does
+    /// not model `dialect` or has no way to keep going past an error (the
+    /// rest fail the whole script, or stop at the first bad statement).
+    /// sqlparser-rs is driven statement by statement with a skip to the next
+    /// `;` (see [`sqlparser_statements`]). qusql-parse and tree-sitter-sql
+    /// recover on their own, and a tree-sitter statement holding an error
+    /// node is not counted.
+    #[must_use]
+    pub fn recover(self, sql: &str, dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        match self {
+            Self::Sqlparser => Some(sqlparser_statements(sql, dialect, true)),
+            Self::Qusql => qusql_dialect(dialect).map(|d| {
+                let opts = ParseOptions::new()
+                    .dialect(d)
+                    .arguments(qusql_parse::SQLArguments::Dollar);
+                let mut issues = Issues::new(sql);
+                let stmts = parse_statements(sql, &mut issues, &opts);
+                stmts.iter().map(Spanned::span).collect()
+            }),
+            Self::TreeSitter => Some(tree_sitter_parser().parse(sql, None).map_or_else(
+                Vec::new,
+                |t| {
+                    let root = t.root_node();
+                    let mut cursor = root.walk();
+                    let ranges = root
+                        .named_children(&mut cursor)
+                        .filter(|c| c.kind() == "statement" && !c.has_error())
+                        .map(|c| c.byte_range())
+                        .collect();
+                    ranges
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// Whether this parser can reparse an edited statement against its old
+    /// tree (tree-sitter-sql) rather than from scratch.
+    #[must_use]
+    pub const fn incremental(self) -> bool {
+        matches!(self, Self::TreeSitter)
+    }
+
+    /// `sql` after `edit`, prepared for timed reparses on the edit-reparse
+    /// axis: incremental where [`Self::incremental`], otherwise a cold
+    /// [`Self::parse_once`] of the edited text.
+    ///
+    /// `None` if the parser does not model `dialect` or does not accept the
+    /// edited text. That check is panic-guarded like [`Self::accepts`], so the
+    /// unguarded timed reparses only ever see text the parser is known to take.
+    #[must_use]
+    pub fn edit_reparse(
+        self,
+        sql: &str,
+        edit: &edit::Edit,
+        dialect: Dialect,
+    ) -> Option<edit::EditReparse> {
+        let edited = edit.apply(sql);
+        if self.accepts(&edited, dialect) != Some(true) {
+            return None;
+        }
+        if self.incremental() {
+            return incremental::Reparse::new(sql, edit).map(edit::EditReparse::Incremental);
+        }
+        Some(edit::EditReparse::Cold {
+            parser: self,
+            edited,
+            dialect,
+        })
+    }
+
+    /// The parser's `Debug` rendering of its parse result, the input to
+    /// [`norm::lower`] and [`spans::from_debug`].
+    fn debug_tree(self, sql: &str, dialect: Dialect) -> Option<String> {
+        match self {
+            Self::Sqlparser => SqlparserParser::parse_sql(&*sqlparser_dialect(dialect), sql)
+                .ok()
+                .map(|v| format!("{v:?}")),
+            Self::PgQuery => (dialect == Dialect::Postgresql)
+                .then(|| {
+                    pg_query::parse(sql)
+                        .ok()
+                        .map(|r| format!("{:?}", r.protobuf))
+                })
+                .flatten(),
+            Self::Qusql => qusql_dialect(dialect).and_then(|d| {
+                let opts = ParseOptions::new()
+                    .dialect(d)
+                    .arguments(qusql_parse::SQLArguments::Dollar);
+                let mut issues = Issues::new(sql);
+                let stmts = parse_statements(sql, &mut issues, &opts);
+                (!issues.get().iter().any(|i| i.level == Level::Error))
+                    .then(|| format!("{stmts:?}"))
+            }),
+            Self::Polyglot => polyglot_parse(sql, polyglot_dialect(dialect))
+                .ok()
+                .map(|v| format!("{v:?}")),
+            Self::Databend => databend_dialect_of(dialect).and_then(|d| {
+                let tokens = databend_tokenize(sql).ok()?;
+                databend_parse(&tokens, d)
+                    .ok()
+                    .map(|(s, _)| format!("{s:?}"))
+            }),
+            Self::Sqlglot => sqlglot_rust::parser::parse_statements(sql, sqlglot_dialect(dialect))
+                .ok()
+                .map(|v| format!("{v:?}")),
+            Self::Sqlite3 => (dialect == Dialect::Sqlite)
+                .then(|| {
+                    let mut parser = sqlite3_parser::lexer::sql::Parser::new(sql.as_bytes());
+                    let mut out = Vec::new();
+                    loop {
+                        match parser.next() {
+                            Ok(Some(cmd)) => out.push(cmd),
+                            Ok(None) => break Some(format!("{out:?}")),
+                            Err(_) => break None,
+                        }
+                    }
+                })
+                .flatten(),
+            Self::Turso => (dialect == Dialect::Sqlite)
+                .then(|| {
+                    let mut parser = turso_parser::parser::Parser::new(sql.as_bytes());
+                    let mut out = Vec::new();
+                    loop {
+                        match parser.next_cmd() {
+                            Ok(Some(cmd)) => out.push(cmd),
+                            Ok(None) => break Some(format!("{out:?}")),
+                            Err(_) => break None,
+                        }
+                    }
+                })
+                .flatten(),
+            Self::PgQuerySummary | Self::Orql | Self::TreeSitter => None,
+        }
+    }
 }
 
 /// Identity of one benchmarked parser build: which library and which version.
@@ -797,7 +1316,8 @@ pub struct ParserId {
 /// Implementors provide the required methods. `accepts`, `measure_mem_batch`,
 /// and `roundtrips` have default implementations built on them
 /// (mirroring [`BenchParser`]'s inherent methods), so a historical version only
-/// needs the core parse hooks.
+/// needs the core parse hooks. Optional capabilities (`normalized`, `spans`,
+/// `tokenize`, `split`, `recover`) default to absent.
 pub trait Parser: Sync {
     /// Library and version identity.
     fn id(&self) -> ParserId;
@@ -833,10 +1353,43 @@ pub trait Parser: Sync {
         match self.try_parse(sql, dialect) {
             None => ParseOutcome::Unsupported,
             Some(Ok(())) => ParseOutcome::Accepted,
-            Some(Err(e)) => ParseOutcome::Rejected(e),
+            Some(Err(e)) => ParseOutcome::Rejected(ParseError::new(e)),
         }
     }
 
+    /// The parser's tree lowered for the AST-equivalence axis. See
+    /// [`BenchParser::normalized`]. The default exposes no tree, so historical
+    /// versions sit out the axis.
+    fn normalized(&self, _sql: &str, _dialect: Dialect) -> Option<norm::NormTree> {
+        None
+    }
+
+    /// Identifier and literal spans for the span-fidelity check. See
+    /// [`BenchParser::spans`]. The default reports none, so parsers without
+    /// AST spans sit out the check.
+    fn spans(&self, _sql: &str, _dialect: Dialect) -> Option<Vec<spans::TokenSpan>> {
+        None
+    }
+
+    /// Token count from the parser's standalone lexer, for the lex-time axis.
+    /// See [`BenchParser::tokenize`]. The default has no lexer, so historical
+    /// versions sit out the axis.
+    fn tokenize(&self, _sql: &str, _dialect: Dialect) -> Option<usize> {
+        None
+    }
+
+    /// Statement ranges in a multi-statement script, for the splitter axis.
+    /// See [`BenchParser::split`]. The default reports none.
+    fn split(&self, _sql: &str, _dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        None
+    }
+
+    /// Statement ranges salvaged from a script with an error, for the
+    /// recovery axis. See [`BenchParser::recover`]. The default reports none.
+    fn recover(&self, _sql: &str, _dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        None
+    }
+
     /// Whole-script `(peak, retained)`, gated on a batch entry point.
     fn measure_mem_batch(&self, sql: &str, dialect: Dialect) -> Option<(usize, usize)> {
         if self.can_batch() {
@@ -910,30 +1463,61 @@ impl Parser for BenchParser {
     fn roundtrips(&self, sql: &str, dialect: Dialect) -> Option<bool> {
         (*self).roundtrips(sql, dialect)
     }
+    fn normalized(&self, sql: &str, dialect: Dialect) -> Option<norm::NormTree> {
+        (*self).normalized(sql, dialect)
+    }
+    fn spans(&self, sql: &str, dialect: Dialect) -> Option<Vec<spans::TokenSpan>> {
+        (*self).spans(sql, dialect)
+    }
+    fn tokenize(&self, sql: &str, dialect: Dialect) -> Option<usize> {
+        (*self).tokenize(sql, dialect)
+    }
+    fn split(&self, sql: &str, dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        (*self).split(sql, dialect)
+    }
+    fn recover(&self, sql: &str, dialect: Dialect) -> Option<Vec<Range<usize>>> {
+        (*self).recover(sql, dialect)
+    }
 }
 
 pub mod batch;
 pub mod bench_dist;
+pub mod common;
+pub mod constructs;
 pub mod contentious;
 pub mod datasets;
+pub mod diagnostics;
+pub mod edit;
 pub mod export;
+pub mod fuzz;
+pub mod gaps;
+pub mod incremental;
+pub mod ingest;
 pub mod mem;
+pub mod norm;
 pub mod oracle_cache;
+pub mod placeholder;
+pub mod recovery;
+pub mod reduce;
 pub mod report;
+pub mod spans;
+pub mod split;
 pub mod stats;
+pub mod stmt_kind;
+pub mod subprocess;
 
 #[cfg(test)]
 mod tests {
-    use super::ParseOutcome;
-    use super::{catch_outcome, has_reference, reference_accepts, BenchParser};
+    use super::{catch_outcome, pg_error, ErrorLocation, ParseError, ParseOutcome};
+    use super::{has_reference, reference_accepts, BenchParser};
     use crate::datasets::Dialect;
 
     #[test]
     fn catch_outcome_classifies_accept_reject_and_panic() {
         assert_eq!(catch_outcome(|| Ok(())), ParseOutcome::Accepted);
         assert_eq!(
-            catch_outcome(|| Err("bad".to_string())),
-            ParseOutcome::Rejected("bad".to_string())
+            catch_outcome(|| Err(ParseError::new("bad"))),
+            ParseOutcome::Rejected(ParseError::new("bad"))
         );
         // A panic in the parse closure becomes Panicked, not an abort.
         match catch_outcome(|| panic!("boom")) {
@@ -942,6 +1526,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pg_query_rejections_carry_the_error_offset() {
+        let sql = "SELECT a, FROM t";
+        let ParseOutcome::Rejected(e) =
+            BenchParser::PgQuery.parse_outcome(sql, Dialect::Postgresql)
+        else {
+            panic!("trailing comma should be rejected");
+        };
+        assert_eq!(e.offset(sql), Some(10));
+        assert_eq!(
+            pg_error("SELECT", "syntax error at end of input".to_string()).location,
+            Some(ErrorLocation::Offset(6))
+        );
+    }
+
+    #[test]
+    fn tree_sitter_rejects_error_and_missing_nodes_at_their_offset() {
+        let p = BenchParser::TreeSitter;
+        assert_eq!(
+            p.parse_outcome("SELECT a FROM t", Dialect::Postgresql),
+            ParseOutcome::Accepted
+        );
+        // An unparsable token becomes an ERROR node, an unclosed paren a
+        // zero-width MISSING one. Both reject, located within the statement.
+        for sql in ["SELECT a FROM t WHERE )", "SELECT (1"] {
+            let ParseOutcome::Rejected(e) = p.parse_outcome(sql, Dialect::Postgresql) else {
+                panic!("{sql} should be rejected");
+            };
+            assert!(e.offset(sql).is_some(), "{sql}: {}", e.message);
+        }
+    }
+
     #[test]
     fn parse_outcome_agrees_with_try_parse() {
         let p = BenchParser::Sqlparser;
@@ -1003,12 +1619,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tokenize_counts_tokens_and_skips_parsers_without_a_lexer() {
+        let sql = "SELECT a, -- note\n b FROM t";
+        // SELECT a , b FROM t: whitespace and the comment are not tokens.
+        assert_eq!(
+            BenchParser::Sqlparser.tokenize(sql, Dialect::Postgresql),
+            Some(6)
+        );
+        assert_eq!(
+            BenchParser::PgQuery.tokenize(sql, Dialect::Postgresql),
+            None
+        );
+        assert_eq!(BenchParser::Sqlite3.tokenize(sql, Dialect::Mysql), None);
+    }
+
+    #[test]
+    fn sqlparser_recovers_the_statements_around_a_bad_one() {
+        let sql = "SELECT 1\n;\nSELEC oops\n;\nSELECT 2";
+        let ranges = BenchParser::Sqlparser
+            .recover(sql, Dialect::Postgresql)
+            .expect("sqlparser-rs recovers");
+        let stmts: Vec<&str> = ranges.iter().map(|r| sql[r.clone()].trim()).collect();
+        assert_eq!(stmts, ["SELECT 1", "SELECT 2"]);
+        assert_eq!(BenchParser::PgQuery.recover(sql, Dialect::Postgresql), None);
+        // The bad statement fails on its own `;`, where a table name should be,
+        // so the statement after it is resumed rather than skipped.
+        let sql = "SELECT 1\n;\nSELECT 1 FROM\n;\nSELECT 2\n;\nSELECT 3";
+        let ranges = BenchParser::Sqlparser
+            .recover(sql, Dialect::Postgresql)
+            .expect("sqlparser-rs recovers");
+        let stmts: Vec<&str> = ranges.iter().map(|r| sql[r.clone()].trim()).collect();
+        assert_eq!(stmts, ["SELECT 1", "SELECT 2", "SELECT 3"]);
+    }
+
+    #[test]
+    fn sqlparser_splits_only_a_clean_script() {
+        let sql = "SELECT 1\n;\nSELECT 'a;b'\n;\nSELECT 2";
+        let ranges = BenchParser::Sqlparser
+            .split(sql, Dialect::Postgresql)
+            .expect("sqlparser-rs reports statement positions");
+        let stmts: Vec<&str> = ranges.iter().map(|r| sql[r.clone()].trim()).collect();
+        assert_eq!(stmts, ["SELECT 1", "SELECT 'a;b'", "SELECT 2"]);
+        let bad = "SELECT 1\n;\nSELEC oops\n;\nSELECT 2";
+        assert_eq!(
+            BenchParser::Sqlparser.split(bad, Dialect::Postgresql),
+            Some(Vec::new())
+        );
+    }
+
     #[test]
     fn multi_dialect_parsers_support_everything() {
         for p in [
             BenchParser::Sqlparser,
             BenchParser::Polyglot,
             BenchParser::Sqlglot,
+            BenchParser::TreeSitter,
         ] {
             assert_eq!(
                 supported(p).len(),
@@ -1099,13 +1765,11 @@ mod tests {
 
     #[test]
     fn reference_excludes_dialects_without_a_real_engine() {
-        // Cloud, heavy-JVM, and Oracle dialects never get a real-engine cache, so
-        // they are never reference-graded and the reference verdict is None,
-        // independent of which label caches happen to be present.
+        // Cloud, heavy-JVM, and Oracle dialects have no engine the `oracle` crate
+        // runs, so they never get a label cache and the reference verdict is
+        // None. Redshift (libpg_query) and Trino (Docker) do get one.
         for d in [
             Dialect::Bigquery,
-            Dialect::Redshift,
-            Dialect::Trino,
             Dialect::Hive,
             Dialect::SparkSql,
             Dialect::Oracle,
@@ -1210,4 +1874,18 @@ mod tests {
             None
         );
     }
+    #[test]
+    fn lemon_rs_splits_trigger_scripts_between_commands() {
+        let script = "CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b; END;\nSELECT 1;";
+        let ranges = BenchParser::Sqlite3.split(script, Dialect::Sqlite).unwrap();
+        let texts: Vec<&str> = ranges.iter().map(|r| script[r.clone()].trim()).collect();
+        assert_eq!(
+            texts,
+            [
+                "CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b; END;",
+                "SELECT 1;"
+            ]
+        );
+        assert_eq!(BenchParser::Turso.split(script, Dialect::Sqlite), None);
+    }
 }
@@ -0,0 +1,1096 @@
+//! Normalized parse trees for the AST-equivalence axis.
+//!
+//! Round-trip stability only shows that a parser prints what it parsed. It says
+//! nothing about whether it parsed the statement *correctly*: a parser that
+//! reads `a - b * c` as `(a - b) * c` can still print and re-parse it forever.
+//! This module lowers each parser's tree into a shared [`NormTree`] (statement
+//! kind, table references, projected expressions, join structure, and
+//! predicates) so it can be compared with the reference parser's tree: `pg_query`
+//! on `PostgreSQL` and lemon-rs (`sqlite3-parser`) on `SQLite`.
+//!
+//! The lowering reads the parser's `Debug` rendering rather than its typed AST.
+//! Every benchmarked library derives `Debug`, so one lowering serves all of them
+//! and survives AST churn across releases. It maps the node names the libraries
+//! share (`BinaryOp`/`Binary`/`AExpr`, `Identifier`/`Id`/`ColumnRef`, ...) onto
+//! a small vocabulary. Anything outside that vocabulary lowers to
+//! [`NormExpr::Other`], which matches any subtree, so a construct the lowering
+//! does not understand never counts against a parser. Only what both sides
+//! lower is compared, and a tree with nothing lowered ([`NormTree::is_opaque`])
+//! is not compared at all, so it cannot count for a parser either.
+
+use crate::datasets::Dialect;
+use crate::BenchParser;
+use std::collections::VecDeque;
+
+/// A statement reduced to the parts the equivalence check compares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NormTree {
+    /// Canonical statement kind (`select`, `insert`, `create_table`, ...), or
+    /// `other` for statements outside the vocabulary.
+    pub kind: &'static str,
+    /// Referenced relations (unqualified, lowercased, sorted). Queries and DML
+    /// only: DDL object names are modelled too differently to compare.
+    pub tables: Vec<String>,
+    /// The outermost select list (queries only).
+    pub projections: Vec<NormExpr>,
+    /// Join kinds (`inner`, `left`, `right`, `full`), sorted.
+    pub joins: Vec<&'static str>,
+    /// `WHERE` and `HAVING` predicates, outermost first.
+    pub predicates: Vec<NormExpr>,
+}
+
+/// A normalized expression. Operator precedence is explicit in the nesting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NormExpr {
+    /// A column reference, dotted path lowercased (`t.a`, `*`, `t.*`).
+    Column(String),
+    /// Any literal. Values are not compared, only where a literal sits.
+    Literal,
+    /// An operator applied to its operands (canonical spelling, `and`/`or`
+    /// chains flattened).
+    Op(String, Vec<Self>),
+    /// A function call by lowercased name.
+    Call(String, Vec<Self>),
+    /// A construct outside the vocabulary. Matches anything.
+    Other,
+}
+
+impl NormExpr {
+    /// Structural agreement, treating [`Self::Other`] on either side as a match.
+    #[must_use]
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Other, _) | (_, Self::Other) | (Self::Literal, Self::Literal) => true,
+            (Self::Column(a), Self::Column(b)) => a == b,
+            (Self::Op(a, xs), Self::Op(b, ys)) | (Self::Call(a, xs), Self::Call(b, ys)) => {
+                a == b && all_match(xs, ys)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn all_match(xs: &[NormExpr], ys: &[NormExpr]) -> bool {
+    xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| x.matches(y))
+}
+
+impl NormTree {
+    /// Whether the lowering understood nothing of the statement: its kind is
+    /// outside the vocabulary, or every projection and predicate lowered to
+    /// [`NormExpr::Other`]. Such a tree agrees with almost anything, so the
+    /// agreement rate leaves it out.
+    #[must_use]
+    pub fn is_opaque(&self) -> bool {
+        let mut exprs = self.projections.iter().chain(&self.predicates).peekable();
+        self.kind == "other" || (exprs.peek().is_some() && exprs.all(|e| *e == NormExpr::Other))
+    }
+
+    /// Whether two lowered trees agree: same kind, tables, and joins, and
+    /// matching projections and predicates (see [`NormExpr::matches`]).
+    #[must_use]
+    pub fn agrees(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.tables == other.tables
+            && self.joins == other.joins
+            && all_match(&self.projections, &other.projections)
+            && all_match(&self.predicates, &other.predicates)
+    }
+}
+
+/// The reference parser whose tree `dialect` is compared against, if any.
+#[must_use]
+pub const fn reference_parser(dialect: Dialect) -> Option<BenchParser> {
+    match dialect {
+        Dialect::Postgresql => Some(BenchParser::PgQuery),
+        Dialect::Sqlite => Some(BenchParser::Sqlite3),
+        _ => None,
+    }
+}
+
+/// The reference parser's normalized tree for `sql`, or `None` if the dialect
+/// has no reference tree or the reference does not parse it.
+#[must_use]
+pub fn reference_tree(sql: &str, dialect: Dialect) -> Option<NormTree> {
+    reference_parser(dialect)?.normalized(sql, dialect)
+}
+
+/// Lower a parser's `Debug` rendering of its parse result into a [`NormTree`].
+#[must_use]
+pub fn lower(debug: &str) -> NormTree {
+    let root = DebugParser::new(debug).value();
+    let kind = kind_of(&root);
+    let dml = matches!(kind, "select" | "insert" | "update" | "delete");
+    let mut tables = Vec::new();
+    let mut joins = Vec::new();
+    walk(&root, &mut |n| {
+        if dml {
+            collect_table(n, &mut tables);
+        }
+        if let Some(j) = join_kind(n) {
+            joins.push(j);
+        }
+    });
+    tables.sort();
+    joins.sort_unstable();
+    let projections = if kind == "select" {
+        first_list(&root, PROJECTION_KEYS)
+            .map_or_else(Vec::new, |items| items.iter().map(lower_expr).collect())
+    } else {
+        Vec::new()
+    };
+    let mut predicates = Vec::new();
+    breadth_first(&root, &mut |n| {
+        if let Dbg::Node { fields, .. } = n {
+            for (k, v) in fields {
+                if k.as_deref().is_some_and(|k| PREDICATE_KEYS.contains(&k)) {
+                    predicates.push(lower_expr(v));
+                }
+            }
+        }
+    });
+    NormTree {
+        kind,
+        tables,
+        projections,
+        joins,
+        predicates,
+    }
+}
+
+// Vocabulary.
+
+/// Statement node names and their canonical kind.
+const KINDS: &[(&str, &str)] = &[
+    ("SelectStmt", "select"),
+    ("Query", "select"),
+    ("Select", "select"),
+    ("InsertStmt", "insert"),
+    ("Insert", "insert"),
+    ("UpdateStmt", "update"),
+    ("Update", "update"),
+    ("DeleteStmt", "delete"),
+    ("Delete", "delete"),
+    ("CreateStmt", "create_table"),
+    ("CreateTable", "create_table"),
+    ("IndexStmt", "create_index"),
+    ("CreateIndex", "create_index"),
+    ("ViewStmt", "create_view"),
+    ("CreateView", "create_view"),
+    ("DropStmt", "drop"),
+    ("Drop", "drop"),
+    ("DropTable", "drop"),
+    ("DropIndex", "drop"),
+    ("DropView", "drop"),
+    ("AlterTableStmt", "alter_table"),
+    ("AlterTable", "alter_table"),
+    ("ExplainStmt", "explain"),
+    ("Explain", "explain"),
+];
+
+/// Fields holding the select list.
+const PROJECTION_KEYS: &[&str] = &[
+    "target_list",
+    "projection",
+    "columns",
+    "select_list",
+    "select_exprs",
+];
+
+/// Fields holding a `WHERE` or `HAVING` predicate.
+const PREDICATE_KEYS: &[&str] = &[
+    "where_clause",
+    "selection",
+    "where_",
+    "having_clause",
+    "having",
+];
+
+/// Statement-level fields naming a DML target table.
+const TARGET_KEYS: &[&str] = &["table", "table_name", "tbl_name"];
+
+/// Transparent wrappers: the expression is in `val`/`expr` or the first
+/// positional child (parentheses, aliases, select-list items, tuple pairs).
+const WRAPPERS: &[&str] = &[
+    "",
+    "Nested",
+    "Parenthesized",
+    "Paren",
+    "UnnamedExpr",
+    "ExprWithAlias",
+    "ResTarget",
+    "AliasedExpr",
+    "SelectExpr",
+    "Expr",
+    "Unnamed",
+];
+
+const COLUMNS: &[&str] = &[
+    "ColumnRef",
+    "Identifier",
+    "CompoundIdentifier",
+    "Id",
+    "Qualified",
+    "DoublyQualified",
+    "Column",
+    "Name",
+];
+
+const LITERALS: &[&str] = &[
+    "Value",
+    "Literal",
+    "AConst",
+    "Number",
+    "Numeric",
+    "Integer",
+    "Float",
+    "String",
+    "StringLiteral",
+    "SingleQuotedString",
+    "Boolean",
+    "Bool",
+    "Null",
+    "Blob",
+];
+
+const CALLS: &[&str] = &["FuncCall", "Function", "FunctionCall", "FunctionCallStar"];
+
+/// Canonical spelling of an operator, whichever library named it.
+fn canon_op(raw: &str) -> String {
+    let key: String = raw
+        .chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect();
+    match key.as_str() {
+        "-" | "minus" | "sub" | "subtract" | "negative" | "neg" => "-",
+        "+" | "plus" | "add" | "positive" => "+",
+        "*" | "multiply" | "mul" | "times" => "*",
+        "/" | "divide" | "div" => "/",
+        "%" | "modulo" | "mod" | "modulus" | "remainder" => "%",
+        "=" | "==" | "eq" | "equals" | "equal" => "=",
+        "<>" | "!=" | "noteq" | "notequals" | "neq" | "notequal" => "<>",
+        "<" | "lt" | "less" | "lessthan" => "<",
+        "<=" | "lteq" | "lte" | "lessequals" | "lessthanorequal" => "<=",
+        ">" | "gt" | "greater" | "greaterthan" => ">",
+        ">=" | "gteq" | "gte" | "greaterequals" | "greaterthanorequal" => ">=",
+        "and" | "andexpr" => "and",
+        "or" | "orexpr" => "or",
+        "not" | "notexpr" => "not",
+        "||" | "stringconcat" | "concat" => "||",
+        _ => return key,
+    }
+    .to_string()
+}
+
+// Lowering.
+
+fn kind_of(root: &Dbg) -> &'static str {
+    let mut kind = None;
+    walk(root, &mut |n| {
+        if kind.is_none() {
+            if let Dbg::Node { name, .. } = n {
+                kind = create_table_as(n)
+                    .or_else(|| KINDS.iter().find(|(k, _)| k == name).map(|(_, v)| *v));
+            }
+        }
+    });
+    kind.unwrap_or("other")
+}
+
+/// `pg_query`'s `CreateTableAsStmt`, which wraps the query the other trees keep
+/// under their `CREATE TABLE` or `CREATE MATERIALIZED VIEW` node. Without this
+/// the walk would reach the inner `SelectStmt` first and call it a `select`.
+fn create_table_as(n: &Dbg) -> Option<&'static str> {
+    if n.name() != Some("CreateTableAsStmt") {
+        return None;
+    }
+    let matview = n
+        .field("objtype")
+        .and_then(Dbg::label)
+        .is_some_and(|t| t == "ObjectMatview");
+    Some(if matview {
+        "create_view"
+    } else {
+        "create_table"
+    })
+}
+
+/// A relation referenced by `n`, if it is a relation node or a DML statement
+/// naming its target table directly.
+fn collect_table(n: &Dbg, out: &mut Vec<String>) {
+    let Dbg::Node { name, fields } = n else {
+        return;
+    };
+    if name == "RangeVar" || name == "Table" {
+        out.extend(relation_name(n));
+        return;
+    }
+    let is_dml = KINDS
+        .iter()
+        .any(|(k, v)| k == name && matches!(*v, "insert" | "update" | "delete"));
+    if !is_dml {
+        return;
+    }
+    for (k, v) in fields {
+        // A target wrapping a relation node is counted when the walk reaches it.
+        if k.as_deref().is_some_and(|k| TARGET_KEYS.contains(&k)) && !has_relation(v) {
+            out.extend(relation_name(v));
+        }
+    }
+}
+
+fn has_relation(n: &Dbg) -> bool {
+    let mut found = false;
+    walk(n, &mut |c| {
+        found |= matches!(c, Dbg::Node { name, .. } if name == "RangeVar" || name == "Table");
+    });
+    found
+}
+
+/// The unqualified, lowercased relation name under `n`.
+fn relation_name(n: &Dbg) -> Option<String> {
+    for key in ["relname", "name", "table"] {
+        if let Some(v) = n.field(key) {
+            return relation_name(v);
+        }
+    }
+    if let Some(first) = n.positional().next() {
+        return relation_name(first);
+    }
+    strings(n).pop().map(|s| s.to_lowercase())
+}
+
+fn join_kind(n: &Dbg) -> Option<&'static str> {
+    let Dbg::Node { name, fields } = n else {
+        return None;
+    };
+    if !matches!(name.as_str(), "JoinExpr" | "Join" | "JoinedSelectTable") {
+        return None;
+    }
+    let mut text = String::new();
+    for (k, v) in fields {
+        if k.as_deref().is_some_and(|k| {
+            matches!(
+                k,
+                "jointype" | "join_operator" | "operator" | "op" | "join_type"
+            )
+        }) {
+            walk(v, &mut |c| match c {
+                Dbg::Node { name, .. } | Dbg::Atom(name) => text.push_str(&name.to_lowercase()),
+                _ => {}
+            });
+        }
+    }
+    // A comma is a second FROM item, not a join, in every other tree.
+    if text.contains("comma") {
+        None
+    } else if text.contains("left") {
+        Some("left")
+    } else if text.contains("right") {
+        Some("right")
+    } else if text.contains("full") {
+        Some("full")
+    } else {
+        // CROSS JOIN is an inner join without a condition in pg_query's tree.
+        Some("inner")
+    }
+}
+
+/// The first non-empty list under one of `keys`, shallowest first, so the
+/// outermost select list wins over one inside a CTE or subquery.
+fn first_list<'a>(root: &'a Dbg, keys: &[&str]) -> Option<&'a [Dbg]> {
+    let mut queue = VecDeque::from([root]);
+    while let Some(n) = queue.pop_front() {
+        match n {
+            Dbg::Node { fields, .. } => {
+                for (k, v) in fields {
+                    if let (Some(k), Dbg::List(items)) = (k.as_deref(), v) {
+                        if keys.contains(&k) && !items.is_empty() {
+                            return Some(items);
+                        }
+                    }
+                }
+                queue.extend(fields.iter().map(|(_, v)| v));
+            }
+            Dbg::List(items) => queue.extend(items),
+            Dbg::Str(_) | Dbg::Atom(_) => {}
+        }
+    }
+    None
+}
+
+fn lower_expr(n: &Dbg) -> NormExpr {
+    match n {
+        Dbg::Str(_) => NormExpr::Literal,
+        Dbg::Atom(a) => lower_atom(a),
+        Dbg::List(items) if items.len() == 1 => lower_expr(&items[0]),
+        Dbg::List(_) => NormExpr::Other,
+        Dbg::Node { name, .. } => lower_node(name, n),
+    }
+}
+
+fn lower_atom(a: &str) -> NormExpr {
+    if matches!(a, "Star" | "Wildcard" | "AStar") {
+        NormExpr::Column("*".to_string())
+    } else if matches!(a, "Null" | "NULL" | "true" | "false")
+        || a.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+    {
+        NormExpr::Literal
+    } else {
+        NormExpr::Other
+    }
+}
+
+fn lower_node(name: &str, n: &Dbg) -> NormExpr {
+    if WRAPPERS.contains(&name) {
+        return n
+            .field("val")
+            .or_else(|| n.field("expr"))
+            .or_else(|| n.positional().next())
+            .map_or(NormExpr::Other, lower_expr);
+    }
+    match name {
+        "Wildcard" | "Star" => return NormExpr::Column("*".to_string()),
+        "QualifiedWildcard" | "TableStar" => {
+            let mut path = strings(n);
+            path.push("*".to_string());
+            return NormExpr::Column(path.join(".").to_lowercase());
+        }
+        "BoolExpr" => return lower_bool_expr(n),
+        "AExpr" => return lower_a_expr(n),
+        "UnaryOp" | "Unary" => return lower_unary(n),
+        _ => {}
+    }
+    if COLUMNS.contains(&name) {
+        let mut path = strings(n);
+        if n.atoms().any(|a| a == "AStar") {
+            path.push("*".to_string());
+        }
+        let path = path.join(".").to_lowercase();
+        // SQLite has no boolean literal, so lemon-rs reads TRUE as a name.
+        return if path == "true" || path == "false" {
+            NormExpr::Literal
+        } else {
+            NormExpr::Column(path)
+        };
+    }
+    if LITERALS.contains(&name) {
+        return NormExpr::Literal;
+    }
+    if CALLS.contains(&name) {
+        return lower_call(n);
+    }
+    if let Some(e) = lower_binary(n, None) {
+        return e;
+    }
+    // An operator named by its variant around an operand pair (`Sub(BinaryOp
+    // { left, right })`).
+    let mut positional = n.positional();
+    if let (Some(only), None) = (positional.next(), positional.next()) {
+        if let Some(e) = lower_binary(only, Some(name)) {
+            return e;
+        }
+    }
+    NormExpr::Other
+}
+
+/// A binary operator: `left`/`right`, `lhs`/`rhs`, or a `(lhs, op, rhs)` tuple,
+/// with the operator in `op`, or in `hint` (the enclosing variant's name).
+fn lower_binary(n: &Dbg, hint: Option<&str>) -> Option<NormExpr> {
+    let pair = [("left", "right"), ("lhs", "rhs")]
+        .iter()
+        .find_map(|(l, r)| Some((n.field(l)?, n.field(r)?)));
+    let (lhs, op, rhs) = if let Some((lhs, rhs)) = pair {
+        (lhs, n.field("op").and_then(Dbg::label).or(hint)?, rhs)
+    } else {
+        let items: Vec<&Dbg> = n.positional().collect();
+        match (n.name(), items.as_slice()) {
+            (Some("Binary" | "BinaryOp"), [lhs, op, rhs]) => (*lhs, op.label()?, *rhs),
+            _ => return None,
+        }
+    };
+    Some(flatten(
+        canon_op(op),
+        vec![lower_expr(lhs), lower_expr(rhs)],
+    ))
+}
+
+/// `pg_query`'s operator node: only plain operators (`AexprOp`) are lowered, the
+/// `IN`/`LIKE`/`BETWEEN`/... kinds stay opaque like their counterparts.
+fn lower_a_expr(n: &Dbg) -> NormExpr {
+    if !n
+        .field("kind")
+        .and_then(Dbg::label)
+        .is_some_and(|k| k == "AexprOp" || k == "1")
+    {
+        return NormExpr::Other;
+    }
+    let Some(op) = n.field("name").and_then(|v| strings(v).pop()) else {
+        return NormExpr::Other;
+    };
+    match (n.field("lexpr"), n.field("rexpr")) {
+        (Some(l), Some(r)) => flatten(canon_op(&op), vec![lower_expr(l), lower_expr(r)]),
+        (None, Some(x)) => unary(canon_op(&op), lower_expr(x)),
+        _ => NormExpr::Other,
+    }
+}
+
+/// `pg_query`'s `AND`/`OR`/`NOT` node, already n-ary.
+fn lower_bool_expr(n: &Dbg) -> NormExpr {
+    let op = match n.field("boolop").and_then(Dbg::label) {
+        Some("AndExpr" | "1") => "and",
+        Some("OrExpr" | "2") => "or",
+        Some("NotExpr" | "3") => "not",
+        _ => return NormExpr::Other,
+    };
+    let args = match n.field("args") {
+        Some(Dbg::List(items)) => items.iter().map(lower_expr).collect(),
+        _ => return NormExpr::Other,
+    };
+    flatten(op.to_string(), args)
+}
+
+fn lower_unary(n: &Dbg) -> NormExpr {
+    if let (Some(op), Some(x)) = (n.field("op").and_then(Dbg::label), n.field("expr")) {
+        return unary(canon_op(op), lower_expr(x));
+    }
+    let items: Vec<&Dbg> = n.positional().collect();
+    match items.as_slice() {
+        [op, x] => op
+            .label()
+            .map_or(NormExpr::Other, |op| unary(canon_op(op), lower_expr(x))),
+        _ => NormExpr::Other,
+    }
+}
+
+/// A unary operator, folding a sign into the literal it applies to (`pg_query`
+/// reads `-1` as one constant, the others as a negation).
+fn unary(op: String, x: NormExpr) -> NormExpr {
+    if matches!(op.as_str(), "-" | "+") && x == NormExpr::Literal {
+        x
+    } else {
+        NormExpr::Op(op, vec![x])
+    }
+}
+
+/// Flatten nested `and`/`or` chains into one n-ary node, matching `pg_query`.
+fn flatten(op: String, args: Vec<NormExpr>) -> NormExpr {
+    if op != "and" && op != "or" {
+        return NormExpr::Op(op, args);
+    }
+    let mut flat = Vec::with_capacity(args.len());
+    for a in args {
+        match a {
+            NormExpr::Op(inner, xs) if inner == op => flat.extend(xs),
+            other => flat.push(other),
+        }
+    }
+    NormExpr::Op(op, flat)
+}
+
+fn lower_call(n: &Dbg) -> NormExpr {
+    let name = find_field(n, "funcname", |_| true)
+        .or_else(|| find_field(n, "name", |_| true))
+        .and_then(|v| strings(v).pop())
+        .unwrap_or_default()
+        .to_lowercase();
+    // `count(*)` carries a star argument in some trees and a flag in others.
+    let args = match find_field(n, "args", |v| matches!(v, Dbg::List(_))) {
+        Some(Dbg::List(items)) => items
+            .iter()
+            .map(lower_expr)
+            .filter(|a| *a != NormExpr::Column("*".to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    NormExpr::Call(name, args)
+}
+
+/// The shallowest field named `key` whose value passes `want` (sqlparser nests
+/// its argument list as `args: List(FunctionArgumentList { args: [..] })`).
+fn find_field<'a>(root: &'a Dbg, key: &str, want: impl Fn(&Dbg) -> bool) -> Option<&'a Dbg> {
+    let mut queue = VecDeque::from([root]);
+    while let Some(n) = queue.pop_front() {
+        match n {
+            Dbg::Node { fields, .. } => {
+                if let Some((_, v)) = fields
+                    .iter()
+                    .find(|(k, v)| k.as_deref() == Some(key) && want(v))
+                {
+                    return Some(v);
+                }
+                queue.extend(fields.iter().map(|(_, v)| v));
+            }
+            Dbg::List(items) => queue.extend(items),
+            Dbg::Str(_) | Dbg::Atom(_) => {}
+        }
+    }
+    None
+}
+
+// Generic traversal.
+
+/// Pre-order visit of every value.
+pub(crate) fn walk(n: &Dbg, f: &mut impl FnMut(&Dbg)) {
+    f(n);
+    match n {
+        Dbg::Node { fields, .. } => fields.iter().for_each(|(_, v)| walk(v, f)),
+        Dbg::List(items) => items.iter().for_each(|v| walk(v, f)),
+        Dbg::Str(_) | Dbg::Atom(_) => {}
+    }
+}
+
+/// Level-order visit of every value, so outer clauses come before inner ones.
+fn breadth_first(root: &Dbg, f: &mut impl FnMut(&Dbg)) {
+    let mut queue = VecDeque::from([root]);
+    while let Some(n) = queue.pop_front() {
+        f(n);
+        match n {
+            Dbg::Node { fields, .. } => queue.extend(fields.iter().map(|(_, v)| v)),
+            Dbg::List(items) => queue.extend(items),
+            Dbg::Str(_) | Dbg::Atom(_) => {}
+        }
+    }
+}
+
+/// Every string leaf under `n`, in order.
+fn strings(n: &Dbg) -> Vec<String> {
+    let mut out = Vec::new();
+    walk(n, &mut |c| {
+        if let Dbg::Str(s) = c {
+            out.push(s.clone());
+        }
+    });
+    out
+}
+
+// A parsed `Debug` rendering.
+
+/// One value of a `Debug` rendering: `Name { k: v }` / `Name(v, ..)` nodes
+/// (anonymous tuples and maps have an empty name), lists, string and char
+/// literals, and bare words (unit variants, numbers, booleans).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Dbg {
+    Node {
+        name: String,
+        fields: Vec<(Option<String>, Self)>,
+    },
+    List(Vec<Self>),
+    Str(String),
+    Atom(String),
+}
+
+impl Dbg {
+    pub(crate) fn name(&self) -> Option<&str> {
+        match self {
+            Self::Node { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn field(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Node { fields, .. } => fields
+                .iter()
+                .find(|(k, _)| k.as_deref() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn positional(&self) -> impl Iterator<Item = &Self> {
+        let fields: &[(Option<String>, Self)] = match self {
+            Self::Node { fields, .. } => fields,
+            _ => &[],
+        };
+        fields.iter().filter(|(k, _)| k.is_none()).map(|(_, v)| v)
+    }
+
+    /// Bare words anywhere under this value.
+    fn atoms(&self) -> impl Iterator<Item = String> {
+        let mut out = Vec::new();
+        walk(self, &mut |c| {
+            if let Self::Atom(a) = c {
+                out.push(a.clone());
+            }
+        });
+        out.into_iter()
+    }
+
+    /// A word naming this value: a unit variant, a string, or a node's name
+    /// (`op: Minus`, `op: Sub(..)`, `name: [String { sval: "-" }]`).
+    fn label(&self) -> Option<&str> {
+        match self {
+            Self::Atom(a) | Self::Str(a) => Some(a),
+            Self::Node { name, .. } if !name.is_empty() => Some(name),
+            Self::List(items) => items.last().and_then(Self::label),
+            Self::Node { .. } => None,
+        }
+    }
+}
+
+/// Fields dropped while parsing for [`lower`]: positions and quoting carry no
+/// structure.
+fn skipped(key: &str) -> bool {
+    matches!(
+        key,
+        "location" | "span" | "stmt_location" | "stmt_len" | "quote_style" | "quote"
+    ) || key.ends_with("_span")
+        || key.ends_with("_token")
+        || key.ends_with("_location")
+}
+
+/// A lenient recursive-descent reader for `Debug` output. Unexpected input is
+/// kept as extra atoms rather than rejected, so an unusual `Debug` impl (spans
+/// printed as `Location(1,1)..Location(1,8)`, bitflags as `A | B`) degrades to
+/// noise instead of failing the whole lowering.
+pub(crate) struct DebugParser<'a> {
+    src: &'a [u8],
+    pos: usize,
+    keep_positions: bool,
+    depth: usize,
+}
+
+/// Deepest value [`DebugParser`] builds. A deeper value is skipped whole and
+/// read as an empty atom, so a deeply nested statement cannot overflow the
+/// stack in the reader or in the recursive walks over its result.
+const MAX_DEPTH: usize = 256;
+
+impl<'a> DebugParser<'a> {
+    const fn new(src: &'a str) -> Self {
+        Self {
+            src: src.as_bytes(),
+            pos: 0,
+            keep_positions: false,
+            depth: 0,
+        }
+    }
+
+    /// A reader that keeps the position and quoting fields [`Self::new`]
+    /// drops, for checks on the spans themselves.
+    pub(crate) const fn with_positions(src: &'a str) -> Self {
+        Self {
+            keep_positions: true,
+            ..Self::new(src)
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    pub(crate) fn value(&mut self) -> Dbg {
+        self.skip_ws();
+        if self.depth >= MAX_DEPTH {
+            self.skip_value();
+            return Dbg::Atom(String::new());
+        }
+        self.depth += 1;
+        let v = self.unbounded_value();
+        self.depth -= 1;
+        v
+    }
+
+    fn unbounded_value(&mut self) -> Dbg {
+        match self.peek() {
+            Some(b'"') => Dbg::Str(self.quoted(b'"')),
+            Some(b'\'') => Dbg::Str(self.quoted(b'\'')),
+            Some(b'[') => Dbg::List(self.seq(b']')),
+            Some(b'(') => Self::node(String::new(), self.tuple()),
+            Some(b'{') => Self::node(String::new(), self.fields()),
+            _ => {
+                let word = self.word();
+                self.skip_ws();
+                match self.peek() {
+                    Some(b'(') => Self::node(word, self.tuple()),
+                    Some(b'{') => Self::node(word, self.fields()),
+                    _ => Dbg::Atom(word),
+                }
+            }
+        }
+    }
+
+    /// Consume one value without building it: up to the `,` or closing
+    /// bracket that ends it, skipping nested brackets and quoted text.
+    fn skip_value(&mut self) {
+        let mut open = 0_usize;
+        while let Some(c) = self.peek() {
+            match c {
+                b'"' | b'\'' => {
+                    self.quoted(c);
+                    continue;
+                }
+                b'(' | b'[' | b'{' => open += 1,
+                b')' | b']' | b'}' | b',' if open == 0 => return,
+                b')' | b']' | b'}' => open -= 1,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Build a node, unwrapping the transparent layers every tree has:
+    /// `Some(x)`, `pg_query`'s `Node { node: x }`, and a variant wrapping a struct
+    /// of the same name (`Select(Select { .. })`).
+    fn node(name: String, fields: Vec<(Option<String>, Dbg)>) -> Dbg {
+        if let [(key, only)] = fields.as_slice() {
+            let same_name = matches!(only, Dbg::Node { name: inner, .. } if *inner == name);
+            if (name == "Some" && key.is_none())
+                || (name == "Node" && key.as_deref() == Some("node"))
+                || (key.is_none() && same_name)
+            {
+                return only.clone();
+            }
+        }
+        Dbg::Node { name, fields }
+    }
+
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_ascii_whitespace() && !b",:()[]{}\"'".contains(&c))
+        {
+            self.pos += 1;
+        }
+        if self.pos == start && self.pos < self.src.len() {
+            // A stray delimiter: consume it so parsing always makes progress.
+            self.pos += 1;
+        }
+        String::from_utf8_lossy(&self.src[start..self.pos]).into_owned()
+    }
+
+    fn quoted(&mut self, quote: u8) -> String {
+        self.pos += 1;
+        let mut out = Vec::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == quote {
+                break;
+            }
+            if c == b'\\' {
+                if let Some(e) = self.peek() {
+                    self.pos += 1;
+                    out.push(match e {
+                        b'n' => b'\n',
+                        b't' => b'\t',
+                        b'r' => b'\r',
+                        other => other,
+                    });
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
+    /// A comma-separated sequence up to `close`, dropping `None` entries.
+    fn seq(&mut self, close: u8) -> Vec<Dbg> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                None => break,
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    let v = self.value();
+                    if v != Dbg::Atom("None".to_string()) {
+                        out.push(v);
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn tuple(&mut self) -> Vec<(Option<String>, Dbg)> {
+        self.seq(b')').into_iter().map(|v| (None, v)).collect()
+    }
+
+    /// `{ key: value, .. }`, dropping `None` values and (unless positions are
+    /// kept) [`skipped`] keys.
+    fn fields(&mut self) -> Vec<(Option<String>, Dbg)> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                None => break,
+                Some(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => self.pos += 1,
+                Some(_) => {
+                    let key = self.value();
+                    self.skip_ws();
+                    if self.peek() == Some(b':') {
+                        self.pos += 1;
+                        let v = self.value();
+                        let key = match key {
+                            Dbg::Atom(k) | Dbg::Str(k) => k,
+                            _ => String::new(),
+                        };
+                        if (self.keep_positions || !skipped(&key))
+                            && v != Dbg::Atom("None".to_string())
+                        {
+                            out.push((Some(key), v));
+                        }
+                    } else {
+                        out.push((None, key));
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lower, Dbg, DebugParser, NormExpr};
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    fn col(name: &str) -> NormExpr {
+        NormExpr::Column(name.to_string())
+    }
+
+    fn op(o: &str, args: Vec<NormExpr>) -> NormExpr {
+        NormExpr::Op(o.to_string(), args)
+    }
+
+    // `SELECT a - b * c FROM t WHERE x = 1`, as pg_query prints it (trimmed).
+    const PG: &str = r#"ParseResult { version: 170004, stmts: [RawStmt { stmt: Some(Node { node: Some(SelectStmt(SelectStmt { target_list: [Node { node: Some(ResTarget(ResTarget { name: "", indirection: [], val: Some(Node { node: Some(AExpr(AExpr { kind: AexprOp, name: [Node { node: Some(String(String { sval: "-" })) }], lexpr: Some(Node { node: Some(ColumnRef(ColumnRef { fields: [Node { node: Some(String(String { sval: "a" })) }], location: 7 })) }), rexpr: Some(Node { node: Some(AExpr(AExpr { kind: AexprOp, name: [Node { node: Some(String(String { sval: "*" })) }], lexpr: Some(Node { node: Some(ColumnRef(ColumnRef { fields: [Node { node: Some(String(String { sval: "b" })) }], location: 11 })) }), rexpr: Some(Node { node: Some(ColumnRef(ColumnRef { fields: [Node { node: Some(String(String { sval: "c" })) }], location: 15 })) }), location: 13 })) }), location: 9 })) }), location: 7 })) }], from_clause: [Node { node: Some(RangeVar(RangeVar { catalogname: "", schemaname: "", relname: "t", inh: true, relpersistence: "p", alias: None, location: 22 })) }], where_clause: Some(Node { node: Some(AExpr(AExpr { kind: AexprOp, name: [Node { node: Some(String(String { sval: "=" })) }], lexpr: Some(Node { node: Some(ColumnRef(ColumnRef { fields: [Node { node: Some(String(String { sval: "x" })) }], location: 30 })) }), rexpr: Some(Node { node: Some(AConst(AConst { isnull: false, location: 34, val: Some(Ival(Integer { ival: 1 })) })) }), location: 32 })) }), op: SetopNone, all: false, larg: None, rarg: None })) }), stmt_location: 0, stmt_len: 0 }] }"#;
+
+    // The same statement as lemon-rs prints it.
+    const LEMON: &str = r#"Stmt(Select(Select { with: None, body: SelectBody { select: Select { distinctness: None, columns: [Expr(Binary(Id(Id("a")), Subtract, Binary(Id(Id("b")), Multiply, Id(Id("c")))), None)], from: Some(FromClause { select: Some(Table(QualifiedName { db_name: None, name: Name("t"), alias: None }, None, None)), joins: None, op: None }), where_clause: Some(Binary(Id(Id("x")), Equals, Literal(Numeric("1")))), group_by: None, window_clause: None }, compounds: None }, order_by: None, limit: None }))"#;
+
+    // A parser that got the precedence wrong: `(a - b) * c`, printed the way
+    // sqlparser-rs prints its tree.
+    const WRONG: &str = r#"[Query(Query { with: None, body: Select(Select { distinct: None, projection: [UnnamedExpr(BinaryOp { left: BinaryOp { left: Identifier(Ident { value: "a", quote_style: None, span: Span(Location(1,8)..Location(1,9)) }), op: Minus, right: Identifier(Ident { value: "b", quote_style: None, span: Span(Location(1,12)..Location(1,13)) }) }, op: Multiply, right: Identifier(Ident { value: "c", quote_style: None, span: Span(Location(1,16)..Location(1,17)) }) })], from: [TableWithJoins { relation: Table { name: ObjectName([Identifier(Ident { value: "t", quote_style: None, span: Span(Location(1,23)..Location(1,24)) })]), alias: None }, joins: [] }], selection: Some(BinaryOp { left: Identifier(Ident { value: "x", quote_style: None, span: Span(Location(1,31)..Location(1,32)) }), op: Eq, right: Value(ValueWithSpan { value: Number("1", false), span: Span(Location(1,35)..Location(1,36)) }) }) }), order_by: None })]"#;
+
+    #[test]
+    fn reference_trees_lower_to_the_same_shape() {
+        let pg = lower(PG);
+        let lemon = lower(LEMON);
+        assert_eq!(pg.kind, "select");
+        assert_eq!(pg.tables, vec!["t"]);
+        assert_eq!(
+            pg.projections,
+            vec![op("-", vec![col("a"), op("*", vec![col("b"), col("c")])])]
+        );
+        assert_eq!(
+            pg.predicates,
+            vec![op("=", vec![col("x"), NormExpr::Literal])]
+        );
+        assert_eq!(pg, lemon);
+        assert!(pg.agrees(&lemon));
+    }
+
+    #[test]
+    fn wrong_precedence_disagrees() {
+        let wrong = lower(WRONG);
+        assert_eq!(wrong.tables, vec!["t"]);
+        assert_eq!(
+            wrong.projections,
+            vec![op("*", vec![op("-", vec![col("a"), col("b")]), col("c")])]
+        );
+        assert!(!wrong.agrees(&lower(PG)));
+    }
+
+    #[test]
+    fn sqlparser_agrees_with_reference_shape() {
+        let t = BenchParser::Sqlparser
+            .normalized("SELECT a - b * c FROM t WHERE x = 1", Dialect::Postgresql)
+            .unwrap();
+        assert!(t.agrees(&lower(PG)), "{t:?}");
+    }
+
+    #[test]
+    fn other_matches_anything_and_chains_flatten() {
+        assert!(NormExpr::Other.matches(&col("a")));
+        assert!(!col("a").matches(&col("b")));
+        // ((a AND b) AND c) and pg_query's n-ary AND lower alike.
+        let nested = lower(
+            "Select { selection: BinaryOp { left: BinaryOp { left: Identifier(\"a\"), op: And, right: Identifier(\"b\") }, op: And, right: Identifier(\"c\") } }",
+        );
+        let nary = lower(
+            "SelectStmt { where_clause: BoolExpr { boolop: AndExpr, args: [ColumnRef { fields: [String { sval: \"a\" }] }, ColumnRef { fields: [String { sval: \"b\" }] }, ColumnRef { fields: [String { sval: \"c\" }] }] } }",
+        );
+        assert_eq!(nested.predicates, nary.predicates);
+    }
+
+    #[test]
+    fn joins_and_ddl() {
+        let join = lower(
+            "Select { from: [TableWithJoins { relation: Table { name: ObjectName([\"a\"]) }, joins: [Join { relation: Table { name: ObjectName([\"b\"]) }, join_operator: LeftOuter(On(Identifier(\"x\"))) }] }] }",
+        );
+        assert_eq!(join.joins, vec!["left"]);
+        assert_eq!(join.tables, vec!["a", "b"]);
+        // DDL compares kind only: object names are modelled too differently.
+        let ddl = lower("CreateStmt { relation: RangeVar { relname: \"t\" } }");
+        assert_eq!(ddl.kind, "create_table");
+        assert!(ddl.tables.is_empty());
+    }
+
+    #[test]
+    fn debug_reader_unwraps_and_drops_positions() {
+        let v =
+            DebugParser::new("Some(Node { node: Some(Foo(Foo { a: 1, location: 3, b: None })) })")
+                .value();
+        assert_eq!(
+            v,
+            Dbg::Node {
+                name: "Foo".to_string(),
+                fields: vec![(Some("a".to_string()), Dbg::Atom("1".to_string()))],
+            }
+        );
+        // Malformed input still terminates.
+        let _ = DebugParser::new("Foo { a: ) ] ,, }").value();
+    }
+
+    #[test]
+    fn deep_nesting_is_cut_off_instead_of_overflowing() {
+        let n = 100_000;
+        let deep = format!("{}x{}", "Paren(".repeat(n), ")".repeat(n));
+        let t = lower(&format!(
+            "Select {{ projection: [{deep}], from: [Table(\"t\")] }}"
+        ));
+        assert_eq!(t.projections, vec![NormExpr::Other]);
+        assert_eq!(t.tables, vec!["t"]);
+        assert!(t.is_opaque());
+    }
+
+    #[test]
+    fn only_trees_with_something_lowered_are_comparable() {
+        assert!(!lower(PG).is_opaque());
+        // DDL has no expressions, but its kind is still compared.
+        assert!(!lower("CreateStmt { relation: RangeVar { relname: \"t\" } }").is_opaque());
+        assert!(lower("VacuumStmt { options: [] }").is_opaque());
+    }
+
+    #[test]
+    fn create_table_as_is_a_create_table_in_every_tree() {
+        let pg = lower(
+            "CreateTableAsStmt { query: SelectStmt { from_clause: [RangeVar { relname: \"s\" }] }, into: IntoClause { rel: RangeVar { relname: \"t\" } }, objtype: ObjectTable }",
+        );
+        let sp = lower("CreateTable { name: ObjectName([\"t\"]), query: Query { body: Select { from: [Table(\"s\")] } } }");
+        assert_eq!(pg.kind, "create_table");
+        assert!(pg.agrees(&sp));
+        let mv = lower("CreateTableAsStmt { query: SelectStmt {}, objtype: ObjectMatview }");
+        assert_eq!(mv.kind, "create_view");
+    }
+}
@@ -6,6 +6,21 @@
 //! lines are `hash\t0|1` where the hash is [`statement_hash`] and `1` means the
 //! real database engine parsed the statement (valid). The cache is loaded once
 //! and shared. Dialects without a file are not reference-graded.
+//!
+//! A cache labeled against several engine releases lists them after the count
+//! on the header line (`count\t12,13,14,15,16,17`, oldest first), and each row
+//! carries a third column with one `0`/`1` per release in the same order. The
+//! second column stays the verdict of the pinned engine the rest of the
+//! benchmark grades against, so a reader that ignores the versions still works.
+//! [`reference_accepts_at`] reads one release's verdict. At most
+//! [`MAX_RELEASES`] releases fit one cache, and so far only the oracle's
+//! `PostgreSQL` adapter labels per release: every other cache is single-engine.
+//!
+//! Version 2 of the format starts the header with `v2` (`v2\tcount[\treleases]`)
+//! and adds the engine's [`EngineClass`] as the third column of every row, ahead
+//! of the per-release bits: `hash\t0|1\tclass[\tbits]`. A header without the
+//! `v2` prefix is version 1, so older label files still load, with every row
+//! classed by its valid bit alone.
 
 use crate::datasets::Dialect;
 use std::collections::HashMap;
@@ -14,6 +29,13 @@ use std::sync::OnceLock;
 /// Directory holding the committed per-dialect validity caches.
 pub const LABELS_DIR: &str = "oracle/labels";
 
+/// Header prefix of a version-2 cache, whose rows carry an [`EngineClass`].
+pub const FORMAT_V2: &str = "v2\t";
+
+/// Most engine releases one cache can label: a row keeps its per-release bits
+/// in a `u64`.
+pub const MAX_RELEASES: usize = 64;
+
 /// Stable 64-bit FNV-1a hash of a statement, used by both the `oracle` producer
 /// and this reader so keys line up regardless of std hashing changes.
 #[must_use]
@@ -26,7 +48,104 @@ pub fn statement_hash(sql: &str) -> u64 {
     h
 }
 
-type Labels = HashMap<&'static str, HashMap<u64, bool>>;
+/// The engine's own classification of a statement, kept next to the valid bit.
+///
+/// The bit follows each adapter's documented rule, which differs per engine on
+/// where an unsupported feature or an exceeded limit falls (a `0A000` from the
+/// `PostgreSQL` server comes after its parser accepted the statement, while
+/// `SQLite` reports "not currently supported" from the grammar), so the class
+/// refines the bit rather than replacing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EngineClass {
+    /// No error.
+    Valid,
+    /// A syntax or grammar error.
+    Syntax,
+    /// A missing object, binding or other post-parse error.
+    Semantic,
+    /// A feature the engine recognizes but does not implement.
+    Unsupported,
+    /// An engine limit (nesting depth, statement size, column count) exceeded,
+    /// or a statement that brought the engine down.
+    Limit,
+}
+
+impl EngineClass {
+    pub const ALL: [Self; 5] = [
+        Self::Valid,
+        Self::Syntax,
+        Self::Semantic,
+        Self::Unsupported,
+        Self::Limit,
+    ];
+
+    /// The name used in the cache files and contentious rules.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Syntax => "syntax",
+            Self::Semantic => "semantic",
+            Self::Unsupported => "unsupported",
+            Self::Limit => "limit",
+        }
+    }
+
+    /// The class named `name`, as written by [`Self::name`].
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+/// One statement's label: the valid bit grading uses and the engine's class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub valid: bool,
+    pub class: EngineClass,
+}
+
+impl Label {
+    /// The engine ran the statement without error.
+    pub const VALID: Self = Self::parsed(EngineClass::Valid);
+    /// The engine reported a syntax error.
+    pub const SYNTAX: Self = Self::rejected(EngineClass::Syntax);
+
+    /// An error of `class` that still counts as parsed.
+    #[must_use]
+    pub const fn parsed(class: EngineClass) -> Self {
+        Self { valid: true, class }
+    }
+
+    /// An error of `class` that counts as a rejection.
+    #[must_use]
+    pub const fn rejected(class: EngineClass) -> Self {
+        Self {
+            valid: false,
+            class,
+        }
+    }
+}
+
+/// One statement's row: the pinned engine's label and, for a multi-release
+/// cache, one bit per release (bit `i` for `versions[i]`) over the first
+/// `labeled` releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Row {
+    label: Label,
+    releases: u64,
+    labeled: usize,
+}
+
+/// One dialect's cache: the labeled engine releases (oldest first, empty for a
+/// single-engine cache) and the row of each statement hash.
+#[derive(Debug, Default)]
+struct DialectLabels {
+    versions: Vec<String>,
+    rows: HashMap<u64, Row>,
+}
+
+type Labels = HashMap<&'static str, DialectLabels>;
 
 fn labels() -> &'static Labels {
     static CACHE: OnceLock<Labels> = OnceLock::new();
@@ -43,22 +162,65 @@ fn load_all() -> Labels {
     out
 }
 
-fn load_dialect(d: Dialect) -> Option<HashMap<u64, bool>> {
+fn load_dialect(d: Dialect) -> Option<DialectLabels> {
     let path = format!("{LABELS_DIR}/{}.tsv.zst", d.dir_name());
     let bytes = std::fs::read(&path).ok()?;
     let text = zstd::decode_all(&bytes[..]).ok()?;
     let text = String::from_utf8(text).ok()?;
-    let mut map = HashMap::new();
-    // Skip the first line (corpus count header).
-    for line in text.lines().skip(1) {
+    Some(parse_labels(&text))
+}
+
+/// Parse a decompressed cache of either format. A version-1 row has no class,
+/// so its valid bit stands for [`Label::VALID`] or [`Label::SYNTAX`]. A
+/// per-release column shorter than the header's release list leaves the
+/// missing releases unlabeled.
+fn parse_labels(text: &str) -> DialectLabels {
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or("");
+    let (v2, header) = header
+        .strip_prefix(FORMAT_V2)
+        .map_or((false, header), |rest| (true, rest));
+    let versions: Vec<String> = header
+        .split_once('\t')
+        .map(|(_, v)| v.split(',').map(str::to_string).collect())
+        .unwrap_or_default();
+    let mut rows = HashMap::new();
+    for line in lines {
         let mut it = line.split('\t');
-        if let (Some(h), Some(b)) = (it.next(), it.next()) {
-            if let Ok(h) = h.parse::<u64>() {
-                map.insert(h, b == "1");
-            }
-        }
+        let (Some(Ok(h)), Some(b)) = (it.next().map(str::parse::<u64>), it.next()) else {
+            continue;
+        };
+        let valid = b == "1";
+        let class = if v2 {
+            it.next().and_then(EngineClass::from_name)
+        } else {
+            None
+        };
+        let label = Label {
+            valid,
+            class: class.unwrap_or(if valid {
+                EngineClass::Valid
+            } else {
+                EngineClass::Syntax
+            }),
+        };
+        let column = it.next().unwrap_or("").as_bytes();
+        let labeled = column.len().min(MAX_RELEASES);
+        let releases = column[..labeled]
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == b'1')
+            .fold(0, |acc, (i, _)| acc | 1 << i);
+        rows.insert(
+            h,
+            Row {
+                label,
+                releases,
+                labeled,
+            },
+        );
     }
-    Some(map)
+    DialectLabels { versions, rows }
 }
 
 /// Whether `d` has a committed real-engine reference cache.
@@ -74,8 +236,94 @@ pub fn has_reference(d: Dialect) -> bool {
 /// miss the caller should skip).
 #[must_use]
 pub fn reference_accepts(sql: &str, d: Dialect) -> Option<bool> {
+    reference_label(sql, d).map(|l| l.valid)
+}
+
+/// The real engine's classification of `sql` in `d`, `None` as for
+/// [`reference_accepts`].
+#[must_use]
+pub fn reference_class(sql: &str, d: Dialect) -> Option<EngineClass> {
+    reference_label(sql, d).map(|l| l.class)
+}
+
+/// The committed [`Label`] of `sql` in `d`, `None` as for
+/// [`reference_accepts`].
+#[must_use]
+pub fn reference_label(sql: &str, d: Dialect) -> Option<Label> {
     labels()
         .get(d.dir_name())?
+        .rows
         .get(&statement_hash(sql))
-        .copied()
+        .map(|r| r.label)
+}
+
+/// The engine releases `d`'s cache was labeled against, oldest first. Empty
+/// when the cache holds only the pinned engine's verdicts.
+#[must_use]
+pub fn engine_versions(d: Dialect) -> &'static [String] {
+    labels()
+        .get(d.dir_name())
+        .map_or(&[], |l| l.versions.as_slice())
+}
+
+/// As [`reference_accepts`], but the verdict of engine release `version` (one
+/// of [`engine_versions`]). `None` also when `version` was not labeled.
+#[must_use]
+pub fn reference_accepts_at(sql: &str, d: Dialect, version: &str) -> Option<bool> {
+    let l = labels().get(d.dir_name())?;
+    lookup_at(l, statement_hash(sql), version)
+}
+
+fn lookup_at(l: &DialectLabels, hash: u64, version: &str) -> Option<bool> {
+    let i = l.versions.iter().position(|v| v == version)?;
+    let r = l.rows.get(&hash)?;
+    (i < r.labeled).then_some(r.releases >> i & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lookup_at, parse_labels, EngineClass, Label};
+
+    #[test]
+    fn single_engine_caches_have_no_versions() {
+        let l = parse_labels("3\n11\t1\n12\t0\n");
+        assert!(l.versions.is_empty());
+        assert_eq!(l.rows[&11].label, Label::VALID);
+        assert_eq!(l.rows[&12].label, Label::SYNTAX);
+        assert_eq!(l.rows[&11].labeled, 0);
+        assert_eq!(lookup_at(&l, 11, "17"), None);
+    }
+
+    #[test]
+    fn version_2_rows_carry_the_engine_class() {
+        let l = parse_labels("v2\t3\t16,17\n1\t1\tunsupported\t11\n2\t0\tlimit\n3\t1\tbogus\n");
+        assert_eq!(l.versions, ["16", "17"]);
+        assert_eq!(l.rows[&1].label, Label::parsed(EngineClass::Unsupported));
+        assert_eq!(lookup_at(&l, 1, "16"), Some(true));
+        assert_eq!(l.rows[&2].label, Label::rejected(EngineClass::Limit));
+        // An unknown class falls back to the valid bit, as in version 1.
+        assert_eq!(l.rows[&3].label, Label::VALID);
+    }
+
+    #[test]
+    fn class_names_roundtrip() {
+        for c in EngineClass::ALL {
+            assert_eq!(EngineClass::from_name(c.name()), Some(c));
+        }
+        assert_eq!(EngineClass::from_name("Valid"), None);
+    }
+
+    #[test]
+    fn per_release_bits_follow_the_header_order() {
+        // Statement 7 is valid only from release 16 on (MERGE ... RETURNING).
+        let l = parse_labels("2\t14,15,16,17\n7\t1\t0011\n8\t0\t00\n");
+        assert_eq!(l.versions, ["14", "15", "16", "17"]);
+        assert_eq!(lookup_at(&l, 7, "15"), Some(false));
+        assert_eq!(lookup_at(&l, 7, "16"), Some(true));
+        assert_eq!(lookup_at(&l, 7, "13"), None);
+        // A short bitmap leaves the later releases unlabeled.
+        assert_eq!(lookup_at(&l, 8, "15"), Some(false));
+        assert_eq!(lookup_at(&l, 8, "17"), None);
+        assert_eq!(lookup_at(&l, 9, "17"), None);
+    }
 }
@@ -0,0 +1,233 @@
+//! Bind-parameter (placeholder) syntax coverage.
+//!
+//! Drivers and ORMs send SQL with placeholders in whatever style the driver
+//! speaks, and a parser in the path has to accept it. This builds a probe
+//! suite per dialect from the corpus itself: data statements (`SELECT`,
+//! `INSERT`, `UPDATE`, `DELETE`, `WITH`) whose last literal is swapped for a
+//! placeholder in each [`Style`]. A parser is graded only on the templates it
+//! accepts with the literal in place, so a rejection reflects the placeholder
+//! and not a grammar gap elsewhere in the statement.
+//!
+//! Parsers are probed as configured for the rest of the benchmark, so
+//! qusql-parse, set up with `SQLArguments::Dollar`, is graded on `$1` support
+//! only even though other settings accept `?` or `%s`.
+
+use crate::datasets::Dialect;
+use crate::edit::last_literal;
+use crate::report::{self, WORKER_STACK};
+use crate::{has_reference, reference_accepts, Parser, ParserId};
+
+/// Data statements sampled per dialect as templates, evenly spread over the
+/// corpus.
+pub const TEMPLATES: usize = 300;
+
+/// A bind-parameter syntax.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// `$1`: `PostgreSQL`, libpq and most Rust Postgres drivers.
+    Dollar,
+    /// `?`: JDBC, ODBC, `MySQL` and `SQLite` drivers.
+    Question,
+    /// `?1`: `SQLite` numbered parameters.
+    NumberedQuestion,
+    /// `:name`: Oracle, `SQLite` and named-parameter ORMs.
+    Colon,
+    /// `@name`: SQL Server and `SQLite`.
+    At,
+    /// `%s`: Python DB-API `format` style (psycopg, `MySQLdb`).
+    Percent,
+    /// `${var}`: template variables (Hive and Spark substitution, `MyBatis`).
+    Brace,
+}
+
+impl Style {
+    pub const ALL: [Self; 7] = [
+        Self::Dollar,
+        Self::Question,
+        Self::NumberedQuestion,
+        Self::Colon,
+        Self::At,
+        Self::Percent,
+        Self::Brace,
+    ];
+
+    /// Column label.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Dollar => "$1",
+            Self::Question => "?",
+            Self::NumberedQuestion => "?1",
+            Self::Colon => ":name",
+            Self::At => "@name",
+            Self::Percent => "%s",
+            Self::Brace => "${var}",
+        }
+    }
+
+    /// The placeholder text put in place of the literal.
+    #[must_use]
+    pub const fn token(self) -> &'static str {
+        match self {
+            Self::Dollar => "$1",
+            Self::Question => "?",
+            Self::NumberedQuestion => "?1",
+            Self::Colon => ":p1",
+            Self::At => "@p1",
+            Self::Percent => "%s",
+            Self::Brace => "${p1}",
+        }
+    }
+}
+
+/// A corpus statement with its last literal cut out, ready for a placeholder.
+pub struct Template {
+    /// The original statement, literal in place.
+    pub base: String,
+    before: String,
+    after: String,
+}
+
+impl Template {
+    /// A template from a data statement with a literal, else `None`.
+    #[must_use]
+    pub fn new(sql: &str) -> Option<Self> {
+        let first = sql.split_whitespace().next()?.to_ascii_uppercase();
+        if !matches!(
+            first.trim_start_matches('('),
+            "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "WITH"
+        ) {
+            return None;
+        }
+        let r = last_literal(sql)?;
+        Some(Self {
+            base: sql.to_string(),
+            before: sql[..r.start].to_string(),
+            after: sql[r.end..].to_string(),
+        })
+    }
+
+    /// The statement with `style`'s placeholder in place of the literal.
+    #[must_use]
+    pub fn with(&self, style: Style) -> String {
+        format!("{}{}{}", self.before, style.token(), self.after)
+    }
+}
+
+/// Up to [`TEMPLATES`] templates from `stmts`, evenly spread. On a dialect
+/// with a reference engine only reference-valid statements are used.
+#[must_use]
+pub fn templates(stmts: &[String], dialect: Dialect) -> Vec<Template> {
+    let reference = has_reference(dialect);
+    let all: Vec<Template> = stmts
+        .iter()
+        .filter(|s| !reference || reference_accepts(s, dialect) == Some(true))
+        .filter_map(|s| Template::new(s))
+        .collect();
+    let stride = all.len().div_ceil(TEMPLATES).max(1);
+    all.into_iter().step_by(stride).collect()
+}
+
+/// One parser's placeholder acceptance in one dialect.
+pub struct ParserPlaceholders {
+    pub parser: ParserId,
+    /// Templates the parser accepts with the literal in place (the base).
+    pub base: usize,
+    /// Of those, how many it accepts with each placeholder, in
+    /// [`Style::ALL`] order.
+    pub accepted: Vec<usize>,
+}
+
+/// Grade one parser on `templates`.
+#[must_use]
+pub fn grade(parser: &dyn Parser, dialect: Dialect, templates: &[Template]) -> ParserPlaceholders {
+    let mut base = 0;
+    let mut accepted = vec![0; Style::ALL.len()];
+    for t in templates {
+        if parser.accepts(&t.base, dialect) != Some(true) {
+            continue;
+        }
+        base += 1;
+        for (n, style) in accepted.iter_mut().zip(Style::ALL) {
+            if parser.accepts(&t.with(style), dialect) == Some(true) {
+                *n += 1;
+            }
+        }
+    }
+    ParserPlaceholders {
+        parser: parser.id(),
+        base,
+        accepted,
+    }
+}
+
+/// The placeholder matrix for `dialect` over the parsers that model it, one
+/// [`WORKER_STACK`] thread per parser, with the template count. Empty without
+/// a corpus.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while grading.
+#[allow(clippy::needless_collect)] // handles must all spawn before any join
+#[must_use]
+pub fn placeholders_dialect(
+    dialect: Dialect,
+    all_parsers: &[&dyn Parser],
+) -> (usize, Vec<ParserPlaceholders>) {
+    let templates = templates(&report::load_dialect(dialect), dialect);
+    if templates.is_empty() {
+        return (0, Vec::new());
+    }
+    let rows = std::thread::scope(|scope| {
+        let handles: Vec<_> = all_parsers
+            .iter()
+            .filter(|p| p.supports(dialect))
+            .map(|&p| {
+                let templates = &templates;
+                std::thread::Builder::new()
+                    .stack_size(WORKER_STACK)
+                    .spawn_scoped(scope, move || grade(p, dialect, templates))
+                    .expect("spawn worker")
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("placeholder thread panicked"))
+            .collect()
+    });
+    (templates.len(), rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grade, Style, Template};
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    #[test]
+    fn the_last_literal_of_a_data_statement_becomes_the_placeholder() {
+        let t = Template::new("SELECT a FROM t WHERE b = 'x' AND c = 10").expect("template");
+        assert_eq!(
+            t.with(Style::Dollar),
+            "SELECT a FROM t WHERE b = 'x' AND c = $1"
+        );
+        assert_eq!(
+            t.with(Style::Brace),
+            "SELECT a FROM t WHERE b = 'x' AND c = ${p1}"
+        );
+        // Schema statements and statements without a literal are not templates.
+        assert!(Template::new("CREATE TABLE t (a VARCHAR(10))").is_none());
+        assert!(Template::new("SELECT a FROM t").is_none());
+    }
+
+    #[test]
+    fn only_templates_the_parser_accepts_count_toward_its_base() {
+        let templates: Vec<Template> = ["SELECT a FROM t WHERE b = 1", "SELECT 1 FROM"]
+            .iter()
+            .filter_map(|s| Template::new(s))
+            .collect();
+        let g = grade(&BenchParser::Sqlparser, Dialect::Postgresql, &templates);
+        assert_eq!(g.base, 1);
+        let dollar = Style::ALL.iter().position(|&s| s == Style::Dollar).unwrap();
+        assert_eq!(g.accepted[dollar], 1, "sqlparser-rs takes $1 in PostgreSQL");
+    }
+}
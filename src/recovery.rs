@@ -0,0 +1,233 @@
+//! Error recovery: how much of a broken script a parser still returns.
+//!
+//! A linter, formatter or editor has to cope with a script that is wrong in
+//! one place, and a parser that gives up on the whole file leaves it nothing
+//! to work with. This axis takes batches of statements the parser accepts,
+//! joined exactly as [`batch::join_batch`] does, plants one statement the
+//! reference engine rejects (and the parser rejects too) at a random position,
+//! and asks the parser's recovering entry point ([`crate::Parser::recover`])
+//! what it returns.
+//!
+//! A valid statement counts as recovered when a returned range starts inside
+//! it and does not run past its terminator. A range starting in the planted
+//! statement never counts, so a parser that glues the bad statement onto the
+//! next good one loses that one. A script from which no valid statement comes
+//! back is one the parser gave up on. Only reference dialects take part, since
+//! the planted statements come from the reference-invalid set.
+
+use crate::batch::{batch_eligible, join_batch_with, sample_batches, seed_for};
+use crate::datasets::Dialect;
+use crate::report::{self, WORKER_STACK};
+use crate::split::trim;
+use crate::{has_reference, reference_accepts, Parser, ParserId};
+use std::ops::Range;
+
+/// Statements per script, the planted one included.
+pub const RECOVERY_M: usize = 16;
+
+/// Sampled scripts per (parser, dialect).
+pub const RECOVERY_K: usize = 50;
+
+/// A script with one invalid statement planted among valid ones.
+pub struct Script {
+    pub sql: String,
+    /// Byte range of each statement, up to (not including) its terminator.
+    pub statements: Vec<Range<usize>>,
+    /// Index into `statements` of the planted invalid statement.
+    pub planted: usize,
+}
+
+impl Script {
+    /// `valid` joined with `invalid` inserted at index `at` (clamped).
+    #[must_use]
+    pub fn new(valid: &[&str], invalid: &str, at: usize) -> Self {
+        let planted = at.min(valid.len());
+        let mut stmts = valid.to_vec();
+        stmts.insert(planted, invalid);
+        let (sql, boundaries) = join_batch_with(&stmts, ";");
+        let starts = std::iter::once(0).chain(boundaries.iter().map(|b| b + 1));
+        let ends = boundaries.iter().copied().chain(std::iter::once(sql.len()));
+        Self {
+            statements: starts.zip(ends).map(|(s, e)| s..e).collect(),
+            sql,
+            planted,
+        }
+    }
+
+    /// Valid statements in the script.
+    #[must_use]
+    pub const fn expected(&self) -> usize {
+        self.statements.len() - 1
+    }
+
+    /// Valid statements recovered from the `ranges` a parser returned.
+    #[must_use]
+    pub fn score(&self, ranges: &[Range<usize>]) -> usize {
+        let mut found = vec![false; self.statements.len()];
+        for r in ranges.iter().filter_map(|r| trim(&self.sql, r)) {
+            if let Some(i) = self.statements.iter().position(|s| s.contains(&r.start)) {
+                if i != self.planted && r.end <= self.statements[i].end {
+                    found[i] = true;
+                }
+            }
+        }
+        found.iter().filter(|&&f| f).count()
+    }
+}
+
+/// Recovery counts for one parser in one dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecoveryStat {
+    /// Scripts parsed.
+    pub scripts: usize,
+    /// Scripts from which no valid statement was returned.
+    pub gave_up: usize,
+    /// Valid statements returned, over all scripts.
+    pub recovered: usize,
+    /// Valid statements in all scripts.
+    pub expected: usize,
+}
+
+/// Score `parser` on scripts of `accepted` statements with one of `invalid`
+/// planted in each.
+///
+/// `accepted` are reference-valid statements the parser accepts, `invalid`
+/// reference-invalid ones. `None` if the parser has no recovering entry
+/// point, and `scripts` is zero if `invalid` holds nothing the parser rejects.
+/// Recovery runs under `catch_unwind`: a panic scores as giving up.
+#[must_use]
+pub fn evaluate(
+    parser: &dyn Parser,
+    dialect: Dialect,
+    accepted: &[&str],
+    invalid: &[&str],
+) -> Option<RecoveryStat> {
+    let recover = |sql: &str| {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            parser.recover(sql, dialect)
+        }))
+        .unwrap_or_else(|_| Some(Vec::new()))
+    };
+    recover("SELECT 1")?;
+    let valid: Vec<&str> = accepted
+        .iter()
+        .copied()
+        .filter(|s| batch_eligible(s))
+        .collect();
+    let bad: Vec<&str> = invalid
+        .iter()
+        .copied()
+        .filter(|s| batch_eligible(s) && parser.accepts(s, dialect) == Some(false))
+        .collect();
+    let label = format!("recovery/{}/{}", parser.id().family, dialect.dir_name());
+    let batches = sample_batches(valid.len(), RECOVERY_M - 1, RECOVERY_K, seed_for(&label));
+    let plants = sample_batches(bad.len(), 1, RECOVERY_K, seed_for(&format!("{label}/bad")));
+    let mut stat = RecoveryStat::default();
+    for (idxs, plant) in batches.iter().zip(&plants) {
+        let stmts: Vec<&str> = idxs.iter().map(|&i| valid[i]).collect();
+        // Where to plant: derived from the sample so it is reproducible too.
+        let script = Script::new(&stmts, bad[plant[0]], idxs[0] % RECOVERY_M);
+        let got = script.score(&recover(&script.sql).unwrap_or_default());
+        stat.scripts += 1;
+        stat.gave_up += usize::from(got == 0);
+        stat.recovered += got;
+        stat.expected += script.expected();
+    }
+    Some(stat)
+}
+
+/// One parser's recovery in one dialect. `stat` is `None` for a parser that
+/// has no recovering entry point.
+pub struct ParserRecovery {
+    pub parser: ParserId,
+    pub stat: Option<RecoveryStat>,
+}
+
+/// The recovery axis for `dialect` over the parsers that model it, one
+/// [`WORKER_STACK`] thread per parser.
+///
+/// Empty for a dialect without a corpus or a reference engine (there is no
+/// invalid set to plant from).
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while scoring.
+#[allow(clippy::needless_collect)] // handles must all spawn before any join
+#[must_use]
+pub fn recovery_dialect(dialect: Dialect, all_parsers: &[&dyn Parser]) -> Vec<ParserRecovery> {
+    if !has_reference(dialect) {
+        return Vec::new();
+    }
+    let stmts = report::load_dialect(dialect);
+    let (mut valid, mut invalid) = (Vec::new(), Vec::new());
+    for s in &stmts {
+        match reference_accepts(s, dialect) {
+            Some(true) => valid.push(s.as_str()),
+            Some(false) => invalid.push(s.as_str()),
+            None => {}
+        }
+    }
+    if valid.is_empty() || invalid.is_empty() {
+        return Vec::new();
+    }
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = all_parsers
+            .iter()
+            .filter(|p| p.supports(dialect))
+            .map(|&p| {
+                let (valid, invalid) = (&valid, &invalid);
+                std::thread::Builder::new()
+                    .stack_size(WORKER_STACK)
+                    .spawn_scoped(scope, move || {
+                        let accepted: Vec<&str> = valid
+                            .iter()
+                            .copied()
+                            .filter(|s| p.accepts(s, dialect) == Some(true))
+                            .collect();
+                        ParserRecovery {
+                            parser: p.id(),
+                            stat: evaluate(p, dialect, &accepted, invalid),
+                        }
+                    })
+                    .expect("spawn worker")
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("recovery thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, Script};
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    #[test]
+    fn only_valid_statements_inside_their_own_bounds_are_recovered() {
+        let s = Script::new(&["SELECT 1", "SELECT 2"], "SELEC oops", 1);
+        assert_eq!(s.sql, "SELECT 1\n;\nSELEC oops\n;\nSELECT 2");
+        assert_eq!(s.expected(), 2);
+        // Both valid statements, terminator included or not.
+        assert_eq!(s.score(&[0..10, 24..32]), 2);
+        // The planted statement does not count, nor a range starting in it.
+        assert_eq!(s.score(&[11..21, 11..32]), 0);
+        // A range running past its statement's terminator merges two, and an
+        // empty range is ignored.
+        assert_eq!(s.score(&[0..21, 24..24]), 0);
+        assert_eq!(s.score(&[]), 0);
+    }
+
+    #[test]
+    fn parsers_without_recovery_sit_out() {
+        let ok = ["SELECT 1", "SELECT 2", "SELECT 3"];
+        let bad = ["SELEC oops"];
+        assert!(evaluate(&BenchParser::PgQuery, Dialect::Postgresql, &ok, &bad).is_none());
+        let stat = evaluate(&BenchParser::Sqlparser, Dialect::Postgresql, &ok, &bad)
+            .expect("sqlparser-rs recovers");
+        assert!(stat.scripts > 0);
+        assert_eq!(stat.recovered, stat.expected);
+        assert_eq!(stat.gave_up, 0);
+    }
+}
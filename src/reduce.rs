@@ -0,0 +1,246 @@
+//! Test-case reduction for failure reports.
+//!
+//! A rejected corpus statement can run to hundreds of tokens, most of them
+//! beside the point. [`minimize_failure`] shrinks it with delta debugging
+//! ([`ddmin`], Zeller and Hildebrandt's algorithm over [`lex`] tokens) while
+//! the failure still holds:
+//!
+//! - a rejection must stay "reference accepts, parser rejects". Validity is
+//!   read from the engine cache ([`crate::oracle_cache`]) where the candidate
+//!   happens to be a corpus statement, and from the dialect's reference parser
+//!   ([`norm::reference_parser`]) as a local stand-in otherwise. Dialects
+//!   without a stand-in are not reduced, since nothing would stop the reducer
+//!   from shrinking a valid statement into an invalid one.
+//! - a panic must stay a panic with the same message ([`fuzz::signature`]),
+//!   which needs no validity check and so works in every dialect.
+//!
+//! The same reducer shrinks the fuzzer's crashers ([`crate::fuzz`]).
+
+use crate::datasets::Dialect;
+use crate::{fuzz, norm, reference_accepts, ParseOutcome, Parser};
+use std::ops::Range;
+
+/// Test runs one reduction may spend. Reduction stops early with what it has.
+pub const REDUCE_BUDGET: usize = 500;
+
+/// `sql` as tokens: words, numbers, quoted strings and identifiers, and single
+/// punctuation characters. Whitespace is dropped.
+#[must_use]
+pub fn lex(sql: &str) -> Vec<&str> {
+    lex_spans(sql).into_iter().map(|r| &sql[r]).collect()
+}
+
+/// The byte range of each [`lex`] token in `sql`.
+#[must_use]
+fn lex_spans(sql: &str) -> Vec<Range<usize>> {
+    let b = sql.as_bytes();
+    let word = |x: u8| x.is_ascii_alphanumeric() || x == b'_' || !x.is_ascii();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        let start = i;
+        let c = b[i];
+        i += 1;
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        if matches!(c, b'\'' | b'"' | b'`') {
+            while i < b.len() && b[i] != c {
+                i += 1;
+            }
+            i = (i + 1).min(b.len());
+        } else if word(c) {
+            while i < b.len() && word(b[i]) {
+                i += 1;
+            }
+        }
+        // A split inside a multi-byte character can only follow a non-ASCII
+        // byte, which `word` already swallows.
+        out.push(start..i);
+    }
+    out
+}
+
+/// A 1-minimal subsequence of `items` on which `fails` still holds, within
+/// `budget` calls to `fails`.
+///
+/// Classic ddmin: split into `n` chunks, keep any one chunk or any complement
+/// that still fails, otherwise double `n`. `fails` is assumed true of
+/// `items` and is never asked about the empty sequence.
+#[must_use]
+pub fn ddmin<T: Clone>(items: &[T], budget: usize, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut cur = items.to_vec();
+    let mut left = budget;
+    let mut n = 2;
+    while cur.len() >= 2 && left > 0 {
+        let size = cur.len().div_ceil(n);
+        let chunks: Vec<(usize, usize)> = (0..cur.len())
+            .step_by(size)
+            .map(|s| (s, (s + size).min(cur.len())))
+            .collect();
+        let mut next = None;
+        // Subsets first, then complements (skipped at n = 2, where a
+        // complement is the other subset).
+        for &(s, e) in &chunks {
+            if left == 0 {
+                break;
+            }
+            left -= 1;
+            if fails(&cur[s..e]) {
+                next = Some((cur[s..e].to_vec(), 2));
+                break;
+            }
+        }
+        if next.is_none() && chunks.len() > 2 {
+            for &(s, e) in &chunks {
+                if left == 0 {
+                    break;
+                }
+                let complement = [&cur[..s], &cur[e..]].concat();
+                left -= 1;
+                if fails(&complement) {
+                    next = Some((complement, (n - 1).max(2)));
+                    break;
+                }
+            }
+        }
+        match next {
+            Some((c, k)) => {
+                cur = c;
+                n = k;
+            }
+            None if n >= cur.len() => break,
+            None => n = (n * 2).min(cur.len()),
+        }
+    }
+    cur
+}
+
+/// [`ddmin`] over the tokens of `sql`.
+///
+/// A candidate is cut from the original text, so tokens that were adjacent
+/// stay adjacent (`<=`, `::`, `1.5`) and keep the whitespace between them. A
+/// single space stands in for a removed run of tokens, which keeps the tokens
+/// on either side apart.
+#[must_use]
+pub fn reduce_sql(sql: &str, budget: usize, mut fails: impl FnMut(&str) -> bool) -> String {
+    let spans = lex_spans(sql);
+    let kept: Vec<usize> = (0..spans.len()).collect();
+    let cut = |idxs: &[usize]| {
+        let mut out = String::new();
+        for (k, &i) in idxs.iter().enumerate() {
+            if k > 0 {
+                let prev = idxs[k - 1];
+                if prev + 1 == i {
+                    out.push_str(&sql[spans[prev].end..spans[i].start]);
+                } else {
+                    out.push(' ');
+                }
+            }
+            out.push_str(&sql[spans[i].clone()]);
+        }
+        out
+    };
+    cut(&ddmin(&kept, budget, |idxs| fails(&cut(idxs))))
+}
+
+/// Whether `sql` is valid in `dialect`: the engine cache where it has the
+/// statement, else the dialect's reference parser. `None` without either.
+#[must_use]
+pub fn reference_valid(sql: &str, dialect: Dialect) -> Option<bool> {
+    reference_accepts(sql, dialect)
+        .or_else(|| norm::reference_parser(dialect).and_then(|p| p.accepts(sql, dialect)))
+}
+
+/// A shorter statement that fails `parser` the same way `sql` does.
+///
+/// `None` if `sql` does not fail, the failure cannot be checked (a rejection
+/// in a dialect without a local reference, or by the reference parser
+/// itself), or nothing could be removed.
+#[must_use]
+pub fn minimize_failure(parser: &dyn Parser, sql: &str, dialect: Dialect) -> Option<String> {
+    let min = match parser.parse_outcome(sql, dialect) {
+        ParseOutcome::Panicked(message) => {
+            let sig = fuzz::signature(&message);
+            reduce_sql(sql, REDUCE_BUDGET, |s| {
+                matches!(parser.parse_outcome(s, dialect),
+                    ParseOutcome::Panicked(m) if fuzz::signature(&m) == sig)
+            })
+        }
+        ParseOutcome::Rejected(_) => {
+            let stand_in = norm::reference_parser(dialect)?;
+            if stand_in.name() == parser.id().family {
+                return None;
+            }
+            reduce_sql(sql, REDUCE_BUDGET, |s| {
+                parser.accepts(s, dialect) == Some(false)
+                    && reference_valid(s, dialect) == Some(true)
+            })
+        }
+        ParseOutcome::Accepted | ParseOutcome::Unsupported => return None,
+    };
+    (lex(&min).len() < lex(sql).len()).then_some(min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ddmin, lex, minimize_failure, reduce_sql};
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    #[test]
+    fn lex_keeps_words_strings_and_punctuation_apart() {
+        assert_eq!(
+            lex("SELECT a,'x y' FROM\tt1;"),
+            ["SELECT", "a", ",", "'x y'", "FROM", "t1", ";"]
+        );
+    }
+
+    #[test]
+    fn ddmin_keeps_only_what_the_failure_needs() {
+        let items: Vec<u32> = (0..40).collect();
+        let min = ddmin(&items, 1_000, |c| c.contains(&7) && c.contains(&31));
+        assert_eq!(min, [7, 31]);
+        let min = reduce_sql("SELECT a , b FROM t WHERE BOOM ( 1 )", 1_000, |s| {
+            s.contains("BOOM")
+        });
+        assert_eq!(min, "BOOM");
+    }
+
+    #[test]
+    fn reduction_cuts_the_original_text() {
+        // Multi-character operators and numbers survive, and a removed run
+        // leaves one space so its neighbours stay separate tokens.
+        let sql = "SELECT a::int, 1.5 FROM t WHERE b <= 2";
+        let min = reduce_sql(sql, 1_000, |s| s.contains("::") && s.contains("<="));
+        assert_eq!(min, ":: <=");
+        let min = reduce_sql(sql, 1_000, |s| s.contains("1.5") && s.contains("FROM"));
+        assert_eq!(min, "1.5 FROM");
+    }
+
+    #[test]
+    fn ddmin_stops_at_the_budget() {
+        let items: Vec<u32> = (0..64).collect();
+        let mut calls = 0;
+        let _ = ddmin(&items, 5, |c| {
+            calls += 1;
+            c.contains(&0)
+        });
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn rejections_reduce_only_against_a_reference() {
+        // pg_query.rs is PostgreSQL's own reference, and MySQL has no local
+        // stand-in: neither rejection can be checked while shrinking.
+        let sql = "SELEC 1 FROM t WHERE a = 1";
+        assert_eq!(
+            minimize_failure(&BenchParser::PgQuery, sql, Dialect::Postgresql),
+            None
+        );
+        assert_eq!(
+            minimize_failure(&BenchParser::Sqlparser, sql, Dialect::Mysql),
+            None
+        );
+    }
+}
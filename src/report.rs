@@ -4,12 +4,27 @@
 //!
 //! Shared by the `sqlbench` tool and unit-tested here. `grade_chunk` is the
 //! correctness core: it splits a dialect's statements by reference verdict (where
-//! one exists) and tallies per parser recall, false-positive, and round-trip. It
-//! is deterministic, so callers may chunk the corpus and `merge` partial reports
-//! for speed.
+//! one exists) and tallies per parser recall, false-positive, round-trip,
+//! agreement with the reference parser's tree, how closely rejections locate
+//! the error, whether AST spans point at the right text, and recall per SQL
+//! construct ([`crate::constructs`]) and statement kind ([`crate::stmt_kind`]),
+//! against the pinned engine or, with [`grade_chunk_at`], one labeled engine
+//! release.
+//! It is deterministic, so callers may chunk
+//! the corpus and `merge` partial reports for speed. [`grade_dialect`] adds the statement-splitter axis
+//! ([`crate::split`]), which needs each parser's whole accepted set, and
+//! [`failures_dialect`] attaches a minimized reproducer ([`crate::reduce`]) to
+//! the first rejections of each parser and groups all of them into clusters
+//! by normalized error ([`cluster_failures`]). [`disagreements`] lists the
+//! statements one parser of a pair accepts and the other rejects.
 
 use crate::datasets::Dialect;
-use crate::{contentious, has_reference, reference_accepts, Parser, ParserId};
+use crate::{
+    constructs, contentious, diagnostics, edit, has_reference, norm,
+    oracle_cache::{self, EngineClass},
+    reduce, reference_accepts, split, stmt_kind, Parser, ParserId,
+};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -42,16 +57,77 @@ pub struct ParserStat {
     /// Statements on which the parser threw a caught panic instead of returning a
     /// result (the empirical panic-rate numerator).
     pub panicked: usize,
+    /// Accepted-valid statements whose tree was compared with the reference
+    /// parser's (the AST-agreement denominator). Zero where the dialect has no
+    /// reference tree, for the reference parser itself, and for parsers that
+    /// expose no tree. Statements where either tree is opaque
+    /// ([`norm::NormTree::is_opaque`]) are not compared.
+    pub ast_compared: usize,
+    /// Compared statements whose normalized tree agrees with the reference's.
+    pub ast_agreed: usize,
+    /// Reference-invalid statements the parser rejected with an error (not a
+    /// panic), the error-location denominator. Reference dialects only.
+    pub rejected_invalid: usize,
+    /// Rejections whose error carried a location.
+    pub error_located: usize,
+    /// Located rejections scored against the reference parser's own error
+    /// location (the position-accuracy denominator). Zero for the reference
+    /// parser itself and where the reference reported no location.
+    pub error_scored: usize,
+    /// Scored rejections within [`diagnostics::POSITION_TOLERANCE`] bytes of the
+    /// reference's position.
+    pub error_near: usize,
+    /// Identifier and literal spans collected from the parser's trees of
+    /// accepted-valid statements (the span-accuracy denominator). Zero for
+    /// parsers that attach no spans.
+    pub spans_checked: usize,
+    /// Checked spans that slice back to their token in the statement.
+    pub spans_correct: usize,
+    /// Statement-boundary counts from the splitter axis. Filled once per
+    /// dialect by [`grade_dialect`] (not per chunk), and `None` for parsers
+    /// that do not report where their statements are.
+    pub split: Option<split::SplitStat>,
+    /// Accepted-valid statements using each construct, aligned with
+    /// [`constructs::registry`] (the per-construct recall numerators).
+    pub construct_accepted: Vec<usize>,
+    /// Accepted-valid statements of each kind, aligned with
+    /// [`stmt_kind::StatementKind::ALL`] (the per-kind recall numerators).
+    pub kind_accepted: Vec<usize>,
+}
+
+/// Add `other` into `acc` elementwise, growing `acc` to fit.
+fn add_counts(acc: &mut Vec<usize>, other: &[usize]) {
+    if acc.len() < other.len() {
+        acc.resize(other.len(), 0);
+    }
+    for (a, b) in acc.iter_mut().zip(other) {
+        *a += b;
+    }
 }
 
 impl ParserStat {
-    const fn merge(&mut self, other: &Self) {
+    fn merge(&mut self, other: &Self) {
         self.accepted_valid += other.accepted_valid;
         self.accepted_valid_contentious += other.accepted_valid_contentious;
         self.accepted_invalid += other.accepted_invalid;
         self.roundtrip_ok += other.roundtrip_ok;
         self.attempted += other.attempted;
         self.panicked += other.panicked;
+        self.ast_compared += other.ast_compared;
+        self.ast_agreed += other.ast_agreed;
+        self.rejected_invalid += other.rejected_invalid;
+        self.error_located += other.error_located;
+        self.error_scored += other.error_scored;
+        self.error_near += other.error_near;
+        self.spans_checked += other.spans_checked;
+        self.spans_correct += other.spans_correct;
+        add_counts(&mut self.construct_accepted, &other.construct_accepted);
+        add_counts(&mut self.kind_accepted, &other.kind_accepted);
+        match (&mut self.split, &other.split) {
+            (Some(a), Some(b)) => a.merge(b),
+            (None, Some(b)) => self.split = Some(*b),
+            _ => {}
+        }
     }
 }
 
@@ -65,26 +141,53 @@ pub struct DialectReport {
     /// provenance dialects.
     pub contentious_valid: usize,
     pub invalid_total: usize,
+    /// Reference dialects graded against the pinned engine: statements the
+    /// engine classed as an unsupported feature
+    /// ([`oracle_cache::EngineClass::Unsupported`]).
+    pub unsupported: usize,
+    /// Whether the `unsupported` statements were left out of every metric,
+    /// since the engine did not say whether they are valid SQL. Otherwise they
+    /// are graded by their valid bit like any other statement.
+    pub exclude_unsupported: bool,
+    /// Valid statements using each construct, aligned with
+    /// [`constructs::registry`] (the per-construct recall denominators). A
+    /// statement counts once under every construct it uses.
+    pub construct_valid: Vec<usize>,
+    /// Valid statements of each kind, aligned with
+    /// [`stmt_kind::StatementKind::ALL`] (the per-kind recall denominators).
+    pub kind_valid: Vec<usize>,
     /// Identity (family + version) of each graded parser, aligned with `stats`.
     pub parsers: Vec<ParserId>,
     pub stats: Vec<ParserStat>,
+    /// The engine release valid/invalid was read from
+    /// ([`oracle_cache::engine_versions`]), `None` for the pinned engine.
+    pub target_version: Option<String>,
 }
 
 impl DialectReport {
     /// Zeroed report with `can_reprint` precomputed per parser.
     #[must_use]
     pub fn empty(dialect: Dialect, parsers: &[&dyn Parser]) -> Self {
+        let n_constructs = constructs::registry().constructs.len();
+        let n_kinds = stmt_kind::StatementKind::ALL.len();
         Self {
             dialect,
             has_reference: has_reference(dialect),
             valid_total: 0,
             contentious_valid: 0,
             invalid_total: 0,
+            unsupported: 0,
+            exclude_unsupported: false,
+            construct_valid: vec![0; n_constructs],
+            kind_valid: vec![0; n_kinds],
             parsers: parsers.iter().map(|p| p.id()).collect(),
+            target_version: None,
             stats: parsers
                 .iter()
                 .map(|p| ParserStat {
                     can_reprint: p.can_reprint(dialect),
+                    construct_accepted: vec![0; n_constructs],
+                    kind_accepted: vec![0; n_kinds],
                     ..ParserStat::default()
                 })
                 .collect(),
@@ -96,26 +199,86 @@ impl DialectReport {
         self.valid_total += other.valid_total;
         self.contentious_valid += other.contentious_valid;
         self.invalid_total += other.invalid_total;
+        self.unsupported += other.unsupported;
+        add_counts(&mut self.construct_valid, &other.construct_valid);
+        add_counts(&mut self.kind_valid, &other.kind_valid);
         for (a, b) in self.stats.iter_mut().zip(other.stats.iter()) {
             a.merge(b);
         }
     }
 }
 
-/// Grade a chunk of statements for one dialect. Reference dialects (PostgreSQL,
-/// SQLite) split valid/invalid by the reference, while provenance dialects treat
-/// every statement as valid.
+/// Grade a chunk of statements for one dialect.
+///
+/// Reference dialects (PostgreSQL, SQLite) split valid/invalid by the
+/// reference, while provenance dialects treat every statement as valid.
 #[must_use]
 pub fn grade_chunk(stmts: &[String], dialect: Dialect, parsers: &[&dyn Parser]) -> DialectReport {
+    grade_chunk_at(stmts, dialect, parsers, None, false)
+}
+
+/// Whether the reference engine classed `sql` as an unsupported feature, so
+/// it never said whether the statement is valid.
+fn engine_unsupported(sql: &str, dialect: Dialect) -> bool {
+    oracle_cache::reference_class(sql, dialect) == Some(EngineClass::Unsupported)
+}
+
+/// The reference verdict on `sql`, from engine release `target` if set. `None`
+/// for a coverage miss and, with `report.exclude_unsupported`, for a statement
+/// the pinned engine classed as unsupported. Those are counted on `report`.
+/// Releases keep no class, so a `target` verdict is the release's valid bit.
+fn verdict(sql: &str, report: &mut DialectReport, target: Option<&str>) -> Option<bool> {
+    let dialect = report.dialect;
+    if let Some(v) = target {
+        return oracle_cache::reference_accepts_at(sql, dialect, v);
+    }
+    if engine_unsupported(sql, dialect) {
+        report.unsupported += 1;
+        if report.exclude_unsupported {
+            return None;
+        }
+    }
+    reference_accepts(sql, dialect)
+}
+
+/// As [`grade_chunk`], with valid/invalid read from engine release `target`.
+///
+/// `target` is one of [`oracle_cache::engine_versions`]; `None` grades against
+/// the pinned engine. A statement the release was not labeled on is skipped
+/// like a coverage miss. `exclude_unsupported` sets
+/// [`DialectReport::exclude_unsupported`] (pinned engine only).
+#[must_use]
+pub fn grade_chunk_at(
+    stmts: &[String],
+    dialect: Dialect,
+    parsers: &[&dyn Parser],
+    target: Option<&str>,
+    exclude_unsupported: bool,
+) -> DialectReport {
+    grade_chunk_accepting(stmts, dialect, parsers, target, exclude_unsupported).0
+}
+
+/// As [`grade_chunk_at`], also returning which statements each parser
+/// accepted (aligned with `parsers`, then with `stmts`), so the splitter axis
+/// draws its scripts from the same verdicts the report counts.
+#[allow(clippy::too_many_lines)]
+fn grade_chunk_accepting(
+    stmts: &[String],
+    dialect: Dialect,
+    parsers: &[&dyn Parser],
+    target: Option<&str>,
+    exclude_unsupported: bool,
+) -> (DialectReport, Vec<Vec<bool>>) {
     let reference = has_reference(dialect);
     let mut report = DialectReport::empty(dialect, parsers);
-
-    for sql in stmts {
+    report.exclude_unsupported = exclude_unsupported;
+    let mut accepted = vec![vec![false; stmts.len()]; parsers.len()];
+    for (n, sql) in stmts.iter().enumerate() {
         let is_valid = if reference {
-            match reference_accepts(sql, dialect) {
+            match verdict(sql, &mut report, target) {
                 Some(v) => v,
-                // Coverage miss: the cache does not cover this statement (stale
-                // cache). Skip it rather than mislabel it invalid.
+                // Coverage miss (a stale cache) or an excluded unsupported
+                // feature. Skip it rather than mislabel it invalid.
                 None => continue,
             }
         } else {
@@ -134,6 +297,33 @@ pub fn grade_chunk(stmts: &[String], dialect: Dialect, parsers: &[&dyn Parser])
         if is_contentious {
             report.contentious_valid += 1;
         }
+        // The constructs a valid statement uses, for per-construct recall.
+        let tags = if is_valid {
+            constructs::registry().tags(sql, dialect)
+        } else {
+            Vec::new()
+        };
+        for &t in &tags {
+            report.construct_valid[t] += 1;
+        }
+        let kind = stmt_kind::classify(sql).index();
+        if is_valid {
+            report.kind_valid[kind] += 1;
+        }
+        // The reference parser's normalized tree, shared by every parser that
+        // accepts the statement (PostgreSQL and SQLite only).
+        let reference_tree = if reference && is_valid {
+            norm::reference_tree(sql, dialect).filter(|t| !t.is_opaque())
+        } else {
+            None
+        };
+        // Where the reference parser places the error in an invalid statement,
+        // the target each parser's reported position is scored against.
+        let reference_error = if reference && !is_valid {
+            diagnostics::reference_error_offset(sql, dialect)
+        } else {
+            None
+        };
 
         for (i, &p) in parsers.iter().enumerate() {
             // A panic is still a non-acceptance (it does not enter the accepted
@@ -145,26 +335,74 @@ pub fn grade_chunk(stmts: &[String], dialect: Dialect, parsers: &[&dyn Parser])
                     report.stats[i].panicked += 1;
                     continue;
                 }
-                crate::ParseOutcome::Rejected(_) => {
+                crate::ParseOutcome::Rejected(e) => {
                     report.stats[i].attempted += 1;
+                    if reference && !is_valid {
+                        score_error_position(
+                            &mut report.stats[i],
+                            e.offset(sql),
+                            reference_error.filter(|_| !is_reference_parser(p, dialect)),
+                        );
+                    }
                     continue;
                 }
                 crate::ParseOutcome::Accepted => report.stats[i].attempted += 1,
             }
+            accepted[i][n] = true;
             if is_valid {
                 report.stats[i].accepted_valid += 1;
                 if is_contentious {
                     report.stats[i].accepted_valid_contentious += 1;
                 }
+                for &t in &tags {
+                    report.stats[i].construct_accepted[t] += 1;
+                }
+                report.stats[i].kind_accepted[kind] += 1;
                 if report.stats[i].can_reprint && p.roundtrips(sql, dialect) == Some(true) {
                     report.stats[i].roundtrip_ok += 1;
                 }
+                if let Some(theirs) = reference_tree
+                    .as_ref()
+                    .filter(|_| !is_reference_parser(p, dialect))
+                {
+                    if let Some(ours) = p.normalized(sql, dialect).filter(|t| !t.is_opaque()) {
+                        report.stats[i].ast_compared += 1;
+                        if ours.agrees(theirs) {
+                            report.stats[i].ast_agreed += 1;
+                        }
+                    }
+                }
+                if let Some(spans) = p.spans(sql, dialect) {
+                    report.stats[i].spans_checked += spans.len();
+                    report.stats[i].spans_correct +=
+                        spans.iter().filter(|s| s.slices_back(sql)).count();
+                }
             } else {
                 report.stats[i].accepted_invalid += 1;
             }
         }
     }
-    report
+    (report, accepted)
+}
+
+/// Tally one rejection of an invalid statement: whether the parser located the
+/// error and, when the reference located it too, whether the two agree.
+const fn score_error_position(stat: &mut ParserStat, ours: Option<usize>, theirs: Option<usize>) {
+    stat.rejected_invalid += 1;
+    let Some(ours) = ours else { return };
+    stat.error_located += 1;
+    if let Some(theirs) = theirs {
+        stat.error_scored += 1;
+        if ours.abs_diff(theirs) <= diagnostics::POSITION_TOLERANCE {
+            stat.error_near += 1;
+        }
+    }
+}
+
+/// Whether `p` is the parser whose tree `dialect` is compared against, which
+/// would trivially agree with itself.
+fn is_reference_parser(p: &dyn Parser, dialect: Dialect) -> bool {
+    norm::reference_parser(dialect).is_some_and(|r| p.id().family == r.name())
 }
 
 /// Number of statements `parser` accepts in `dialect` (per-file coverage).
@@ -184,6 +422,22 @@ pub fn count_accepted(stmts: &[&str], dialect: Dialect, parser: &dyn Parser) ->
 /// Panics if a worker thread cannot be spawned or panics while grading.
 #[must_use]
 pub fn grade_dialect(dialect: Dialect, all_parsers: &[&dyn Parser]) -> Option<DialectReport> {
+    grade_dialect_at(dialect, all_parsers, None, false)
+}
+
+/// As [`grade_dialect`], graded against engine release `target` and with
+/// `exclude_unsupported` (see [`grade_chunk_at`]). Used by `sqlbench
+/// correctness --target` and `--exclude-unsupported`.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while grading.
+#[must_use]
+pub fn grade_dialect_at(
+    dialect: Dialect,
+    all_parsers: &[&dyn Parser],
+    target: Option<&str>,
+    exclude_unsupported: bool,
+) -> Option<DialectReport> {
     let stmts = load_dialect(dialect);
     if stmts.is_empty() {
         return None;
@@ -199,26 +453,83 @@ pub fn grade_dialect(dialect: Dialect, all_parsers: &[&dyn Parser]) -> Option<Di
         .min(32);
     let chunk = stmts.len().div_ceil(n_threads).max(1);
 
-    let merged = std::thread::scope(|scope| {
+    let (mut merged, accepted) = std::thread::scope(|scope| {
         let handles: Vec<_> = stmts
             .chunks(chunk)
             .map(|c| {
                 let parsers = &parsers;
                 std::thread::Builder::new()
                     .stack_size(WORKER_STACK)
-                    .spawn_scoped(scope, move || grade_chunk(c, dialect, parsers))
+                    .spawn_scoped(scope, move || {
+                        grade_chunk_accepting(c, dialect, parsers, target, exclude_unsupported)
+                    })
                     .expect("spawn worker")
             })
             .collect();
         let mut acc = DialectReport::empty(dialect, &parsers);
+        acc.target_version = target.map(str::to_string);
+        acc.exclude_unsupported = exclude_unsupported;
+        let mut accepted: Vec<Vec<bool>> = vec![Vec::with_capacity(stmts.len()); parsers.len()];
         for h in handles {
-            acc.merge(&h.join().expect("grade thread panicked"));
+            let (report, flags) = h.join().expect("grade thread panicked");
+            acc.merge(&report);
+            for (all, chunk) in accepted.iter_mut().zip(flags) {
+                all.extend(chunk);
+            }
         }
-        acc
+        (acc, accepted)
     });
+    for (stat, split) in merged
+        .stats
+        .iter_mut()
+        .zip(split_dialect(dialect, &stmts, &parsers, &accepted))
+    {
+        stat.split = split;
+    }
     Some(merged)
 }
 
+/// The splitter axis for each of `parsers` (aligned), one [`WORKER_STACK`]
+/// thread per parser that reports statement positions. `accepted` holds the
+/// grading verdicts (aligned with `parsers`, then with `stmts`), so a statement
+/// left out of grading is left out of the scripts too.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while splitting.
+#[allow(clippy::needless_collect)] // handles must all spawn before any join
+fn split_dialect(
+    dialect: Dialect,
+    stmts: &[String],
+    parsers: &[&dyn Parser],
+    accepted: &[Vec<bool>],
+) -> Vec<Option<split::SplitStat>> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = parsers
+            .iter()
+            .zip(accepted)
+            .map(|(&p, flags)| {
+                std::thread::Builder::new()
+                    .stack_size(WORKER_STACK)
+                    .spawn_scoped(scope, move || {
+                        p.split("SELECT 1", dialect)?;
+                        let accepted: Vec<&str> = stmts
+                            .iter()
+                            .zip(flags)
+                            .filter(|(_, &ok)| ok)
+                            .map(|(s, _)| s.as_str())
+                            .collect();
+                        split::evaluate(p, dialect, &accepted)
+                    })
+                    .expect("spawn worker")
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("split thread panicked"))
+            .collect()
+    })
+}
+
 /// One dataset file's acceptance counts, aligned to a parser column order.
 pub struct FileCoverage {
     pub name: String,
@@ -278,6 +589,14 @@ pub fn coverage_dialect(
     (ids, stats)
 }
 
+/// Rejected statements per (parser, dialect) that get a minimized reproducer.
+///
+/// Each reduction costs up to [`reduce::REDUCE_BUDGET`] parses, so this bounds
+/// the export's reduction work at 12,500 parses per (parser, dialect). That
+/// covers the previewed failures with room to spare, and the long tail of a
+/// parser with thousands of failures is left as is.
+pub const MINIMIZE_CAP: usize = 25;
+
 /// The statements one parser rejected in one dialect, with the corpus total.
 pub struct ParserFailures {
     pub parser: ParserId,
@@ -286,16 +605,175 @@ pub struct ParserFailures {
     /// The parser's error message for each rejected statement, aligned with
     /// `rejected` (same length, same order).
     pub reasons: Vec<String>,
+    /// A minimized reproducer for each of the first [`MINIMIZE_CAP`] rejected
+    /// statements ([`reduce::minimize_failure`]), `None` where the failure
+    /// could not be reduced. Shorter than `rejected` past the cap.
+    pub minimized: Vec<Option<String>>,
+    /// The largest groups of rejections sharing an [`ErrorSignature`], at most
+    /// [`CLUSTER_TOP`], biggest first ([`cluster_failures`]).
+    pub clusters: Vec<FailureCluster>,
     /// Total statements graded for the dialect (denominator for the count).
     pub total: usize,
 }
 
+/// Failure clusters kept per (parser, dialect).
+pub const CLUSTER_TOP: usize = 20;
+
+/// What two rejections must share to be the same failure.
+///
+/// `message` is the parser's error with what varies by statement blurred:
+/// first line only, every digit run (positions, line and column numbers) as
+/// `#`, and every word that is an identifier in the statement as `?`.
+/// Keywords survive, upper-cased where the statement uses them, so "found:
+/// window" and "found: WINDOW" are one failure.
+///
+/// `head` is the run of keywords the statement starts with after
+/// [`contentious::mask`] (`SELECT`, `CREATE TABLE`, `WITH RECURSIVE`), so the
+/// same message from a `SELECT` and an `ALTER` stays apart. `shape` is the
+/// masked statement's first [`SHAPE_LEN`] tokens with keywords upper-cased
+/// and each run of anything else (identifiers, numbers, punctuation) as one
+/// `?`: `SELECT ? FROM ? WHERE ?`, so a column list or a literal's length
+/// does not split a cluster but a different clause layout does.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorSignature {
+    pub message: String,
+    pub head: String,
+    pub shape: String,
+}
+
+/// Entries in an [`ErrorSignature`]'s `shape`.
+pub const SHAPE_LEN: usize = 8;
+
+impl ErrorSignature {
+    /// The signature of `reason`, the error a parser gave for `sql`.
+    #[must_use]
+    pub fn new(sql: &str, reason: &str) -> Self {
+        let masked = contentious::mask(sql);
+        let toks = reduce::lex(&masked);
+        let (mut idents, mut keywords) = (HashSet::new(), HashSet::new());
+        for t in &toks {
+            let word = t.trim_matches(|c| matches!(c, '"' | '`'));
+            if !word.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                continue;
+            }
+            if word.len() == t.len() && edit::is_keyword(word) {
+                keywords.insert(word.to_ascii_uppercase());
+            } else {
+                idents.insert(word);
+            }
+        }
+        let is_keyword =
+            |t: &str| t.starts_with(|c: char| c.is_ascii_alphabetic()) && edit::is_keyword(t);
+        let head: Vec<String> = toks
+            .iter()
+            .take_while(|t| is_keyword(t))
+            .take(3)
+            .map(|t| t.to_ascii_uppercase())
+            .collect();
+        let mut shape: Vec<String> = Vec::new();
+        for t in &toks {
+            if is_keyword(t) {
+                shape.push(t.to_ascii_uppercase());
+            } else if shape.last().is_none_or(|s| s != "?") {
+                shape.push("?".to_string());
+            }
+            if shape.len() > SHAPE_LEN {
+                shape.truncate(SHAPE_LEN);
+                break;
+            }
+        }
+        Self {
+            message: blur_message(reason, &idents, &keywords),
+            head: head.join(" "),
+            shape: shape.join(" "),
+        }
+    }
+}
+
+/// `reason`'s first line with digit runs as `#`, the words in `idents` as `?`
+/// and those in `keywords` upper-cased, at most 160 characters.
+fn blur_message(reason: &str, idents: &HashSet<&str>, keywords: &HashSet<String>) -> String {
+    let line: String = reason
+        .lines()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .take(160)
+        .collect();
+    let mut out = String::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        if word.starts_with(|c: char| c.is_ascii_digit()) {
+            out.push('#');
+        } else if idents.contains(word.as_str()) {
+            out.push('?');
+        } else if keywords.contains(&word.to_ascii_uppercase()) {
+            out.push_str(&word.to_ascii_uppercase());
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in line.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+/// Rejections sharing one [`ErrorSignature`].
+#[derive(Clone, Debug)]
+pub struct FailureCluster {
+    pub signature: ErrorSignature,
+    /// Rejections with this signature.
+    pub count: usize,
+    /// Index into the rejected list of the shortest member, the one shown.
+    pub exemplar: usize,
+}
+
+/// Group `rejected` (with their aligned `reasons`) by [`ErrorSignature`] and
+/// keep the [`CLUSTER_TOP`] largest groups, biggest first.
+///
+/// Ties go to the signature first seen in corpus order, so the result is
+/// stable across runs.
+#[must_use]
+pub fn cluster_failures(rejected: &[String], reasons: &[String]) -> Vec<FailureCluster> {
+    let mut by_sig: HashMap<ErrorSignature, usize> = HashMap::new();
+    let mut clusters: Vec<FailureCluster> = Vec::new();
+    for (i, (sql, reason)) in rejected.iter().zip(reasons).enumerate() {
+        let signature = ErrorSignature::new(sql, reason);
+        let k = *by_sig.entry(signature.clone()).or_insert(clusters.len());
+        if k == clusters.len() {
+            clusters.push(FailureCluster {
+                signature,
+                count: 0,
+                exemplar: i,
+            });
+        }
+        let c = &mut clusters[k];
+        c.count += 1;
+        if sql.len() < rejected[c.exemplar].len() {
+            c.exemplar = i;
+        }
+    }
+    // Stable sort: equal counts keep first-seen order.
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.count));
+    clusters.truncate(CLUSTER_TOP);
+    clusters
+}
+
 /// For each parser that supports `dialect`, collect the statements it rejected.
 ///
 /// These are the actionable "should parse but did not" cases a parser author
 /// would want to fix. Reference-invalid statements are excluded so the set stays
 /// meaningful: only statements the parser ought to accept (reference-valid, or
-/// provenance-valid where there is no reference).
+/// provenance-valid where there is no reference). Statements the engine classed
+/// as unsupported are left out, as in grading.
 ///
 /// # Panics
 /// Panics if a worker thread cannot be spawned or panics while grading.
@@ -323,7 +801,10 @@ pub fn failures_dialect_from(
     // Only statements the parser is expected to accept count as failures.
     let expected: Vec<&String> = stmts
         .iter()
-        .filter(|s| !reference || reference_accepts(s, dialect) == Some(true))
+        .filter(|s| {
+            !reference
+                || (reference_accepts(s, dialect) == Some(true) && !engine_unsupported(s, dialect))
+        })
         .collect();
     let total = expected.len();
 
@@ -353,10 +834,18 @@ pub fn failures_dialect_from(
                                 reasons.push(reason);
                             }
                         }
+                        let minimized = rejected
+                            .iter()
+                            .take(MINIMIZE_CAP)
+                            .map(|s| reduce::minimize_failure(p, s, dialect))
+                            .collect();
+                        let clusters = cluster_failures(&rejected, &reasons);
                         ParserFailures {
                             parser: p.id(),
                             rejected,
                             reasons,
+                            minimized,
+                            clusters,
                             total,
                         }
                     })
@@ -370,6 +859,151 @@ pub fn failures_dialect_from(
     })
 }
 
+/// Statements kept per side of a [`Disagreement`].
+pub const DISAGREEMENT_SAMPLES: usize = 10;
+
+/// A statement one parser of a pair accepts and the other rejects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisagreementSample {
+    pub sql: String,
+    /// The reference engine's verdict: `None` on a provenance dialect or where
+    /// the cache does not cover the statement.
+    pub reference: Option<bool>,
+}
+
+/// Where two parsers part ways on one dialect's corpus.
+///
+/// Both sides count every statement, valid or not, so a migration from `a` to
+/// `b` has exactly `only_a` statements that stop parsing and `only_b` that
+/// start to. Statements the engine classed as unsupported are left out, as in
+/// grading.
+#[derive(Clone, Debug)]
+pub struct Disagreement {
+    pub a: ParserId,
+    pub b: ParserId,
+    /// Statements `a` accepts and `b` rejects.
+    pub only_a: usize,
+    /// Statements `b` accepts and `a` rejects.
+    pub only_b: usize,
+    /// Statements both accept.
+    pub both: usize,
+    /// The first [`DISAGREEMENT_SAMPLES`] statements counted in `only_a`, in
+    /// corpus order.
+    pub samples_a: Vec<DisagreementSample>,
+    /// As `samples_a`, for `only_b`.
+    pub samples_b: Vec<DisagreementSample>,
+}
+
+/// Compare `a` and `b` over `dialect`'s corpus. A parser that does not model
+/// the dialect accepts nothing.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while parsing.
+#[must_use]
+pub fn disagreements(dialect: Dialect, a: &dyn Parser, b: &dyn Parser) -> Disagreement {
+    pairwise(&load_dialect(dialect), dialect, &[a, b])
+        .pop()
+        .expect("one pair")
+}
+
+/// [`disagreements`] for every pair of parsers that support `dialect`, in
+/// `all_parsers` order (`a` before `b`). Empty without a corpus. Used by
+/// `sqlbench export`.
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while parsing.
+#[must_use]
+pub fn disagreements_dialect(dialect: Dialect, all_parsers: &[&dyn Parser]) -> Vec<Disagreement> {
+    let stmts = load_dialect(dialect);
+    if stmts.is_empty() {
+        return Vec::new();
+    }
+    let parsers: Vec<&dyn Parser> = all_parsers
+        .iter()
+        .copied()
+        .filter(|p| p.supports(dialect))
+        .collect();
+    pairwise(&stmts, dialect, &parsers)
+}
+
+/// The [`Disagreement`] of every pair in `parsers` over `stmts`, parsing each
+/// statement once per parser (one [`WORKER_STACK`] thread each).
+///
+/// # Panics
+/// Panics if a worker thread cannot be spawned or panics while parsing.
+#[allow(clippy::needless_collect)] // handles must all spawn before any join
+#[must_use]
+pub fn pairwise(stmts: &[String], dialect: Dialect, parsers: &[&dyn Parser]) -> Vec<Disagreement> {
+    let stmts: Vec<&String> = stmts
+        .iter()
+        .filter(|s| !engine_unsupported(s, dialect))
+        .collect();
+    let stmts = &stmts;
+    let accepted: Vec<Vec<bool>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = parsers
+            .iter()
+            .map(|&p| {
+                std::thread::Builder::new()
+                    .stack_size(WORKER_STACK)
+                    .spawn_scoped(scope, move || {
+                        stmts
+                            .iter()
+                            .map(|s| p.accepts(s, dialect) == Some(true))
+                            .collect()
+                    })
+                    .expect("spawn worker")
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("verdict thread panicked"))
+            .collect()
+    });
+    let reference = has_reference(dialect);
+    let sample = |sql: &String| DisagreementSample {
+        sql: sql.clone(),
+        reference: if reference {
+            reference_accepts(sql, dialect)
+        } else {
+            None
+        },
+    };
+    let mut out = Vec::new();
+    for (i, a) in parsers.iter().enumerate() {
+        for (j, b) in parsers.iter().enumerate().skip(i + 1) {
+            let mut d = Disagreement {
+                a: a.id(),
+                b: b.id(),
+                only_a: 0,
+                only_b: 0,
+                both: 0,
+                samples_a: Vec::new(),
+                samples_b: Vec::new(),
+            };
+            for (k, sql) in stmts.iter().enumerate() {
+                match (accepted[i][k], accepted[j][k]) {
+                    (true, true) => d.both += 1,
+                    (true, false) => {
+                        d.only_a += 1;
+                        if d.samples_a.len() < DISAGREEMENT_SAMPLES {
+                            d.samples_a.push(sample(sql));
+                        }
+                    }
+                    (false, true) => {
+                        d.only_b += 1;
+                        if d.samples_b.len() < DISAGREEMENT_SAMPLES {
+                            d.samples_b.push(sample(sql));
+                        }
+                    }
+                    (false, false) => {}
+                }
+            }
+            out.push(d);
+        }
+    }
+    out
+}
+
 /// Acceptance counts for one dataset file (None if unreadable or empty).
 fn eval_file(path: &Path, dialect: Dialect, parsers: &[&dyn Parser]) -> Option<FileCoverage> {
     let name = path.file_name()?.to_string_lossy().into_owned();
@@ -398,6 +1032,17 @@ pub fn load_dialect(dialect: Dialect) -> Vec<String> {
 /// As [`load_dialect`], but from an arbitrary corpus root (for testing).
 #[must_use]
 pub fn load_dialect_from(root: &Path, dialect: Dialect) -> Vec<String> {
+    load_dialect_files_from(root, dialect)
+        .into_iter()
+        .flat_map(|(_, stmts)| stmts)
+        .collect()
+}
+
+/// As [`load_dialect_from`], kept per corpus file: each file's name with its
+/// statements, in file-name order. Used where a statement must be traced back
+/// to its file (the `oracle diff` label audit).
+#[must_use]
+pub fn load_dialect_files_from(root: &Path, dialect: Dialect) -> Vec<(String, Vec<String>)> {
     let dir = root.join(dialect.dir_name());
     let Ok(entries) = fs::read_dir(&dir) else {
         return Vec::new();
@@ -411,12 +1056,15 @@ pub fn load_dialect_from(root: &Path, dialect: Dialect) -> Vec<String> {
     let mut out = Vec::new();
     for f in files {
         if let Ok(content) = fs::read_to_string(&f) {
-            out.extend(
-                content
-                    .lines()
-                    .filter(|l| !l.trim().is_empty())
-                    .map(String::from),
-            );
+            let name = f
+                .file_name()
+                .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+            let stmts = content
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(String::from)
+                .collect();
+            out.push((name, stmts));
         }
     }
     out
@@ -424,7 +1072,10 @@ pub fn load_dialect_from(root: &Path, dialect: Dialect) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{count_accepted, eval_file, grade_chunk, load_dialect_from, DialectReport};
+    use super::{
+        cluster_failures, count_accepted, eval_file, grade_chunk, load_dialect_files_from,
+        load_dialect_from, DialectReport, ErrorSignature,
+    };
     use crate::datasets::Dialect;
     use crate::{BenchParser, Parser};
     use std::fs;
@@ -474,14 +1125,15 @@ mod tests {
             match self.parse_outcome(sql, dialect) {
                 crate::ParseOutcome::Unsupported => None,
                 crate::ParseOutcome::Accepted => Some(Ok(())),
-                crate::ParseOutcome::Rejected(e) | crate::ParseOutcome::Panicked(e) => Some(Err(e)),
+                crate::ParseOutcome::Rejected(e) => Some(Err(e.message)),
+                crate::ParseOutcome::Panicked(e) => Some(Err(e)),
             }
         }
         fn parse_outcome(&self, sql: &str, _d: Dialect) -> crate::ParseOutcome {
             match sql {
                 "OK" => crate::ParseOutcome::Accepted,
                 "PANIC" => crate::ParseOutcome::Panicked("boom".to_string()),
-                _ => crate::ParseOutcome::Rejected("nope".to_string()),
+                _ => crate::ParseOutcome::Rejected(crate::diagnostics::ParseError::new("nope")),
             }
         }
         fn parse_once(&self, sql: &str, _d: Dialect) -> bool {
@@ -524,6 +1176,22 @@ mod tests {
         assert_eq!(s.accepted_invalid, 0);
     }
 
+    #[test]
+    fn error_position_is_scored_only_against_a_located_reference() {
+        let mut s = super::ParserStat::default();
+        super::score_error_position(&mut s, None, Some(10));
+        super::score_error_position(&mut s, Some(12), None);
+        super::score_error_position(&mut s, Some(14), Some(10));
+        super::score_error_position(&mut s, Some(40), Some(10));
+        assert_eq!(s.rejected_invalid, 4);
+        assert_eq!(
+            s.error_located, 3,
+            "every rejection but the first has a location"
+        );
+        assert_eq!(s.error_scored, 2, "only where the reference located it too");
+        assert_eq!(s.error_near, 1, "4 bytes off is near, 30 is not");
+    }
+
     #[test]
     fn provenance_dialect_treats_everything_as_valid() {
         let stmts = vec!["SELECT 1".to_string()];
@@ -538,6 +1206,50 @@ mod tests {
         assert_eq!(r.stats[0].accepted_valid, 1);
     }
 
+    #[test]
+    fn constructs_are_tallied_over_valid_statements() {
+        let stmts = vec![
+            "WITH t AS (SELECT 1) SELECT * FROM t".to_string(),
+            "WITH t AS (SELECT 1) SELECT * FROM".to_string(),
+            "SELECT 1".to_string(),
+        ];
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let r = grade_chunk(&stmts, Dialect::Multi, &parsers);
+        let cte = crate::constructs::registry()
+            .constructs
+            .iter()
+            .position(|c| c.meta.id == "cte")
+            .unwrap();
+        // Both CTE statements count, only the complete one is accepted.
+        assert_eq!(r.construct_valid[cte], 2);
+        assert_eq!(r.stats[0].construct_accepted[cte], 1);
+        let mut merged = DialectReport::empty(Dialect::Multi, &parsers);
+        merged.merge(&r);
+        merged.merge(&r);
+        assert_eq!(merged.stats[0].construct_accepted[cte], 2);
+    }
+
+    #[test]
+    fn statement_kinds_are_tallied_over_valid_statements() {
+        use crate::stmt_kind::StatementKind;
+        let stmts = vec![
+            "SELECT 1".to_string(),
+            "SELECT FROM WHERE".to_string(),
+            "CREATE TABLE t (a int)".to_string(),
+        ];
+        let sp = BenchParser::Sqlparser;
+        let parsers: [&dyn Parser; 1] = [&sp];
+        let r = grade_chunk(&stmts, Dialect::Multi, &parsers);
+        let select = StatementKind::Select.index();
+        let create = StatementKind::CreateTable.index();
+        assert_eq!(r.kind_valid[select], 2);
+        assert_eq!(r.kind_valid[create], 1);
+        assert_eq!(r.stats[0].kind_accepted[select], 1);
+        assert_eq!(r.stats[0].kind_accepted[create], 1);
+        assert_eq!(r.kind_valid.iter().sum::<usize>(), r.valid_total);
+    }
+
     #[test]
     fn failures_collects_rejected_expected_statements() {
         // On a provenance dialect (no reference engine), every statement is
@@ -563,6 +1275,83 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn signatures_blur_identifiers_and_positions_but_keep_keywords() {
+        let a = ErrorSignature::new(
+            "SELECT a FROM t1 WINDOW w AS ()",
+            "Expected: end of statement, found: WINDOW at Line: 1, Column: 18",
+        );
+        let b = ErrorSignature::new(
+            "select x, y from orders window win as (order by x)",
+            "Expected: end of statement, found: window at Line: 1, Column: 27",
+        );
+        assert_eq!(a.head, "SELECT");
+        assert_eq!(
+            a.message,
+            "Expected: end of statement, found: WINDOW at Line: #, Column: #"
+        );
+        assert_eq!(a, b);
+        let pg = |sql| ErrorSignature::new(sql, "syntax error at or near \"foo\"");
+        assert_eq!(
+            pg("CREATE TABLE foo (a int)").message,
+            "syntax error at or near \"?\""
+        );
+        assert_eq!(pg("CREATE TABLE foo (a int)").head, "CREATE TABLE");
+        assert_ne!(
+            pg("CREATE TABLE foo (a int)"),
+            pg("ALTER TABLE foo ADD a int")
+        );
+        // Column lists and literals collapse in the shape, clauses do not.
+        let shape = |sql| ErrorSignature::new(sql, "bad").shape;
+        assert_eq!(
+            shape("SELECT price, 'x' FROM orders WHERE qty = 1"),
+            "SELECT ? FROM ? WHERE ?"
+        );
+        assert_eq!(shape("SELECT price FROM orders"), "SELECT ? FROM ?");
+        assert_ne!(shape("SELECT price"), shape("SELECT price FROM orders"));
+    }
+
+    #[test]
+    fn clusters_count_members_and_show_the_shortest() {
+        let rejected: Vec<String> = [
+            "SELECT aa FROM t",
+            "SELECT bb FROM t",
+            "DELETE FROM t",
+            "SELECT c FROM t",
+        ]
+        .map(String::from)
+        .to_vec();
+        let reasons: Vec<String> = ["bad aa at 9", "bad bb at 8", "bad at 3", "bad c at 7"]
+            .map(String::from)
+            .to_vec();
+        let c = cluster_failures(&rejected, &reasons);
+        assert_eq!(c.len(), 2);
+        assert_eq!((c[0].count, c[0].exemplar), (3, 3));
+        assert_eq!(c[0].signature.message, "bad ? at #");
+        assert_eq!(
+            (c[1].count, c[1].signature.head.as_str()),
+            (1, "DELETE FROM")
+        );
+    }
+
+    #[test]
+    fn pairs_count_what_each_side_alone_accepts() {
+        let stmts: Vec<String> = ["OK", "SELECT 1", "SELECT 2", "nope nope"]
+            .map(String::from)
+            .to_vec();
+        let (mock, sp) = (MockParser, BenchParser::Sqlparser);
+        let parsers: [&dyn Parser; 2] = [&mock, &sp];
+        let pairs = super::pairwise(&stmts, Dialect::Multi, &parsers);
+        assert_eq!(pairs.len(), 1);
+        let d = &pairs[0];
+        assert_eq!((d.a.family, d.b.family), ("mock", "sqlparser-rs"));
+        assert_eq!((d.only_a, d.only_b, d.both), (1, 2, 0));
+        assert_eq!(d.samples_a[0].sql, "OK");
+        assert_eq!(d.samples_b.len(), 2);
+        // Multi has no reference engine to label the samples.
+        assert_eq!(d.samples_b[0].reference, None);
+    }
+
     #[test]
     fn failures_empty_for_missing_corpus() {
         let root = temp_root("failures_missing");
@@ -573,6 +1362,26 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn reference_parser_is_not_compared_with_itself() {
+        assert!(super::is_reference_parser(
+            &BenchParser::PgQuery,
+            Dialect::Postgresql
+        ));
+        assert!(super::is_reference_parser(
+            &BenchParser::Sqlite3,
+            Dialect::Sqlite
+        ));
+        assert!(!super::is_reference_parser(
+            &BenchParser::Sqlparser,
+            Dialect::Postgresql
+        ));
+        assert!(!super::is_reference_parser(
+            &BenchParser::PgQuery,
+            Dialect::Mysql
+        ));
+    }
+
     #[test]
     fn merge_sums_tallies() {
         let bp = BenchParser::Sqlparser;
@@ -610,6 +1419,10 @@ mod tests {
         let got = load_dialect_from(&root, Dialect::Postgresql);
         // a.txt before b.txt (sorted), blank lines dropped, .md ignored.
         assert_eq!(got, vec!["SELECT 1", "SELECT 2", "SELECT 3"]);
+        let files = load_dialect_files_from(&root, Dialect::Postgresql);
+        assert_eq!(files[0], ("a.txt".to_string(), got[..2].to_vec()));
+        assert_eq!(files[1].0, "b.txt");
+        assert_eq!(files.len(), 2);
         let _ = fs::remove_dir_all(&root);
     }
 
@@ -0,0 +1,204 @@
+//! Source-span fidelity for parsers that attach spans to their AST.
+//!
+//! sqlparser-rs, qusql-parse and databend-common-ast record where each node
+//! came from, and linters and language servers rely on those ranges to
+//! underline the right text. This module collects the spans of identifiers
+//! and literals from a parser's tree and checks that each one slices back to the
+//! token it labels: the span of identifier `a` must cover `a` (or `"a"`) in the
+//! original statement.
+//!
+//! Like [`crate::norm`], it reads the parser's `Debug` rendering, keeping the
+//! position fields the normalization drops. A span is a `span` field next to a
+//! `value` or `name`, written as a byte range (`7..8`, qusql-parse and
+//! databend) or a line/column pair (`Span(Location(1,8)..Location(1,9))`,
+//! sqlparser-rs). Empty spans, which parsers use for synthesized nodes, are not
+//! collected.
+
+use crate::diagnostics::ErrorLocation;
+use crate::norm::{walk, Dbg, DebugParser};
+use std::ops::Range;
+
+/// One identifier or literal and the byte range its parser attributes to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenSpan {
+    pub range: Range<usize>,
+    /// The token as the parser holds it (unquoted identifier, literal value).
+    pub text: String,
+}
+
+impl TokenSpan {
+    /// Whether the span covers the token in `sql`: the sliced text equals the
+    /// parser's value, ignoring ASCII case, surrounding quotes, and the
+    /// spelling of numbers (`1.0` and `1`).
+    #[must_use]
+    pub fn slices_back(&self, sql: &str) -> bool {
+        let Some(slice) = sql.get(self.range.clone()) else {
+            return false;
+        };
+        let unquoted = unquote(slice);
+        slice.eq_ignore_ascii_case(&self.text)
+            || unquoted.eq_ignore_ascii_case(&self.text)
+            || matches!(
+                (unquoted.parse::<f64>(), self.text.parse::<f64>()),
+                (Ok(a), Ok(b)) if (a - b).abs() < f64::EPSILON
+            )
+    }
+}
+
+/// The identifier and literal spans in a parser's `Debug` rendering of `sql`'s
+/// tree, in tree order.
+#[must_use]
+pub fn from_debug(debug: &str, sql: &str) -> Vec<TokenSpan> {
+    let root = DebugParser::with_positions(debug).value();
+    let mut out = Vec::new();
+    walk(&root, &mut |n| {
+        if let Some(t) = token_span(n, sql) {
+            out.push(t);
+        }
+    });
+    out
+}
+
+/// A node carrying a token and its span: `{ value: .., span: .. }` (or `name`),
+/// or an anonymous `(value, start..end)` pair.
+fn token_span(n: &Dbg, sql: &str) -> Option<TokenSpan> {
+    let (text, span) = if n.name()?.is_empty() {
+        let mut items = n.positional();
+        let (text, span) = (items.next()?, items.next()?);
+        if items.next().is_some() {
+            return None;
+        }
+        (token_text(text)?, span)
+    } else {
+        let text = n.field("value").or_else(|| n.field("name"))?;
+        (token_text(text)?, n.field("span")?)
+    };
+    let range = byte_range(span, sql)?;
+    Some(TokenSpan { range, text })
+}
+
+/// A token's text: a string or bare word, or a literal variant wrapping one
+/// (`Number("1", false)`, `UInt64(1)`, `SingleQuotedString("x")`).
+fn token_text(v: &Dbg) -> Option<String> {
+    match v {
+        Dbg::Str(s) | Dbg::Atom(s) => Some(s.clone()),
+        Dbg::Node { fields, .. } if fields.iter().all(|(k, _)| k.is_none()) => {
+            match fields.first() {
+                Some((_, Dbg::Str(s) | Dbg::Atom(s))) => Some(s.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// A span value as a non-empty byte range in `sql`.
+fn byte_range(v: &Dbg, sql: &str) -> Option<Range<usize>> {
+    let range = match v {
+        Dbg::Atom(a) => {
+            let (start, end) = a.split_once("..")?;
+            start.parse().ok()?..end.parse().ok()?
+        }
+        // `Span(Location(l,c)..Location(l,c))` reads as a `Span` node whose
+        // second item is named `..Location`.
+        Dbg::Node { name, .. } if name == "Span" => {
+            let mut ends = v.positional().map(|l| line_col(l, sql));
+            ends.next()??..ends.next()??
+        }
+        _ => return None,
+    };
+    (range.start < range.end).then_some(range)
+}
+
+/// A `Location(line, column)` as a byte offset. Line 0 is sqlparser-rs's empty
+/// location.
+fn line_col(v: &Dbg, sql: &str) -> Option<usize> {
+    let mut nums = v.positional().map(|n| match n {
+        Dbg::Atom(a) => a.parse().ok(),
+        _ => None,
+    });
+    let (line, column) = (nums.next()??, nums.next()??);
+    ErrorLocation::LineCol { line, column }.offset(sql)
+}
+
+/// `slice` without one pair of surrounding quotes (`'..'`, `".."`, `` `..` ``,
+/// `[..]`), with doubled quotes inside collapsed.
+fn unquote(slice: &str) -> String {
+    for (open, close) in [('\'', '\''), ('"', '"'), ('`', '`'), ('[', ']')] {
+        if let Some(inner) = slice.strip_prefix(open).and_then(|s| s.strip_suffix(close)) {
+            return inner.replace(&format!("{close}{close}"), &close.to_string());
+        }
+    }
+    slice.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_debug, TokenSpan};
+    use crate::datasets::Dialect;
+    use crate::BenchParser;
+
+    /// `p` reports spans for a plain statement, and every one of them covers
+    /// its token.
+    fn assert_spans_slice_back(p: BenchParser) {
+        let sql = "SELECT a FROM t WHERE b = 1";
+        let spans = p.spans(sql, Dialect::Postgresql).unwrap_or_default();
+        assert!(!spans.is_empty(), "{} reported no spans", p.name());
+        assert!(
+            spans.iter().all(|s| s.slices_back(sql)),
+            "{}: {spans:?}",
+            p.name()
+        );
+    }
+
+    #[test]
+    fn sqlparser_spans_slice_back() {
+        assert_spans_slice_back(BenchParser::Sqlparser);
+    }
+
+    #[test]
+    fn qusql_spans_slice_back() {
+        assert_spans_slice_back(BenchParser::Qusql);
+    }
+
+    #[test]
+    fn databend_spans_slice_back() {
+        assert_spans_slice_back(BenchParser::Databend);
+    }
+
+    #[test]
+    fn byte_range_and_line_column_spans_are_read() {
+        let sql = "SELECT a FROM t";
+        let qusql = r#"[Select(Select { select_exprs: [Identifier { value: "a", span: 7..8 }], table: Identifier { value: "t", span: 14..15 } })]"#;
+        let spans = from_debug(qusql, sql);
+        assert_eq!(spans.len(), 2);
+        assert!(spans.iter().all(|s| s.slices_back(sql)));
+
+        let sqlparser = r#"[Query(Query { body: Ident { value: "a", quote_style: None, span: Span(Location(1,8)..Location(1,9)) }, from: Ident { value: "t", quote_style: None, span: Span(Location(0,0)..Location(0,0)) } })]"#;
+        let spans = from_debug(sqlparser, sql);
+        // The empty span of the synthesized node is not collected.
+        assert_eq!(
+            spans,
+            vec![TokenSpan {
+                range: 7..8,
+                text: "a".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn slices_back_tolerates_quotes_case_and_number_spelling() {
+        let sql = r#"SELECT "Col", 'it''s', 1.0 FROM T"#;
+        let at = |range, text: &str| TokenSpan {
+            range,
+            text: text.to_string(),
+        };
+        assert!(at(7..12, "Col").slices_back(sql));
+        assert!(at(14..21, "it's").slices_back(sql));
+        assert!(at(23..26, "1").slices_back(sql));
+        assert!(at(32..33, "t").slices_back(sql));
+        // Off by one, and past the end of the statement.
+        assert!(!at(8..12, "Col").slices_back(sql));
+        assert!(!at(32..40, "t").slices_back(sql));
+    }
+}
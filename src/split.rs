@@ -0,0 +1,319 @@
+//! Statement-splitter accuracy.
+//!
+//! The batch axis ([`crate::batch`]) only asks whether a parser reparses a
+//! joined script to the right statement count. A migration runner needs more:
+//! where each statement ends, so it can run them one at a time. This axis
+//! builds scripts whose statement boundaries are known, hands them to each
+//! parser's splitting entry point ([`crate::Parser::split`]), and scores the
+//! boundaries it returns. Scripts are drawn from the statements the parser
+//! accepts on their own, in three kinds ([`ScriptKind`]):
+//!
+//! - plain: random batches joined with `;` exactly as [`batch::join_batch`]
+//!   does,
+//! - compound: statements carrying a `;` of their own (dollar-quoted function
+//!   bodies, trigger and `BEGIN ... END` block bodies, the statements
+//!   `build_sqlite_suite` and `build_proc_suites` keep whole), interleaved
+//!   with plain ones. These are what a naive split on `;` shreds.
+//! - delimiter (`MySQL` only): compound statements in a `DELIMITER //` block,
+//!   terminated by `//` as the mysql client expects.
+//!
+//! A returned boundary is the gap between two consecutive returned statements.
+//! It is correct when it contains exactly one of the script's terminators, so
+//! whether a parser's ranges include the terminator, whitespace, or a trailing
+//! comment does not matter, but a statement cut in two (no terminator in the
+//! gap) or two merged into one (a terminator never in a gap) does. Precision
+//! is correct boundaries over returned ones, recall correct boundaries over
+//! the script's terminators.
+
+use crate::batch::{batch_eligible, join_batch_with, sample_batches, seed_for};
+use crate::datasets::Dialect;
+use crate::Parser;
+use std::ops::Range;
+
+/// Statements per sampled script.
+pub const SPLIT_M: usize = 16;
+
+/// Sampled scripts per kind and (parser, dialect).
+pub const SPLIT_K: usize = 50;
+
+/// How a script's statements were chosen and terminated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptKind {
+    Plain,
+    Compound,
+    Delimiter,
+}
+
+/// A multi-statement script and the byte offsets of its terminators.
+pub struct Script {
+    pub kind: ScriptKind,
+    pub sql: String,
+    pub boundaries: Vec<usize>,
+}
+
+/// Boundary counts over one or more scripts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Boundaries the parser returned (one fewer than its statements).
+    pub returned: usize,
+    /// Returned boundaries holding exactly one real terminator.
+    pub correct: usize,
+    /// Real terminators in the scripts.
+    pub expected: usize,
+}
+
+impl Tally {
+    pub const fn merge(&mut self, other: &Self) {
+        self.returned += other.returned;
+        self.correct += other.correct;
+        self.expected += other.expected;
+    }
+}
+
+/// Per-kind boundary counts for one parser in one dialect.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SplitStat {
+    pub plain: Tally,
+    pub compound: Tally,
+    pub delimiter: Tally,
+}
+
+impl SplitStat {
+    pub const fn merge(&mut self, other: &Self) {
+        self.plain.merge(&other.plain);
+        self.compound.merge(&other.compound);
+        self.delimiter.merge(&other.delimiter);
+    }
+
+    /// Plain and compound scripts together. `DELIMITER` blocks are a client
+    /// convention no parser is obliged to know, so they are kept apart.
+    #[must_use]
+    pub const fn overall(&self) -> Tally {
+        let mut t = self.plain;
+        t.merge(&self.compound);
+        t
+    }
+
+    const fn of_kind(&mut self, kind: ScriptKind) -> &mut Tally {
+        match kind {
+            ScriptKind::Plain => &mut self.plain,
+            ScriptKind::Compound => &mut self.compound,
+            ScriptKind::Delimiter => &mut self.delimiter,
+        }
+    }
+}
+
+/// Whether `stmt` has a `;` before its end outside quotes and comments.
+///
+/// A naive split on `;` would cut such a statement. Dollar quotes are
+/// deliberately not recognized: a `;` in a function body is exactly such a cut.
+#[must_use]
+pub fn has_inner_terminator(stmt: &str) -> bool {
+    let b = stmt.trim().trim_end_matches(';').as_bytes();
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b';' => return true,
+            q @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < b.len() && b[i] != q {
+                    i += 1;
+                }
+            }
+            b'-' if b.get(i + 1) == Some(&b'-') => {
+                while i < b.len() && b[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if b.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < b.len() && !(b[i] == b'*' && b[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    false
+}
+
+/// The scripts one parser is scored on, from `eligible` (statements it
+/// accepts and splits into exactly one on their own). Deterministic for a
+/// given `label`.
+#[must_use]
+pub fn scripts(eligible: &[&str], dialect: Dialect, label: &str) -> Vec<Script> {
+    let mut out = Vec::new();
+    for idxs in sample_batches(eligible.len(), SPLIT_M, SPLIT_K, seed_for(label)) {
+        let stmts: Vec<&str> = idxs.iter().map(|&i| eligible[i]).collect();
+        push(&mut out, ScriptKind::Plain, &stmts, ";", "");
+    }
+    let (compound, plain): (Vec<&str>, Vec<&str>) =
+        eligible.iter().partition(|s| has_inner_terminator(s));
+    let seed = seed_for(&format!("{label}/compound"));
+    for idxs in sample_batches(compound.len(), SPLIT_M / 2, SPLIT_K, seed) {
+        // Each compound statement followed by a plain one, as in a real
+        // migration that defines a trigger or function and then uses it.
+        let mixed: Vec<&str> = idxs
+            .iter()
+            .flat_map(|&i| {
+                [
+                    Some(compound[i]),
+                    plain.get(i % plain.len().max(1)).copied(),
+                ]
+            })
+            .flatten()
+            .collect();
+        push(&mut out, ScriptKind::Compound, &mixed, ";", "");
+        if dialect == Dialect::Mysql {
+            push(
+                &mut out,
+                ScriptKind::Delimiter,
+                &mixed,
+                "//",
+                "DELIMITER //\n",
+            );
+        }
+    }
+    out
+}
+
+/// Join `stmts` after `prefix` and keep the script if it has a boundary.
+fn push(out: &mut Vec<Script>, kind: ScriptKind, stmts: &[&str], terminator: &str, prefix: &str) {
+    let (body, at) = join_batch_with(stmts, terminator);
+    if at.is_empty() {
+        return;
+    }
+    out.push(Script {
+        kind,
+        sql: format!("{prefix}{body}"),
+        boundaries: at.into_iter().map(|i| i + prefix.len()).collect(),
+    });
+}
+
+/// Score the statement `ranges` a parser returned for `script`.
+#[must_use]
+pub fn score(script: &Script, ranges: &[Range<usize>]) -> Tally {
+    let mut trimmed: Vec<Range<usize>> =
+        ranges.iter().filter_map(|r| trim(&script.sql, r)).collect();
+    trimmed.sort_by_key(|r| r.start);
+    let correct = trimmed
+        .windows(2)
+        .filter(|w| {
+            let gap = w[0].end..w[1].start;
+            script.boundaries.iter().filter(|b| gap.contains(b)).count() == 1
+        })
+        .count();
+    Tally {
+        returned: trimmed.len().saturating_sub(1),
+        correct,
+        expected: script.boundaries.len(),
+    }
+}
+
+/// `r` without surrounding whitespace or trailing `;`. `None` if nothing is
+/// left or `r` does not slice `sql`.
+pub(crate) fn trim(sql: &str, r: &Range<usize>) -> Option<Range<usize>> {
+    let text = sql.get(r.clone())?;
+    let start = r.start + (text.len() - text.trim_start().len());
+    let end = r.start
+        + text
+            .trim_end_matches(|c: char| c.is_whitespace() || c == ';')
+            .len();
+    (start < end).then_some(start..end)
+}
+
+/// Score `parser` on scripts built from `accepted` (statements it accepts in
+/// `dialect`). `None` if the parser does not report statement positions.
+///
+/// Splitting runs under `catch_unwind`: a panic scores as no statements.
+#[must_use]
+pub fn evaluate(parser: &dyn Parser, dialect: Dialect, accepted: &[&str]) -> Option<SplitStat> {
+    let split = |sql: &str| {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| parser.split(sql, dialect)))
+            .unwrap_or_else(|_| Some(Vec::new()))
+    };
+    split("SELECT 1")?;
+    let eligible: Vec<&str> = accepted
+        .iter()
+        .copied()
+        .filter(|s| batch_eligible(s) && split(s).is_some_and(|r| r.len() == 1))
+        .collect();
+    let label = format!("split/{}/{}", parser.id().family, dialect.dir_name());
+    let mut stat = SplitStat::default();
+    for script in scripts(&eligible, dialect, &label) {
+        let ranges = split(&script.sql).unwrap_or_default();
+        stat.of_kind(script.kind).merge(&score(&script, &ranges));
+    }
+    Some(stat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_inner_terminator, score, scripts, Script, ScriptKind, Tally};
+    use crate::datasets::Dialect;
+
+    fn script(sql: &str) -> Script {
+        Script {
+            kind: ScriptKind::Plain,
+            sql: sql.to_string(),
+            boundaries: sql.match_indices("\n;\n").map(|(i, _)| i + 1).collect(),
+        }
+    }
+
+    #[test]
+    fn boundaries_are_scored_by_the_gap_between_statements() {
+        let s = script("SELECT 1\n;\nSELECT 2 -- two\n;\nSELECT 3");
+        // Ranges with or without surrounding whitespace and trailing comments.
+        assert_eq!(
+            score(&s, &[0..8, 10..26, 29..37]),
+            Tally {
+                returned: 2,
+                correct: 2,
+                expected: 2
+            }
+        );
+        // The last two merged into one: one boundary found, one missed.
+        assert_eq!(score(&s, &[0..8, 11..37]).correct, 1);
+        // The first cut in two: the extra boundary holds no terminator.
+        let cut = score(&s, &[0..6, 7..8, 11..18, 29..37]);
+        assert_eq!((cut.returned, cut.correct), (3, 2));
+        // Nothing parsed: nothing returned, nothing found.
+        assert_eq!(score(&s, &[]).returned, 0);
+    }
+
+    #[test]
+    fn inner_terminators_outside_quotes_and_comments_make_a_compound() {
+        assert!(has_inner_terminator(
+            "CREATE TRIGGER t AFTER INSERT ON a BEGIN DELETE FROM b; END;"
+        ));
+        assert!(has_inner_terminator(
+            "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql"
+        ));
+        assert!(!has_inner_terminator("SELECT ';' /* ; */ -- ;"));
+        assert!(!has_inner_terminator("SELECT 1;"));
+    }
+
+    #[test]
+    fn only_mysql_gets_delimiter_scripts() {
+        let trigger = "CREATE TRIGGER t BEFORE INSERT ON a FOR EACH ROW BEGIN SET @x = 1; END";
+        let pool = [trigger, "SELECT 1", "SELECT 2"];
+        let kinds = |d| {
+            scripts(&pool, d, "t")
+                .iter()
+                .map(|s| s.kind)
+                .collect::<Vec<_>>()
+        };
+        assert!(kinds(Dialect::Mysql).contains(&ScriptKind::Delimiter));
+        assert!(!kinds(Dialect::Postgresql).contains(&ScriptKind::Delimiter));
+        for s in scripts(&pool, Dialect::Mysql, "t") {
+            let term = if s.kind == ScriptKind::Delimiter {
+                "//"
+            } else {
+                ";"
+            };
+            assert!(s.boundaries.iter().all(|&b| s.sql[b..].starts_with(term)));
+        }
+    }
+}
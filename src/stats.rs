@@ -65,6 +65,54 @@ pub fn std_dev(sample: &[f64]) -> f64 {
     var.sqrt()
 }
 
+/// Ordinary least-squares fit of `y = slope * x + intercept` to `(x, y)`
+/// points, as `(slope, intercept)`. `None` for fewer than two points or when
+/// every `x` is the same.
+#[must_use]
+pub fn linear_fit(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    // Tested on the raw values: the mean of equal values can round off them,
+    // leaving a tiny positive `sxx` and a meaningless slope.
+    let x0 = points.first()?.0;
+    if !points.iter().any(|p| (p.0 - x0).abs() > 0.0) {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    (sxx > 0.0).then(|| {
+        let slope = sxy / sxx;
+        (slope, slope.mul_add(-mean_x, mean_y))
+    })
+}
+
+/// A downsampled curve of `y` against `x`.
+///
+/// The points are sorted by `x` and cut into `bins` equal-count bins (fewer
+/// only with fewer points), each reduced to `[median x, median y]`. Equal
+/// counts rather than equal widths keep the long tail of large inputs from
+/// leaving most bins empty.
+#[must_use]
+pub fn binned_medians(points: &[(f64, f64)], bins: usize) -> Vec<[f64; 2]> {
+    if points.is_empty() || bins == 0 {
+        return Vec::new();
+    }
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let n = sorted.len();
+    let bins = bins.min(n);
+    (0..bins)
+        .map(|i| &sorted[i * n / bins..(i + 1) * n / bins])
+        .map(|chunk| {
+            let xs: Vec<f64> = chunk.iter().map(|p| p.0).collect();
+            let mut ys: Vec<f64> = chunk.iter().map(|p| p.1).collect();
+            ys.sort_by(f64::total_cmp);
+            [quantile(&xs, 0.5), quantile(&ys, 0.5)]
+        })
+        .collect()
+}
+
 /// Build a [`viz::MemDist`] from an ascending-sorted sample.
 ///
 /// Percentiles, mean, std, and a downsampled eCDF. Empty input yields an
@@ -132,12 +180,14 @@ pub fn perf_from(
         std: d.std,
         roundtrip_pct,
         ecdf: d.ecdf,
+        by_kind: Vec::new(),
+        scaling: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ecdf_points, quantile, slug};
+    use super::{binned_medians, ecdf_points, linear_fit, quantile, slug};
 
     #[test]
     fn quantile_endpoints_and_median() {
@@ -184,6 +234,31 @@ mod tests {
         assert!(ecdf_points(&[], 10).is_empty());
     }
 
+    #[test]
+    fn linear_fit_recovers_slope_and_intercept() {
+        let pts: Vec<(f64, f64)> = (0..10)
+            .map(|i| (f64::from(i), 3.0f64.mul_add(f64::from(i), 5.0)))
+            .collect();
+        let (slope, intercept) = linear_fit(&pts).expect("fit");
+        assert!((slope - 3.0).abs() < 1e-9);
+        assert!((intercept - 5.0).abs() < 1e-9);
+        assert!(linear_fit(&[(1.0, 2.0)]).is_none());
+        assert!(linear_fit(&[(1.0, 2.0), (1.0, 4.0)]).is_none());
+        let same_ln = [(10f64.ln(), 1.0); 10];
+        assert!(linear_fit(&same_ln).is_none());
+    }
+
+    #[test]
+    fn binned_medians_cut_equal_count_bins_in_x_order() {
+        let pts: Vec<(f64, f64)> = (0..9)
+            .rev()
+            .map(|i| (f64::from(i), 10.0 * f64::from(i)))
+            .collect();
+        let curve = binned_medians(&pts, 3);
+        assert_eq!(curve, [[1.0, 10.0], [4.0, 40.0], [7.0, 70.0]]);
+        assert!(binned_medians(&[], 3).is_empty());
+    }
+
     #[test]
     fn slug_replaces_non_alphanumeric() {
         assert_eq!(slug("pg_query (summary)"), "pg_query__summary_");
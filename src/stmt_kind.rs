@@ -0,0 +1,276 @@
+//! Statement-kind classifier for the per-kind recall and speed breakdown.
+//!
+//! A lightweight, parser-independent label from the leading keywords of the
+//! [`mask`]ed statement: enough to split the headline numbers into queries,
+//! DML, DDL and the rest, so a team that only parses queries can ignore DDL
+//! gaps and one building migration tools can focus on them. A statement the
+//! rules do not recognize is [`StatementKind::Other`].
+
+use crate::contentious::mask;
+
+/// What a statement does, by its leading keywords.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Merge,
+    CreateTable,
+    CreateIndex,
+    Alter,
+    Drop,
+    Grant,
+    Transaction,
+    Setting,
+    Procedural,
+    Other,
+}
+
+impl StatementKind {
+    pub const ALL: [Self; 14] = [
+        Self::Select,
+        Self::Insert,
+        Self::Update,
+        Self::Delete,
+        Self::Merge,
+        Self::CreateTable,
+        Self::CreateIndex,
+        Self::Alter,
+        Self::Drop,
+        Self::Grant,
+        Self::Transaction,
+        Self::Setting,
+        Self::Procedural,
+        Self::Other,
+    ];
+
+    /// Short name, used in file names.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Merge => "merge",
+            Self::CreateTable => "create-table",
+            Self::CreateIndex => "create-index",
+            Self::Alter => "alter",
+            Self::Drop => "drop",
+            Self::Grant => "grant",
+            Self::Transaction => "transaction",
+            Self::Setting => "setting",
+            Self::Procedural => "procedural",
+            Self::Other => "other",
+        }
+    }
+
+    /// Display label, used in the export and table headers.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Select => "SELECT",
+            Self::Insert => "INSERT",
+            Self::Update => "UPDATE",
+            Self::Delete => "DELETE",
+            Self::Merge => "MERGE",
+            Self::CreateTable => "CREATE TABLE",
+            Self::CreateIndex => "CREATE INDEX",
+            Self::Alter => "ALTER",
+            Self::Drop => "DROP",
+            Self::Grant => "GRANT/REVOKE",
+            Self::Transaction => "transaction",
+            Self::Setting => "SET/PRAGMA",
+            Self::Procedural => "procedural",
+            Self::Other => "other",
+        }
+    }
+
+    /// Position in [`Self::ALL`], for per-kind tallies.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Classify `sql` by its leading keywords.
+#[must_use]
+pub fn classify(sql: &str) -> StatementKind {
+    let words = top_level_words(&mask(sql));
+    let word = |i: usize| words.get(i).map_or("", String::as_str);
+    match word(0) {
+        "SELECT" | "VALUES" | "TABLE" | "FROM" => StatementKind::Select,
+        "WITH" => words[1..]
+            .iter()
+            .find_map(|w| dml(w))
+            .unwrap_or(StatementKind::Other),
+        "INSERT" | "REPLACE" | "UPSERT" => StatementKind::Insert,
+        "UPDATE" => StatementKind::Update,
+        "DELETE" => StatementKind::Delete,
+        "MERGE" => StatementKind::Merge,
+        "CREATE" => create(&words[1..]),
+        "ALTER" => StatementKind::Alter,
+        "DROP" => StatementKind::Drop,
+        "GRANT" | "REVOKE" => StatementKind::Grant,
+        "BEGIN" => match word(1) {
+            "" | "TRANSACTION" | "TRAN" | "WORK" | "DEFERRED" | "IMMEDIATE" | "EXCLUSIVE"
+            | "ISOLATION" | "READ" | "DISTRIBUTED" => StatementKind::Transaction,
+            _ => StatementKind::Procedural,
+        },
+        "START" | "COMMIT" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" | "ABORT" | "END" => {
+            StatementKind::Transaction
+        }
+        "SET" if word(1) == "TRANSACTION" => StatementKind::Transaction,
+        "SET" | "PRAGMA" | "RESET" => StatementKind::Setting,
+        "DO" | "DECLARE" | "IF" | "WHILE" | "CALL" | "EXEC" | "EXECUTE" => {
+            StatementKind::Procedural
+        }
+        _ => StatementKind::Other,
+    }
+}
+
+/// The kind of the statement a `WITH` clause leads into.
+fn dml(word: &str) -> Option<StatementKind> {
+    Some(match word {
+        "SELECT" | "VALUES" | "TABLE" => StatementKind::Select,
+        "INSERT" | "REPLACE" => StatementKind::Insert,
+        "UPDATE" => StatementKind::Update,
+        "DELETE" => StatementKind::Delete,
+        "MERGE" => StatementKind::Merge,
+        _ => return None,
+    })
+}
+
+/// The kind of a `CREATE` statement, from the words after `CREATE`.
+fn create(rest: &[String]) -> StatementKind {
+    const MODIFIERS: &[&str] = &[
+        "OR",
+        "REPLACE",
+        "ALTER",
+        "DEFINER",
+        "GLOBAL",
+        "LOCAL",
+        "TEMP",
+        "TEMPORARY",
+        "UNLOGGED",
+        "EXTERNAL",
+        "TRANSIENT",
+        "VOLATILE",
+        "VIRTUAL",
+        "UNIQUE",
+        "CLUSTERED",
+        "NONCLUSTERED",
+    ];
+    match rest
+        .iter()
+        .map(String::as_str)
+        .find(|w| !MODIFIERS.contains(w))
+    {
+        Some("TABLE") => StatementKind::CreateTable,
+        Some("INDEX") => StatementKind::CreateIndex,
+        Some("FUNCTION" | "PROCEDURE" | "PROC" | "TRIGGER") => StatementKind::Procedural,
+        _ => StatementKind::Other,
+    }
+}
+
+/// Upper-cased words outside parentheses, with leading parentheses skipped (so
+/// `(SELECT 1) UNION (SELECT 2)` reads as a `SELECT`) and quoted identifiers
+/// dropped.
+fn top_level_words(masked: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut depth = 0_usize;
+    let mut word = String::new();
+    let mut chars = masked
+        .trim_start_matches(|c: char| c == '(' || c.is_whitespace())
+        .chars();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            if depth == 0 {
+                word.extend(c.to_uppercase());
+            }
+            continue;
+        }
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                chars.by_ref().find(|&x| x == close);
+            }
+            _ => {}
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statements_are_classified_by_their_leading_keywords() {
+        let cases = [
+            ("select 1", StatementKind::Select),
+            ("(SELECT 1) UNION (SELECT 2)", StatementKind::Select),
+            (
+                "WITH t AS (SELECT 1) SELECT * FROM t",
+                StatementKind::Select,
+            ),
+            (
+                "WITH t AS (SELECT 1) INSERT INTO u SELECT * FROM t",
+                StatementKind::Insert,
+            ),
+            ("REPLACE INTO t VALUES (1)", StatementKind::Insert),
+            ("DELETE FROM t", StatementKind::Delete),
+            ("MERGE INTO t USING s ON t.a = s.a", StatementKind::Merge),
+            ("CREATE TEMP TABLE t (a int)", StatementKind::CreateTable),
+            (
+                "CREATE UNIQUE INDEX IF NOT EXISTS i ON t (a)",
+                StatementKind::CreateIndex,
+            ),
+            (
+                "CREATE OR REPLACE FUNCTION f() RETURNS int AS 'select 1'",
+                StatementKind::Procedural,
+            ),
+            ("CREATE VIEW v AS SELECT 1", StatementKind::Other),
+            ("ALTER TABLE t ADD COLUMN b int", StatementKind::Alter),
+            ("REVOKE ALL ON t FROM u", StatementKind::Grant),
+            ("BEGIN", StatementKind::Transaction),
+            ("BEGIN IMMEDIATE TRANSACTION", StatementKind::Transaction),
+            ("BEGIN SELECT 1; END", StatementKind::Procedural),
+            ("SET TRANSACTION READ ONLY", StatementKind::Transaction),
+            ("PRAGMA foreign_keys = ON", StatementKind::Setting),
+            ("DO $$ BEGIN END $$", StatementKind::Procedural),
+            ("EXPLAIN SELECT 1", StatementKind::Other),
+        ];
+        for (sql, want) in cases {
+            assert_eq!(classify(sql), want, "{sql}");
+        }
+    }
+
+    #[test]
+    fn masked_and_quoted_text_cannot_change_the_kind() {
+        assert_eq!(
+            classify("/* DROP TABLE t */ SELECT 'DELETE'"),
+            StatementKind::Select
+        );
+        assert_eq!(
+            classify("WITH \"insert\" AS (SELECT 1) SELECT 1"),
+            StatementKind::Select
+        );
+    }
+
+    #[test]
+    fn every_kind_sits_at_its_index() {
+        for (i, k) in StatementKind::ALL.iter().enumerate() {
+            assert_eq!(k.index(), i);
+        }
+    }
+}
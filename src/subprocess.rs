@@ -0,0 +1,518 @@
+//! Out-of-process parsers behind a line-delimited JSON protocol.
+//!
+//! A [`SubprocessParser`] implements [`Parser`] by talking to a long-lived
+//! child process, so parsers written in other languages (Python sqlglot, the Go
+//! vitess parser, a C tree-sitter grammar) are graded, exported and shown next
+//! to the Rust ones without a crate dependency or a [`crate::BenchParser`]
+//! variant. They are declared in [`MANIFEST`], one `[[parser]]` table each:
+//!
+//! ```toml
+//! [[parser]]
+//! name = "sqlglot (Python)"
+//! version = "26.0"
+//! command = ["python3", "external/sqlglot_adapter.py"]
+//! dialects = ["postgresql", "mysql", "sqlite"]
+//! reprint = true
+//! batch = true
+//! ```
+//!
+//! The adapter reads one JSON request per line on stdin and answers each with
+//! one JSON line on stdout, in order:
+//!
+//! ```text
+//! -> {"op": "parse", "dialect": "postgresql", "sql": "SELECT 1"}
+//! <- {"outcome": "accepted"}
+//! -> {"op": "parse", "dialect": "postgresql", "sql": "SELECT 1 FROM"}
+//! <- {"outcome": "rejected", "error": "unexpected end of input", "offset": 13}
+//! -> {"op": "reprint", "dialect": "postgresql", "sql": "select 1"}
+//! <- {"outcome": "accepted", "sql": "SELECT 1"}
+//! -> {"op": "parse_batch", "dialect": "postgresql", "sql": "SELECT 1; SELECT 2"}
+//! <- {"outcome": "accepted", "statements": 2}
+//! ```
+//!
+//! `outcome` is one of `accepted`, `rejected`, `panicked` (the adapter caught
+//! an internal crash) or `unsupported`. A rejection may locate the error with
+//! `offset` (bytes) or `line` and `column` (1-based). An adapter that exits or
+//! answers with something that is not a response counts as a panic for that
+//! statement and is restarted for the next one. So does one that has not
+//! answered after `timeout_secs` ([`TIMEOUT`] by default): it is killed and the
+//! statement recorded as `Panicked("timeout")`.
+//!
+//! Every request pays a pipe round trip, so these parsers take part in grading
+//! and export (correctness, coverage, failures) but not in the timing and memory
+//! benches, and the viewer marks them as out-of-process. Requests to one adapter
+//! are serialized, so grading threads take turns on it.
+
+use crate::datasets::Dialect;
+use crate::diagnostics::{ErrorLocation, ParseError};
+use crate::{ParseOutcome, Parser, ParserId};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Manifest declaring the out-of-process parsers. Optional: without it only the
+/// compiled-in parsers run.
+pub const MANIFEST: &str = "external/parsers.toml";
+
+/// How long an adapter may take over one answer unless its entry sets
+/// `timeout_secs`. Generous next to a parse, since the first request also pays
+/// the interpreter's start-up.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    parser: Vec<Spec>,
+}
+
+/// One `[[parser]]` entry of the manifest.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Spec {
+    /// Display name, unique among all parsers.
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    /// Program and arguments that start the adapter.
+    pub command: Vec<String>,
+    /// `datasets/` directory names of the dialects the adapter models.
+    pub dialects: Vec<String>,
+    /// Whether the adapter answers `reprint`.
+    #[serde(default)]
+    pub reprint: bool,
+    /// Whether the adapter answers `parse_batch`.
+    #[serde(default)]
+    pub batch: bool,
+    /// Seconds to wait for one answer before the adapter is restarted.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_timeout_secs() -> u64 {
+    TIMEOUT.as_secs()
+}
+
+/// A parser running in a child process. See the module docs for the protocol.
+pub struct SubprocessParser {
+    // Leaked once per manifest entry: `ParserId` carries `&'static str` so that
+    // compiled-in and historical parsers need no allocation.
+    name: &'static str,
+    version: &'static str,
+    command: Vec<String>,
+    dialects: Vec<Dialect>,
+    reprint: bool,
+    batch: bool,
+    timeout: Duration,
+    session: Mutex<Option<Session>>,
+}
+
+/// A running adapter and its pipes. Stdout is drained line by line on a reader
+/// thread, so a reply can be waited for with a timeout; the thread ends when
+/// the child is killed and its stdout closes.
+struct Session {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<std::io::Result<String>>,
+}
+
+impl Session {
+    fn spawn(command: &[String]) -> std::io::Result<Self> {
+        let (program, args) = command.split_first().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "empty command")
+        })?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let (tx, lines) = channel();
+        std::thread::Builder::new()
+            .name("adapter stdout".to_string())
+            .spawn(move || {
+                for line in stdout.lines() {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+        })
+    }
+
+    /// Send one request line and read one response line, failing with
+    /// [`std::io::ErrorKind::TimedOut`] if none arrives within `timeout`.
+    fn exchange(&mut self, line: &str, timeout: Duration) -> std::io::Result<String> {
+        self.stdin.write_all(line.as_bytes())?;
+        self.stdin.write_all(b"\n")?;
+        self.stdin.flush()?;
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => Err(std::io::ErrorKind::TimedOut.into()),
+            Err(RecvTimeoutError::Disconnected) => Err(std::io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    op: &'a str,
+    dialect: &'a str,
+    sql: &'a str,
+}
+
+/// One adapter response.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+enum Response {
+    Accepted {
+        /// The reprinted statement (`reprint`).
+        #[serde(default)]
+        sql: Option<String>,
+        /// Statements parsed (`parse_batch`).
+        #[serde(default)]
+        statements: Option<usize>,
+    },
+    Rejected {
+        error: String,
+        #[serde(default)]
+        offset: Option<usize>,
+        #[serde(default)]
+        line: Option<usize>,
+        #[serde(default)]
+        column: Option<usize>,
+    },
+    Panicked {
+        #[serde(default)]
+        error: String,
+    },
+    Unsupported,
+}
+
+impl Response {
+    fn outcome(self) -> ParseOutcome {
+        match self {
+            Self::Accepted { .. } => ParseOutcome::Accepted,
+            Self::Rejected {
+                error,
+                offset,
+                line,
+                column,
+            } => {
+                let location = match (offset, line, column) {
+                    (Some(o), _, _) => Some(ErrorLocation::Offset(o)),
+                    (None, Some(line), Some(column)) => {
+                        Some(ErrorLocation::LineCol { line, column })
+                    }
+                    _ => None,
+                };
+                match location {
+                    Some(_) => ParseOutcome::Rejected(ParseError::at(error, location)),
+                    None => ParseOutcome::Rejected(ParseError::new(error)),
+                }
+            }
+            Self::Panicked { error } => ParseOutcome::Panicked(error),
+            Self::Unsupported => ParseOutcome::Unsupported,
+        }
+    }
+}
+
+impl SubprocessParser {
+    /// Start the adapter described by `spec`.
+    ///
+    /// # Errors
+    /// Returns an error if a dialect name is unknown or the command cannot be
+    /// started.
+    pub fn start(spec: Spec) -> Result<Self, String> {
+        let dialects = spec
+            .dialects
+            .iter()
+            .map(|d| {
+                Dialect::from_dir_name(d)
+                    .ok_or_else(|| format!("parser `{}`: unknown dialect `{d}`", spec.name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let session = Session::spawn(&spec.command)
+            .map_err(|e| format!("parser `{}`: starting {:?}: {e}", spec.name, spec.command))?;
+        Ok(Self {
+            name: spec.name.leak(),
+            version: spec.version.leak(),
+            command: spec.command,
+            dialects,
+            reprint: spec.reprint,
+            batch: spec.batch,
+            timeout: Duration::from_secs(spec.timeout_secs),
+            session: Mutex::new(Some(session)),
+        })
+    }
+
+    /// Send one request, restarting the adapter if it has died. An adapter that
+    /// cannot be reached, answers with garbage or does not answer in time
+    /// yields a `panicked` response.
+    fn request(&self, op: &str, sql: &str, dialect: Dialect) -> Response {
+        let line = serde_json::to_string(&Request {
+            op,
+            dialect: dialect.dir_name(),
+            sql,
+        })
+        .expect("a request always serializes");
+        let mut guard = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        if guard.is_none() {
+            *guard = Session::spawn(&self.command).ok();
+        }
+        let reply = guard.as_mut().map_or_else(
+            || Err("restarting adapter failed".to_string()),
+            |session| {
+                session
+                    .exchange(&line, self.timeout)
+                    .map_err(|e| match e.kind() {
+                        std::io::ErrorKind::TimedOut => "timeout".to_string(),
+                        _ => format!("adapter exited: {e}"),
+                    })
+                    .and_then(|r| {
+                        serde_json::from_str(&r)
+                            .map_err(|e| format!("malformed adapter response: {e}"))
+                    })
+            },
+        );
+        if reply.is_err() {
+            // Dead, hung or out of sync with the adapter: kill it and start
+            // afresh next time.
+            *guard = None;
+        }
+        drop(guard);
+        reply.unwrap_or_else(|error| Response::Panicked { error })
+    }
+}
+
+impl Parser for SubprocessParser {
+    fn id(&self) -> ParserId {
+        ParserId {
+            family: self.name,
+            version: self.version,
+            released: "",
+        }
+    }
+    fn supports(&self, dialect: Dialect) -> bool {
+        self.dialects.contains(&dialect)
+    }
+    fn try_parse(&self, sql: &str, dialect: Dialect) -> Option<Result<(), String>> {
+        match self.parse_outcome(sql, dialect) {
+            ParseOutcome::Unsupported => None,
+            ParseOutcome::Accepted => Some(Ok(())),
+            ParseOutcome::Rejected(e) => Some(Err(e.message)),
+            ParseOutcome::Panicked(e) => Some(Err(e)),
+        }
+    }
+    fn parse_outcome(&self, sql: &str, dialect: Dialect) -> ParseOutcome {
+        if !self.supports(dialect) {
+            return ParseOutcome::Unsupported;
+        }
+        self.request("parse", sql, dialect).outcome()
+    }
+    fn parse_once(&self, sql: &str, dialect: Dialect) -> bool {
+        self.accepts(sql, dialect) == Some(true)
+    }
+    fn parse_batch(&self, sql: &str, dialect: Dialect) -> Option<usize> {
+        if !self.batch || !self.supports(dialect) {
+            return None;
+        }
+        match self.request("parse_batch", sql, dialect) {
+            Response::Accepted { statements, .. } => statements,
+            _ => None,
+        }
+    }
+    fn can_batch(&self) -> bool {
+        self.batch
+    }
+    /// The adapter's allocations happen in another process.
+    fn measure_mem(&self, _sql: &str, _dialect: Dialect) -> Option<(usize, usize)> {
+        None
+    }
+    fn reprint(&self, sql: &str, dialect: Dialect) -> Option<String> {
+        if !self.can_reprint(dialect) {
+            return None;
+        }
+        match self.request("reprint", sql, dialect) {
+            Response::Accepted { sql, .. } => sql,
+            _ => None,
+        }
+    }
+    fn can_reprint(&self, dialect: Dialect) -> bool {
+        self.reprint && self.supports(dialect)
+    }
+}
+
+/// Read a manifest and start every adapter it declares.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or parsed, a name repeats, or an
+/// adapter cannot be started.
+pub fn load(path: &Path) -> Result<Vec<SubprocessParser>, String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))?;
+    let manifest: Manifest =
+        toml::from_str(&text).map_err(|e| format!("parsing {}: {e}", path.display()))?;
+    let mut names = std::collections::HashSet::new();
+    for spec in &manifest.parser {
+        if !names.insert(spec.name.as_str()) {
+            return Err(format!("duplicate parser name `{}`", spec.name));
+        }
+    }
+    manifest
+        .parser
+        .into_iter()
+        .map(SubprocessParser::start)
+        .collect()
+}
+
+/// The parsers declared in [`MANIFEST`], started and ready, or none if there is
+/// no manifest.
+///
+/// # Panics
+///
+/// Panics with the load error if the manifest exists but is malformed or an
+/// adapter fails to start, rather than silently grading without it.
+#[must_use]
+pub fn external_parsers() -> Vec<SubprocessParser> {
+    let path = Path::new(MANIFEST);
+    if !path.exists() {
+        return Vec::new();
+    }
+    load(path).unwrap_or_else(|e| panic!("external parsers: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Manifest, Response, Spec, SubprocessParser};
+    use crate::datasets::Dialect;
+    use crate::diagnostics::ErrorLocation;
+    use crate::{ParseOutcome, Parser};
+
+    #[test]
+    fn responses_map_to_outcomes() {
+        let parse = |s: &str| serde_json::from_str::<Response>(s).unwrap().outcome();
+        assert_eq!(parse(r#"{"outcome":"accepted"}"#), ParseOutcome::Accepted);
+        assert_eq!(
+            parse(r#"{"outcome":"unsupported"}"#),
+            ParseOutcome::Unsupported
+        );
+        let ParseOutcome::Rejected(e) =
+            parse(r#"{"outcome":"rejected","error":"bad","line":1,"column":8}"#)
+        else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(e.message, "bad");
+        assert_eq!(
+            e.location,
+            Some(ErrorLocation::LineCol { line: 1, column: 8 })
+        );
+        assert_eq!(
+            parse(r#"{"outcome":"panicked","error":"boom"}"#),
+            ParseOutcome::Panicked("boom".to_string())
+        );
+        assert!(serde_json::from_str::<Response>(r#"{"outcome":"maybe"}"#).is_err());
+    }
+
+    /// An adapter in a few lines of shell: accepts statements starting with
+    /// SELECT, rejects the rest at offset 0, exits on `EXIT` and spins forever
+    /// on `HANG`.
+    #[cfg(unix)]
+    fn shell_adapter() -> SubprocessParser {
+        let script = r#"while IFS= read -r line; do
+  case "$line" in
+    *'"sql":"SELECT'*) echo '{"outcome":"accepted"}' ;;
+    *'"sql":"EXIT'*) exit 0 ;;
+    *'"sql":"HANG'*) while :; do :; done ;;
+    *) echo '{"outcome":"rejected","error":"not a select","offset":0}' ;;
+  esac
+done"#;
+        SubprocessParser::start(Spec {
+            name: "shell".to_string(),
+            version: "0".to_string(),
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            dialects: vec!["multi".to_string()],
+            reprint: false,
+            batch: false,
+            timeout_secs: 1,
+        })
+        .expect("sh is available")
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn adapter_is_graded_like_an_in_process_parser() {
+        let p = shell_adapter();
+        assert_eq!(p.accepts("SELECT 1", Dialect::Multi), Some(true));
+        assert_eq!(p.accepts("DROP t", Dialect::Multi), Some(false));
+        assert_eq!(p.accepts("SELECT 1", Dialect::Postgresql), None);
+        // A dead adapter is a panic for that statement and restarts for the next.
+        assert!(matches!(
+            p.parse_outcome("EXIT", Dialect::Multi),
+            ParseOutcome::Panicked(_)
+        ));
+        assert_eq!(p.accepts("SELECT 2", Dialect::Multi), Some(true));
+        // So is a hung one.
+        assert_eq!(
+            p.parse_outcome("HANG", Dialect::Multi),
+            ParseOutcome::Panicked("timeout".to_string())
+        );
+        assert_eq!(p.accepts("SELECT 3", Dialect::Multi), Some(true));
+        assert_eq!(p.id().family, "shell");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn an_adapter_that_never_answers_times_out() {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [[parser]]
+            name = "silent"
+            command = ["sh", "-c", "cat >/dev/null"]
+            dialects = ["multi"]
+            timeout_secs = 1
+            "#,
+        )
+        .unwrap();
+        let spec = manifest.parser.into_iter().next().unwrap();
+        let p = SubprocessParser::start(spec).expect("sh is available");
+        for _ in 0..2 {
+            let start = std::time::Instant::now();
+            assert_eq!(
+                p.parse_outcome("SELECT 1", Dialect::Multi),
+                ParseOutcome::Panicked("timeout".to_string())
+            );
+            assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        }
+    }
+
+    #[test]
+    fn unknown_dialects_are_rejected_at_load() {
+        let err = SubprocessParser::start(Spec {
+            name: "x".to_string(),
+            version: String::new(),
+            command: vec!["true".to_string()],
+            dialects: vec!["klingon".to_string()],
+            reprint: false,
+            batch: false,
+            timeout_secs: 1,
+        })
+        .err()
+        .unwrap();
+        assert!(err.contains("unknown dialect `klingon`"));
+    }
+}
@@ -17,12 +17,12 @@ macro_rules! orql_version {
                 sql: &str,
                 dialect: Dialect,
             ) -> sql_ast_benchmark::ParseOutcome {
-                use sql_ast_benchmark::ParseOutcome;
+                use sql_ast_benchmark::{diagnostics::ParseError, ParseOutcome};
                 match self.try_parse(sql, dialect) {
                     None => ParseOutcome::Unsupported,
                     Some(Ok(())) => ParseOutcome::Accepted,
                     Some(Err(e)) if e == "panicked" => ParseOutcome::Panicked(e),
-                    Some(Err(e)) => ParseOutcome::Rejected(e),
+                    Some(Err(e)) => ParseOutcome::Rejected(ParseError::new(e)),
                 }
             }
 
@@ -172,6 +172,29 @@ fn metrics_of(report: &report::DialectReport) -> ParserMetrics {
         attempted: s.attempted,
         panicked: s.panicked,
         panic_pct: pct(s.panicked, s.attempted),
+        // Historical versions expose no normalized tree, so this stays None.
+        ast_agreement_pct: pct(s.ast_agreed, s.ast_compared),
+        // Historical adapters report plain messages, so locations come only from
+        // what the message text spells out.
+        error_location_pct: pct(s.error_located, s.rejected_invalid),
+        error_position_pct: pct(s.error_near, s.error_scored),
+        // Historical versions expose no spans, so this stays None.
+        span_accuracy_pct: pct(s.spans_correct, s.spans_checked),
+        // Historical versions report no statement positions, so these stay None.
+        split_precision_pct: s
+            .split
+            .and_then(|t| pct(t.overall().correct, t.overall().returned)),
+        split_recall_pct: s
+            .split
+            .and_then(|t| pct(t.overall().correct, t.overall().expected)),
+        split_compound_recall_pct: s
+            .split
+            .and_then(|t| pct(t.compound.correct, t.compound.expected)),
+        split_delimiter_recall_pct: s
+            .split
+            .and_then(|t| pct(t.delimiter.correct, t.delimiter.expected)),
+        // The history tracks the headline numbers only.
+        by_kind: Vec::new(),
     }
 }
 
@@ -14,7 +14,7 @@
 
 use crate::color::parser_rgb;
 use crate::marker::{marker_for, Marker};
-use crate::schema::{DialectData, ParserMetrics, ParserPerf};
+use crate::schema::{DialectData, ParserMetrics, ParserPerf, Scaling};
 use plotters::prelude::*;
 use plotters::style::RGBColor;
 
@@ -372,6 +372,95 @@ pub fn box_svg(d: &DialectData, w: u32, h: u32) -> String {
     )
 }
 
+/// Size-scaling chart for one dialect: x = statement bytes, y = median parse
+/// time, both log, one line per parser with timings that carry sizes.
+///
+/// On log-log axes a parser whose time grows in proportion to size runs
+/// parallel to the others, and a steeper line is super-linear. The legend
+/// gives each parser's fitted exponent and ns per byte.
+#[must_use]
+pub fn scaling_svg(d: &DialectData, w: u32, h: u32) -> String {
+    let series: Vec<(&ParserPerf, &Scaling)> = d
+        .perf
+        .iter()
+        .filter_map(|p| p.scaling.as_ref().map(|s| (p, s)))
+        .filter(|(_, s)| !s.curve.is_empty())
+        .collect();
+    let legend: Vec<Line> = series
+        .iter()
+        .map(|(p, s)| Line {
+            label: p.parser.clone(),
+            rgb: parser_rgb(&p.parser),
+            sub: Some(format!(
+                "exp {}   {:.1} ns/B",
+                s.exponent
+                    .map_or_else(|| "n/a".to_string(), |e| format!("{e:.2}")),
+                s.ns_per_byte.median
+            )),
+            min: 0.0,
+            p10: 0.0,
+            p25: 0.0,
+            median: 0.0,
+            p75: 0.0,
+            p90: 0.0,
+            p99: 0.0,
+            ecdf: Vec::new(),
+        })
+        .collect();
+
+    let mut buf = String::new();
+    {
+        let root = SVGBackend::with_string(&mut buf, (w, h)).into_drawing_area();
+        let _: Res = (|| {
+            root.fill(&WHITE)?;
+            let (plot, legend_area) = root.split_horizontally(w as i32 - legend_width(&legend));
+
+            let pts = series.iter().flat_map(|(_, s)| s.curve.iter());
+            let (mut xmin, mut xmax, mut ymin, mut ymax) = (f64::MAX, 0.0_f64, f64::MAX, 0.0_f64);
+            for pt in pts.filter(|pt| pt[0] > 0.0 && pt[1] > 0.0) {
+                xmin = xmin.min(pt[0]);
+                xmax = xmax.max(pt[0]);
+                ymin = ymin.min(pt[1]);
+                ymax = ymax.max(pt[1]);
+            }
+            if xmax <= 0.0 || ymax <= 0.0 {
+                return Ok(()); // no sized timings
+            }
+            let (xlo, xhi) = (xmin * 0.8, (xmax * 1.25).max(xmin * 10.0));
+            let (ylo, yhi) = (ymin * 0.8, (ymax * 1.25).max(ymin * 10.0));
+
+            let mut chart = ChartBuilder::on(&plot)
+                .caption(dialect_title(d), ("sans-serif", 16))
+                .margin(10)
+                .x_label_area_size(34)
+                .y_label_area_size(52)
+                .build_cartesian_2d((xlo..xhi).log_scale(), (ylo..yhi).log_scale())?;
+            chart
+                .configure_mesh()
+                .x_desc("bytes / statement")
+                .y_desc("median ns")
+                .x_label_style(("sans-serif", 11))
+                .y_label_style(("sans-serif", 11))
+                .draw()?;
+            for (p, s) in &series {
+                let c = rgb(parser_rgb(&p.parser));
+                chart.draw_series(LineSeries::new(
+                    s.curve.iter().map(|pt| (pt[0], pt[1])),
+                    c.stroke_width(2),
+                ))?;
+                let m = marker_for(&p.parser);
+                for pt in s.curve.iter().step_by(4) {
+                    draw_marker(&mut chart, m, pt[0], pt[1], c)?;
+                }
+            }
+            draw_legend(&legend_area, &legend)?;
+            root.present()?;
+            Ok(())
+        })();
+    }
+    buf
+}
+
 /// Build chart [`Line`]s from a labelled set of memory distributions (no eCDF
 /// sub-label), for the per-parser or per-dialect memory charts.
 #[must_use]
@@ -655,8 +744,8 @@ fn linear_trend(
 
 #[cfg(test)]
 mod tests {
-    use super::{box_svg, ecdf_svg};
-    use crate::schema::{CoverageMatrix, DialectData, ParserPerf};
+    use super::{box_svg, ecdf_svg, scaling_svg};
+    use crate::schema::{CoverageMatrix, DialectData, MemDist, ParserPerf, Scaling};
 
     fn sample() -> DialectData {
         let perf = ParserPerf {
@@ -677,6 +766,8 @@ mod tests {
             ecdf: (0..50)
                 .map(|i| [300.0 + f64::from(i) * 100.0, f64::from(i) / 49.0])
                 .collect(),
+            by_kind: vec![],
+            scaling: None,
         };
         DialectData {
             dir_name: "postgresql".to_string(),
@@ -687,6 +778,7 @@ mod tests {
             contentious_valid: 0,
             correctness: vec![],
             perf: vec![perf],
+            perf_common: vec![],
             coverage: CoverageMatrix {
                 parsers: vec![],
                 files: vec![],
@@ -694,8 +786,16 @@ mod tests {
                 subtotal_accepted: vec![],
             },
             failures: vec![],
+            failure_clusters: vec![],
             memory: vec![],
+            memory_common: vec![],
             batch: vec![],
+            placeholders: None,
+            constructs: None,
+            recovery: vec![],
+            lex: vec![],
+            edit: vec![],
+            disagreements: vec![],
         }
     }
 
@@ -713,4 +813,36 @@ mod tests {
             assert!(svg.len() > 500);
         }
     }
+
+    #[test]
+    fn scaling_chart_draws_only_parsers_with_sized_timings() {
+        let flat = |v: f64| MemDist {
+            min: v,
+            p10: v,
+            p25: v,
+            median: v,
+            p75: v,
+            p90: v,
+            p99: v,
+            max: v,
+            mean: v,
+            std: 0.0,
+            ecdf: vec![],
+        };
+        let mut d = sample();
+        // Older snapshots carry no sizes: the chart is left blank.
+        assert!(!scaling_svg(&d, 760, 420).contains("exp "));
+        d.perf[0].scaling = Some(Scaling {
+            n: 3,
+            ns_per_byte: flat(4.0),
+            ns_per_token: flat(20.0),
+            slope_ns_per_byte: Some(4.0),
+            slope_ns_per_token: Some(20.0),
+            exponent: Some(1.0),
+            curve: vec![[20.0, 80.0], [200.0, 800.0], [2000.0, 8000.0]],
+        });
+        let svg = scaling_svg(&d, 760, 420);
+        assert!(svg.contains("</svg>"));
+        assert!(svg.contains("exp 1.00"));
+    }
 }
@@ -15,12 +15,15 @@ pub mod schema;
 
 pub use chart::{
     box_lines, box_svg, count_trend_lines, ecdf_lines, ecdf_svg, mem_line, pct_trend_lines,
-    trend_lines, year_frac, Line, TrendSeries,
+    scaling_svg, trend_lines, year_frac, Line, TrendSeries,
 };
 pub use color::{parser_hex, parser_rgb};
 pub use marker::{marker_for, Marker};
 pub use schema::{
-    Bundle, CoverageFile, CoverageMatrix, DepthReport, DepthScan, DialectData, DialectDelta,
-    DialectRun, FamilyHistory, FeatureCounts, FeatureScan, LintPolicy, MemDist, ParserBatch,
-    ParserFailures, ParserFeatures, ParserMem, ParserMetrics, ParserPerf, RuleMeta, VersionRun,
+    Bundle, ConstructColumn, ConstructMatrix, CoverageFile, CoverageMatrix, DepthReport, DepthScan,
+    DialectData, DialectDelta, DialectRun, DisagreementSample, EditKindTime, FailureCluster,
+    FamilyHistory, FeatureCounts, FeatureScan, KindPerf, KindRecall, KnownGap, LintPolicy, MemDist,
+    PairDisagreement, ParserBatch, ParserConstructs, ParserEdit, ParserFailureClusters,
+    ParserFailures, ParserFeatures, ParserFuzz, ParserLex, ParserMem, ParserMetrics, ParserPerf,
+    ParserPlaceholders, ParserRecovery, PlaceholderMatrix, RuleMeta, Scaling, VersionRun,
 };
@@ -57,6 +57,7 @@ pub fn marker_for(name: &str) -> Marker {
         "sqlglot-rust" => Marker::Pentagon,
         "sqlite3-parser" => Marker::Hexagon,
         "turso_parser" => Marker::Star,
+        "tree-sitter-sql" => Marker::TriangleLeft,
         "orql" => Marker::Cross,
 
         // Dialects (display names, as carried in the chart series label).
@@ -177,6 +178,7 @@ mod tests {
             "sqlglot-rust",
             "sqlite3-parser",
             "turso_parser",
+            "tree-sitter-sql",
             "orql",
         ];
         let mut seen: Vec<Marker> = parsers.iter().map(|p| marker_for(p)).collect();
@@ -127,12 +127,44 @@ pub struct Bundle {
     pub git_commit: Option<String>,
     /// All parser display names that appear anywhere, in palette order.
     pub parsers: Vec<String>,
+    /// Parsers that ran out of process through the subprocess adapter protocol
+    /// (graded but not timed). Empty in older snapshots.
+    #[serde(default)]
+    pub out_of_process: Vec<String>,
     /// One entry per dialect, in display order.
     pub dialects: Vec<DialectData>,
     /// Display metadata for every contentious-construct rule, so the viewer's
     /// legend and per-row badges are data-driven (empty in older snapshots).
     #[serde(default)]
     pub contentious_rules: Vec<RuleMeta>,
+    /// Every known-gap entry (rejections reported upstream), so the failures
+    /// view can link and label them (empty in older snapshots).
+    #[serde(default)]
+    pub known_gaps: Vec<KnownGap>,
+    /// Per-parser mutation-fuzzing results from the committed
+    /// `fuzz/summary.tsv` (empty in older snapshots or before a fuzz run).
+    #[serde(default)]
+    pub fuzz: Vec<ParserFuzz>,
+}
+
+/// Mutation-fuzzing robustness for one parser, over every dialect it models.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserFuzz {
+    pub parser: String,
+    /// Mutated inputs parsed.
+    pub inputs: usize,
+    /// Inputs that made the parser panic (caught).
+    pub panics: usize,
+    /// Inputs the parser had not finished after the fuzzer's timeout (absent
+    /// in older snapshots).
+    #[serde(default)]
+    pub hangs: usize,
+    /// Distinct crashers committed under `fuzz/crashers/`: one minimized
+    /// input per panic, plus the first hang.
+    pub crashers: usize,
+    /// Panics and hangs per million inputs. `None` when nothing was fuzzed.
+    #[serde(alias = "panics_per_million")]
+    pub failures_per_million: Option<f64>,
 }
 
 /// Display metadata for one contentious-construct rule, copied into the export so
@@ -148,6 +180,25 @@ pub struct RuleMeta {
     pub references: Vec<String>,
 }
 
+/// Display metadata for one known-gap entry: a class of rejections already
+/// reported to the parser's upstream.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KnownGap {
+    pub id: String,
+    pub title: String,
+    /// Parser family the report is against.
+    pub parser: String,
+    /// The upstream issue or PR.
+    pub url: String,
+    /// `open`, `fixed` or `wontfix`.
+    pub status: String,
+    /// The first release with the fix, for a `fixed` entry.
+    pub fixed_in: Option<String>,
+    /// Whether the benchmarked build is at or past `fixed_in`, so any
+    /// rejection still tagged with this entry should not be happening.
+    pub fixed_by_current: bool,
+}
+
 /// Everything the viewer shows for one dialect.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DialectData {
@@ -165,18 +216,126 @@ pub struct DialectData {
     pub correctness: Vec<ParserMetrics>,
     /// Per-parser timing distribution (percentiles + downsampled eCDF).
     pub perf: Vec<ParserPerf>,
+    /// Per-parser timing over the common subset, the statements every
+    /// participating parser accepts, so the distributions compare like with
+    /// like. Empty in older snapshots and when the common-subset bench has not
+    /// been run.
+    #[serde(default)]
+    pub perf_common: Vec<ParserPerf>,
     /// Per-file acceptance matrix.
     pub coverage: CoverageMatrix,
     /// Per-parser rejected-statement previews and download info.
     #[serde(default)]
     pub failures: Vec<ParserFailures>,
+    /// Per-parser rejections grouped by normalized error, largest groups first.
+    #[serde(default)]
+    pub failure_clusters: Vec<ParserFailureClusters>,
     /// Per-parser memory distribution (peak and retained bytes per statement).
     #[serde(default)]
     pub memory: Vec<ParserMem>,
+    /// Per-parser memory over the same common subset as `perf_common`.
+    #[serde(default)]
+    pub memory_common: Vec<ParserMem>,
     /// Per-parser whole-script (batch) results: the cost of parsing the whole
     /// accepted set as one script, normalized per statement.
     #[serde(default)]
     pub batch: Vec<ParserBatch>,
+    /// Which bind-parameter syntaxes each parser accepts. `None` in older
+    /// snapshots and for a dialect whose corpus has no usable template.
+    #[serde(default)]
+    pub placeholders: Option<PlaceholderMatrix>,
+    /// Recall per SQL construct (the feature heat map). `None` in older
+    /// snapshots and for a dialect whose corpus uses no tagged construct.
+    #[serde(default)]
+    pub constructs: Option<ConstructMatrix>,
+    /// Per-parser error recovery: how much of a script with one bad statement
+    /// still comes back. Empty for a dialect without a reference engine.
+    #[serde(default)]
+    pub recovery: Vec<ParserRecovery>,
+    /// Per-parser lex-only timing, for the parsers with a standalone lexer.
+    #[serde(default)]
+    pub lex: Vec<ParserLex>,
+    /// Per-parser edit-reparse timing: the cost of parsing a statement again
+    /// after a single-token edit, the workload an editor runs.
+    #[serde(default)]
+    pub edit: Vec<ParserEdit>,
+    /// Every pair of parsers with the statements one accepts and the other
+    /// rejects: what a migration between the two would have to re-check.
+    /// Empty in older snapshots.
+    #[serde(default)]
+    pub disagreements: Vec<PairDisagreement>,
+}
+
+/// Where two parsers part ways on one dialect's corpus.
+///
+/// Counted over every statement, valid or not, so `only_a` is what stops
+/// parsing when moving from `a` to `b` and `only_b` what starts to.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PairDisagreement {
+    pub a: String,
+    pub b: String,
+    /// Statements `a` accepts and `b` rejects.
+    pub only_a: usize,
+    /// Statements `b` accepts and `a` rejects.
+    pub only_b: usize,
+    /// Statements both accept.
+    pub both: usize,
+    /// The first few statements counted in `only_a`, in corpus order.
+    pub samples_a: Vec<DisagreementSample>,
+    /// As `samples_a`, for `only_b`.
+    pub samples_b: Vec<DisagreementSample>,
+}
+
+/// A statement one parser of a pair accepts and the other rejects.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DisagreementSample {
+    pub sql: String,
+    /// The reference engine's verdict, `None` without one.
+    pub reference: Option<bool>,
+}
+
+/// Lex-only timing for one parser in one dialect.
+///
+/// The parser's standalone lexer is timed on the same accepted statements as
+/// its [`ParserPerf`], with the same harness, so the two medians split the
+/// parse time into lexing and the rest.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserLex {
+    pub parser: String,
+    /// Statements timed.
+    pub n: usize,
+    /// Lex time distribution (ns per statement).
+    pub time: MemDist,
+}
+
+/// Edit-reparse timing for one parser in one dialect.
+///
+/// A sample of the accepted statements is edited deterministically (rename an
+/// identifier, change a literal, insert a predicate) and each edited statement
+/// is timed with the same adaptive harness as [`ParserPerf`]. Parsers with an
+/// incremental API reparse against the statement's old tree, the rest parse
+/// the edited text from scratch, so comparing `time` with the cold
+/// [`ParserPerf`] shows what reuse buys.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserEdit {
+    pub parser: String,
+    /// Whether the reparse reused the previous tree (tree-sitter).
+    pub incremental: bool,
+    /// Edits timed, over every kind.
+    pub n: usize,
+    /// Reparse time distribution (ns per edit), over every kind.
+    pub time: MemDist,
+    /// Median reparse time per edit kind, in the bench's kind order.
+    pub kinds: Vec<EditKindTime>,
+}
+
+/// Median reparse time for one kind of edit.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EditKindTime {
+    /// `rename`, `literal` or `predicate`.
+    pub kind: String,
+    pub n: usize,
+    pub median: f64,
 }
 
 /// Whole-script (batch) parse results for one parser in one dialect.
@@ -226,7 +385,8 @@ pub struct ParserMem {
 }
 
 /// A byte distribution: the same percentile set as [`ParserPerf`], in bytes,
-/// plus a downsampled empirical CDF for charting.
+/// plus a downsampled empirical CDF for charting. [`ParserEdit`] reuses it for
+/// nanoseconds and [`Scaling`] for nanoseconds per byte and per token.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct MemDist {
     pub min: f64,
@@ -260,6 +420,14 @@ pub struct ParserFailures {
     /// the UI can show "N of M rejected".
     #[serde(default)]
     pub expected_total: usize,
+    /// Rejections tagged with a known upstream issue ([`KnownGap`]). The rest
+    /// are untriaged.
+    #[serde(default)]
+    pub known_total: usize,
+    /// Tagged rejections whose entry is fixed in the benchmarked build or
+    /// earlier: a regression, or a wrong entry.
+    #[serde(default)]
+    pub stale_total: usize,
     /// A handful of example rejected statements, pre-rendered to static
     /// syntax-highlighted HTML at export time so the viewer ships no runtime
     /// highlighter. Each entry is the inner HTML of one `<pre>` block.
@@ -280,11 +448,55 @@ pub struct ParserFailures {
     /// older snapshots.
     #[serde(default)]
     pub preview_tags: Vec<Option<String>>,
+    /// A minimized reproducer for each previewed statement, aligned with
+    /// `preview_html` (same index): a much shorter statement that still
+    /// fails the same way, or `None` where none was found. Empty in older
+    /// snapshots.
+    #[serde(default)]
+    pub preview_minimized: Vec<Option<String>>,
+    /// The [`KnownGap`] id tagging each previewed statement, aligned with
+    /// `preview_html` (same index), or `None` for an untriaged one. Empty in
+    /// older snapshots.
+    #[serde(default)]
+    pub preview_issues: Vec<Option<String>>,
     /// Path (relative to the site root) of the full `.tsv.zst` download, or
     /// `None` when there were no failures to ship.
     pub download: Option<String>,
 }
 
+/// One parser's rejections in one dialect, grouped by error signature so the
+/// viewer can say "412 failures: `WINDOW` unexpected" instead of listing them.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserFailureClusters {
+    pub parser: String,
+    /// All rejections clustered (not capped like the download).
+    pub rejected_total: usize,
+    /// The largest clusters, biggest first.
+    pub clusters: Vec<FailureCluster>,
+}
+
+/// Rejections that share a normalized error message, statement head and shape.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FailureCluster {
+    /// The error message with identifiers as `?` and numbers as `#`.
+    pub message: String,
+    /// The keywords the statements start with (`SELECT`, `CREATE TABLE`).
+    pub head: String,
+    /// The statements' keyword layout, other tokens as `?` (`SELECT ? FROM
+    /// ?`). Empty in older snapshots.
+    #[serde(default)]
+    pub shape: String,
+    /// Rejections in the cluster.
+    pub count: usize,
+    /// The shortest member statement.
+    pub exemplar: String,
+    /// The parser's own error message for the exemplar.
+    pub exemplar_reason: String,
+    /// The [`KnownGap`] id tagging the exemplar, if any.
+    #[serde(default)]
+    pub issue: Option<String>,
+}
+
 /// Correctness metrics for one parser in one dialect. Percentages are
 /// precomputed as `Option<f64>` so the viewer does pure formatting (None = N/A),
 /// matching the CLI's semantics.
@@ -330,6 +542,67 @@ pub struct ParserMetrics {
     /// dialect. `None` when nothing was attempted or the value is unmeasured.
     #[serde(default)]
     pub panic_pct: Option<f64>,
+    /// Reference dialects: share of accepted-valid statements whose normalized
+    /// tree (statement kind, tables, projections, joins, predicates) agrees with
+    /// the reference parser's, among statements where both trees lower to more
+    /// than unmodelled constructs. `None` for the reference parser itself, for
+    /// parsers that expose no tree, on provenance dialects, and in older
+    /// snapshots.
+    #[serde(default)]
+    pub ast_agreement_pct: Option<f64>,
+    /// Reference dialects: share of the parser's rejections of invalid
+    /// statements whose error carries a location (byte offset or line and
+    /// column). `None` on provenance dialects, when nothing invalid was
+    /// rejected, and in older snapshots.
+    #[serde(default)]
+    pub error_location_pct: Option<f64>,
+    /// Reference dialects: share of located rejections whose position lies
+    /// within a token's width of the reference parser's. `None` for the
+    /// reference parser itself, when no located rejection could be scored, and
+    /// in older snapshots.
+    #[serde(default)]
+    pub error_position_pct: Option<f64>,
+    /// Share of the identifier and literal spans in the parser's trees of
+    /// accepted valid statements that slice back to their token in the source.
+    /// `None` for parsers that attach no spans to their AST and in older
+    /// snapshots.
+    #[serde(default)]
+    pub span_accuracy_pct: Option<f64>,
+    /// Statement-splitter precision: of the boundaries between the statements
+    /// the parser returned for scripts of statements it accepts, the share
+    /// that fall on a real terminator. `None` for parsers that do not report
+    /// where their statements are and in older snapshots.
+    #[serde(default)]
+    pub split_precision_pct: Option<f64>,
+    /// Statement-splitter recall: the share of the scripts' real terminators
+    /// the parser's boundaries found.
+    #[serde(default)]
+    pub split_recall_pct: Option<f64>,
+    /// Splitter recall over the scripts with compound statements (dollar
+    /// quotes, trigger and `BEGIN ... END` bodies), the part of
+    /// `split_recall_pct` a naive split on `;` gets wrong.
+    #[serde(default)]
+    pub split_compound_recall_pct: Option<f64>,
+    /// MySQL only: splitter recall over compound statements in a
+    /// `DELIMITER //` block.
+    #[serde(default)]
+    pub split_delimiter_recall_pct: Option<f64>,
+    /// Recall (acceptance on provenance dialects) per statement kind, for the
+    /// kinds with at least one valid statement. Empty in older snapshots and
+    /// in the time-machine history.
+    #[serde(default)]
+    pub by_kind: Vec<KindRecall>,
+}
+
+/// Recall over the valid statements of one kind (`SELECT`, `CREATE TABLE`,
+/// transaction control, ...).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KindRecall {
+    /// Display label of the statement kind.
+    pub kind: String,
+    pub valid: usize,
+    pub accepted: usize,
+    pub recall_pct: Option<f64>,
 }
 
 /// Timing distribution for one parser in one dialect.
@@ -355,6 +628,127 @@ pub struct ParserPerf {
     pub roundtrip_pct: Option<f64>,
     /// Downsampled empirical CDF: `[ns, fraction]` points, ascending.
     pub ecdf: Vec<[f64; 2]>,
+    /// Parse-time percentiles per statement kind, for the kinds with at least
+    /// one accepted statement. Empty in older snapshots and in the
+    /// time-machine history.
+    #[serde(default)]
+    pub by_kind: Vec<KindPerf>,
+    /// Parse time against statement size. `None` in older snapshots, in the
+    /// time-machine history, and when the timings carry no sizes.
+    #[serde(default)]
+    pub scaling: Option<Scaling>,
+}
+
+/// How one parser's parse time grows with statement size.
+///
+/// Sizes are byte lengths and token counts from one shared lexer, so a parser
+/// that happens to accept longer statements is not penalized for it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Scaling {
+    /// Statements timed.
+    pub n: usize,
+    /// Per-statement parse time divided by byte length.
+    pub ns_per_byte: MemDist,
+    /// Per-statement parse time divided by token count (no eCDF).
+    pub ns_per_token: MemDist,
+    /// Least-squares slope of time against byte length: ns per extra byte.
+    /// `None` (null) when no line fits, as when every statement has the same
+    /// size.
+    pub slope_ns_per_byte: Option<f64>,
+    /// Least-squares slope of time against token count: ns per extra token.
+    /// `None` as for `slope_ns_per_byte`.
+    pub slope_ns_per_token: Option<f64>,
+    /// Slope of log time against log byte length: about 1 when time grows in
+    /// proportion to size, above 1 when it grows faster (super-linear).
+    /// `None` as for `slope_ns_per_byte`.
+    pub exponent: Option<f64>,
+    /// Time against size: `[median bytes, median ns]` per equal-count size
+    /// bin, ascending.
+    pub curve: Vec<[f64; 2]>,
+}
+
+/// Parse-time percentiles over the accepted statements of one kind.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KindPerf {
+    /// Display label of the statement kind.
+    pub kind: String,
+    pub n: usize,
+    pub p10: f64,
+    pub median: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+/// Placeholder-style acceptance for one dialect.
+///
+/// Corpus data statements have their last literal replaced by a placeholder in
+/// each style. A parser's cell is the share of the templates it accepts with
+/// the literal that it still accepts with the placeholder.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlaceholderMatrix {
+    /// Column order: placeholder styles as written (`$1`, `?`, `:name`, ...).
+    pub styles: Vec<String>,
+    /// Templates probed.
+    pub templates: usize,
+    pub parsers: Vec<ParserPlaceholders>,
+}
+
+/// One parser's row of a [`PlaceholderMatrix`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserPlaceholders {
+    pub parser: String,
+    /// Templates accepted with the literal in place (the denominator).
+    pub base: usize,
+    /// Percent of `base` accepted with each style, same order as `styles`.
+    /// `None` when `base` is zero.
+    pub accepted_pct: Vec<Option<f64>>,
+}
+
+/// Recall per SQL construct for one dialect, the feature heat map.
+///
+/// Valid statements are tagged with the constructs they use (CTE, window
+/// function, `MERGE`, ...). A parser's cell is the share of a construct's
+/// valid statements it accepts.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConstructMatrix {
+    /// Column order: one entry per construct found in the corpus.
+    pub constructs: Vec<ConstructColumn>,
+    pub parsers: Vec<ParserConstructs>,
+}
+
+/// One column of a [`ConstructMatrix`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConstructColumn {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    /// Valid statements using the construct (the recall denominator).
+    pub valid: usize,
+}
+
+/// One parser's row of a [`ConstructMatrix`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserConstructs {
+    pub parser: String,
+    /// Percent of each construct's valid statements accepted, same order as
+    /// `constructs`.
+    pub recall_pct: Vec<Option<f64>>,
+}
+
+/// Error recovery for one parser in one dialect.
+///
+/// Scripts of accepted statements have one reference-invalid statement
+/// planted in them. The percentages are `None` (shown as N/A) for a parser
+/// with no way to keep going past an error.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ParserRecovery {
+    pub parser: String,
+    /// Scripts parsed.
+    pub scripts: usize,
+    /// Percent of the valid statements in the scripts that came back.
+    pub recovered_pct: Option<f64>,
+    /// Percent of scripts from which no valid statement came back.
+    pub gave_up_pct: Option<f64>,
 }
 
 /// Per-file acceptance matrix for one dialect.
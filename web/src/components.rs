@@ -113,6 +113,7 @@ fn parser_monogram(name: &str) -> &'static str {
         "sqlglot-rust" => "sg",
         "sqlite3-parser" => "s3",
         "turso_parser" => "tu",
+        "tree-sitter-sql" => "ts",
         _ => "sql",
     }
 }
@@ -348,7 +349,7 @@ pub fn Overview() -> Element {
                 {rich_text("Choosing a SQL parser for a Rust project means weighing dialect coverage, correctness, and speed, yet those trade-offs are seldom measured on realistic input. We benchmarked the actively maintained Rust SQL parsers on a large, multi-dialect corpus of real-world statements so the choice can rest on evidence rather than on each library's own claims.").into_iter()}
             }
             p { class: "blurb",
-                {rich_text(&format!("We evaluated nine parser libraries: [sqlparser-rs](https://github.com/sqlparser-rs/sqlparser-rs) (Apache DataFusion), [pg_query.rs](https://github.com/pganalyze/pg_query.rs) and its faster summary mode (Rust bindings to [libpg_query](https://github.com/pganalyze/libpg_query), PostgreSQL's own parser), [databend-common-ast](https://crates.io/crates/databend-common-ast), [polyglot-sql](https://github.com/tobilg/polyglot), [sqlglot-rust](https://crates.io/crates/sqlglot-rust), [qusql-parse](https://crates.io/crates/qusql-parse), [sqlite3-parser](https://crates.io/crates/sqlite3-parser) (lemon-rs), and [turso_parser](https://crates.io/crates/turso_parser) (the SQLite parser from Turso), plus [orql](https://codeberg.org/xitep/orql) on Oracle. We ran them against a corpus of 340,938 statements spanning these {} dialects, drawn from each engine's own regression suites and official samples and committed compressed so every run is reproducible.", b.dialects.len())).into_iter()}
+                {rich_text(&format!("We evaluated ten parser libraries: [sqlparser-rs](https://github.com/sqlparser-rs/sqlparser-rs) (Apache DataFusion), [pg_query.rs](https://github.com/pganalyze/pg_query.rs) and its faster summary mode (Rust bindings to [libpg_query](https://github.com/pganalyze/libpg_query), PostgreSQL's own parser), [databend-common-ast](https://crates.io/crates/databend-common-ast), [polyglot-sql](https://github.com/tobilg/polyglot), [sqlglot-rust](https://crates.io/crates/sqlglot-rust), [qusql-parse](https://crates.io/crates/qusql-parse), [sqlite3-parser](https://crates.io/crates/sqlite3-parser) (lemon-rs), [turso_parser](https://crates.io/crates/turso_parser) (the SQLite parser from Turso), and the [tree-sitter-sql](https://github.com/DerekStride/tree-sitter-sql) grammar editors use, plus [orql](https://codeberg.org/xitep/orql) on Oracle. We ran them against a corpus of 340,938 statements spanning these {} dialects, drawn from each engine's own regression suites and official samples and committed compressed so every run is reproducible.", b.dialects.len())).into_iter()}
             }
             p { class: "blurb",
                 {rich_text("We exercised each parser in the dialect that matches the corpus under test. Where a dialect has a runnable engine, we labelled each statement valid or invalid with the real database engine itself, run in Docker via [testcontainers](https://github.com/testcontainers/testcontainers-rs): a statement counts as valid unless the engine reports a syntax error, so a missing table or column still counts as parsed. Against that ground truth we scored the parsers on recall (valid statements accepted), false positives (invalid statements wrongly accepted), and display round-trip stability. The other dialects have no runnable engine, so their statements count as provenance-valid and the metric is simply the acceptance rate. Across all dialects, we captured speed as a per-statement parse-time distribution over every accepted statement, and memory as the peak and retained bytes per statement under a counting allocator. A batch axis additionally parses each parser's whole accepted set as a single script, showing what bulk parsing amortizes, and a time machine benchmarks the historical releases of every pure-Rust parser (more than 80 versions in total, reaching back to sqlparser-rs 0.6 in mid-2020), so each parser page also charts how coverage, speed, memory, recall, round-trip, and panic rate evolved across releases, down to the exact statements each release gained or lost, and the section below compares the families against one another over calendar time.").into_iter()}
@@ -656,7 +657,9 @@ fn chart_figure(id: &str, svg: &str, aria_label: &str, caption: &str, filename:
 #[component]
 pub fn DialectView(dir: String) -> Element {
     let b = bundle();
-    let Some(d) = b.dialects.iter().find(|x| x.dir_name == dir) else {
+    let mut common = use_signal(|| false);
+    let pair = use_signal(|| None::<(String, String)>);
+    let Some(full) = b.dialects.iter().find(|x| x.dir_name == dir) else {
         return rsx! {
             section { class: "intro",
                 h1 { "Unknown dialect" }
@@ -665,10 +668,33 @@ pub fn DialectView(dir: String) -> Element {
             }
         };
     };
+    // The common-subset toggle swaps in the head-to-head timing and memory
+    // distributions; everything else reads the full dialect.
+    let has_common = !full.perf_common.is_empty();
+    let on_common = has_common && common();
+    let common_label = format!(
+        "common subset ({})",
+        count_noun(
+            full.perf_common.first().map_or(0, |p| p.n_accepted),
+            "statement"
+        )
+    );
+    let swapped;
+    let d = if on_common {
+        swapped = DialectData {
+            perf: full.perf_common.clone(),
+            memory: full.memory_common.clone(),
+            ..full.clone()
+        };
+        &swapped
+    } else {
+        full
+    };
     let br = brand(&d.dir_name);
     let total = commas(d.valid_total + d.invalid_total);
     let ecdf = viz::ecdf_svg(d, 760, 420);
     let boxp = viz::box_svg(d, 760, 420);
+    let scaling = viz::scaling_svg(d, 760, 420);
 
     rsx! {
         section {
@@ -701,6 +727,21 @@ pub fn DialectView(dir: String) -> Element {
                 Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaChartLine }
                 "Per-statement parse time"
             }
+            if has_common {
+                div { class: "version-picker",
+                    span { class: "version-picker-label", "statements" }
+                    button {
+                        class: if on_common { "version-btn" } else { "version-btn active" },
+                        onclick: move |_| common.set(false),
+                        "each parser's accepted set"
+                    }
+                    button {
+                        class: if on_common { "version-btn active" } else { "version-btn" },
+                        onclick: move |_| common.set(true),
+                        "{common_label}"
+                    }
+                }
+            }
             div { class: "charts",
                 {chart_figure(
                     &format!("chart-{}-ecdf", d.dir_name),
@@ -716,12 +757,28 @@ pub fn DialectView(dir: String) -> Element {
                     "Parse-time spread, one box per parser. Box: quartiles, median line, whiskers: 10th to 90th (log scale).",
                     &format!("{}-boxplot", d.dir_name),
                 )}
+                if d.perf.iter().any(|p| p.scaling.is_some()) {
+                    {chart_figure(
+                        &format!("chart-{}-scaling", d.dir_name),
+                        &scaling,
+                        &format!("Median parse time against statement length in bytes for {}, one line per parser.", d.display_name),
+                        "Parse time against statement size, one line per parser (log-log). A steeper line grows faster than the statement: the legend's exponent is about 1 for linear scaling and above 1 for super-linear.",
+                        &format!("{}-scaling", d.dir_name),
+                    )}
+                }
             }
         }
 
         {perf_table(d)}
+        {lex_table(d)}
+        {edit_table(d)}
         {memory_table(d)}
         {correctness_table(d)}
+        {kind_table(d)}
+        {placeholder_table(d)}
+        {construct_heatmap(d)}
+        {disagreement_matrix(d, pair)}
+        {recovery_table(d)}
 
         Link { class: "back", to: Route::Overview {},
             Icon { width: 14, height: 14, fill: "currentColor".to_string(), icon: FaArrowLeftLong }
@@ -1430,7 +1487,8 @@ fn parser_memory_section(b: &viz::Bundle, parser: &str) -> Element {
 /// Render one failing-statement preview: the highlighted SQL, the copy button,
 /// the per-row feedback buttons (reference dialects only), and, when the
 /// statement matches a contentious rule, an intentional-divergence badge with a
-/// dispute button in place of the "propose" button.
+/// dispute button in place of the "propose" button. A minimized reproducer,
+/// where one was found, goes under the error message.
 #[allow(clippy::too_many_arguments)]
 fn fail_preview_row(
     b: &viz::Bundle,
@@ -1444,6 +1502,7 @@ fn fail_preview_row(
 ) -> Element {
     let reason = f.preview_reasons.get(i).map(String::as_str).unwrap_or("");
     let sql = f.preview_sql.get(i).map(String::as_str);
+    let minimized = f.preview_minimized.get(i).and_then(|m| m.as_deref());
     // The contentious rule this statement matched, if any, resolved against the
     // exported rule metadata.
     let rule = f
@@ -1451,6 +1510,7 @@ fn fail_preview_row(
         .get(i)
         .and_then(|t| t.as_deref())
         .and_then(|id| b.contentious_rules.iter().find(|r| r.id == id));
+    let gap = known_gap(b, f.preview_issues.get(i).and_then(|t| t.as_deref()));
     rsx! {
         div { class: "fail-code-wrap", key: "{i}",
             div { class: "fail-actions",
@@ -1509,24 +1569,99 @@ fn fail_preview_row(
                     "Intentional divergence: {rule.title} ({rule.category})"
                 }
             }
+            if let Some(g) = gap {
+                {gap_badge(g)}
+            }
             if !reason.is_empty() {
                 div { class: "fail-reason", "{reason}" }
             }
+            if let Some(min) = minimized {
+                div { class: "fail-min", title: "A shorter statement that still fails the same way, found by delta debugging",
+                    span { class: "fail-min-label", "Minimized: " }
+                    code { "{min}" }
+                }
+            }
+        }
+    }
+}
+
+/// The exported [`viz::KnownGap`] with id `id`, if any.
+fn known_gap<'a>(b: &'a viz::Bundle, id: Option<&str>) -> Option<&'a viz::KnownGap> {
+    id.and_then(|id| b.known_gaps.iter().find(|g| g.id == id))
+}
+
+/// A link to the upstream report a rejection is known under. An entry fixed
+/// in the benchmarked build or earlier is flagged, since the statement should
+/// no longer fail.
+fn gap_badge(g: &viz::KnownGap) -> Element {
+    let status = match (g.status.as_str(), g.fixed_in.as_deref()) {
+        ("fixed", Some(v)) => format!("fixed in {v}"),
+        (s, _) => s.to_string(),
+    };
+    let class = if g.fixed_by_current {
+        "gap-badge gap-stale"
+    } else {
+        "gap-badge"
+    };
+    rsx! {
+        div { class: "{class}",
+            "Known issue: "
+            a { class: "inline-link", href: "{g.url}", target: "_blank", rel: "noopener noreferrer", "{g.title}" }
+            " ({status})"
+            if g.fixed_by_current {
+                " but still fails in the benchmarked version"
+            }
+        }
+    }
+}
+
+/// The largest groups of one dialect's rejections, each as "N failures:" and
+/// the normalized error, with the shortest member statement under it.
+fn fail_clusters(b: &viz::Bundle, c: &viz::ParserFailureClusters) -> Element {
+    if c.clusters.is_empty() {
+        return rsx! {};
+    }
+    rsx! {
+        ul { class: "fail-clusters",
+            for (i , cl) in c.clusters.iter().enumerate() {
+                li { key: "{i}",
+                    span { class: "fail-cluster-count", "{count_noun(cl.count, \"failure\")}:" }
+                    " "
+                    code { title: "{cl.exemplar_reason}", "{cl.message}" }
+                    if !cl.head.is_empty() {
+                        span { class: "fail-cluster-head", title: "{cl.shape}", " in {cl.head}" }
+                    }
+                    div { class: "fail-min",
+                        span { class: "fail-min-label", "e.g. " }
+                        code { "{cl.exemplar}" }
+                    }
+                    if let Some(g) = known_gap(b, cl.issue.as_deref()) {
+                        {gap_badge(g)}
+                    }
+                }
+            }
         }
     }
 }
 
 fn failures_section(b: &viz::Bundle, parser: &str) -> Element {
-    // Gather (dialect display name, failures) for dialects where this parser
+    // Gather (dialect display name, failures, clusters) for dialects where this parser
     // has at least one rejected statement.
-    let entries: Vec<(&str, bool, &viz::ParserFailures)> = b
+    type Entry<'a> = (
+        &'a str,
+        bool,
+        &'a viz::ParserFailures,
+        Option<&'a viz::ParserFailureClusters>,
+    );
+    let entries: Vec<Entry> = b
         .dialects
         .iter()
         .filter_map(|d| {
+            let clusters = d.failure_clusters.iter().find(|c| c.parser == parser);
             d.failures
                 .iter()
                 .find(|f| f.parser == parser && f.rejected_total > 0)
-                .map(|f| (d.display_name.as_str(), d.has_reference, f))
+                .map(|f| (d.display_name.as_str(), d.has_reference, f, clusters))
         })
         .collect();
     if entries.is_empty() {
@@ -1537,7 +1672,7 @@ fn failures_section(b: &viz::Bundle, parser: &str) -> Element {
     // build a legend explaining the badges below.
     let tagged: std::collections::BTreeSet<&str> = entries
         .iter()
-        .flat_map(|(_, _, f)| f.preview_tags.iter().filter_map(Option::as_deref))
+        .flat_map(|(_, _, f, _)| f.preview_tags.iter().filter_map(Option::as_deref))
         .collect();
     let legend_rules: Vec<&viz::RuleMeta> = b
         .contentious_rules
@@ -1552,7 +1687,7 @@ fn failures_section(b: &viz::Bundle, parser: &str) -> Element {
                 "Failing statements"
             }
             p { class: "fail-intro",
-                "Statements this parser was expected to accept but rejected. Each dialect links to the full set (capped at 1,000) as a compressed TSV."
+                "Statements this parser was expected to accept but rejected, with the most common errors first. Each dialect links to the full set (capped at 1,000) as a compressed TSV."
             }
             if !legend_rules.is_empty() {
                 div { class: "contentious-legend",
@@ -1576,12 +1711,22 @@ fn failures_section(b: &viz::Bundle, parser: &str) -> Element {
                     }
                 }
             }
-            for (di , (dialect , has_ref , f)) in entries.into_iter().enumerate() {
+            for (di , (dialect , has_ref , f , clusters)) in entries.into_iter().enumerate() {
                 div { class: "fail-dialect", key: "{dialect}",
                     div { class: "fail-head",
                         span { class: "fail-title",
                             strong { "{dialect}" }
                             span { class: "fail-count", "{commas(f.rejected_total)} of {commas(f.expected_total)} rejected" }
+                            if f.known_total > 0 {
+                                span { class: "fail-count",
+                                    " · {commas(f.known_total)} known upstream, {commas(f.rejected_total - f.known_total)} untriaged"
+                                }
+                            }
+                            if f.stale_total > 0 {
+                                span { class: "fail-count gap-stale-count",
+                                    " · {commas(f.stale_total)} marked fixed but still failing"
+                                }
+                            }
                         }
                         if let Some(path) = &f.download {
                             a {
@@ -1593,6 +1738,9 @@ fn failures_section(b: &viz::Bundle, parser: &str) -> Element {
                             }
                         }
                     }
+                    if let Some(c) = clusters {
+                        {fail_clusters(b, c)}
+                    }
                     for (i , html) in f.preview_html.iter().enumerate() {
                         {fail_preview_row(b, di, i, dialect, has_ref, parser, f, html)}
                     }
@@ -2085,6 +2233,7 @@ fn parser_meta_pills(parser: &str) -> Element {
             {meta_flag(rsx! { Icon { width: 12, height: 12, fill: "currentColor".to_string(), icon: FaDna } }, "cargo mutants", if m.cargo_mutants { "yes".to_string() } else { "no".to_string() }, m.cargo_mutants, crate::metadata::cargo_mutants_description(m.cargo_mutants))}
             {feat.map_or_else(|| rsx! {}, panic_discipline_pill)}
             {empirical_panic_pill(panic)}
+            {crate::data::fuzz_totals(parser).map_or_else(|| rsx! {}, fuzz_panic_pill)}
             {failures_pill(crate::data::failure_totals(parser))}
             {feat.map_or_else(|| rsx! {}, |f| unsafe_pill(f, m.unsafe_note))}
             {depth.map_or_else(|| rsx! {}, depth_pill)}
@@ -2179,6 +2328,45 @@ fn empirical_panic_pill(totals: Option<(usize, usize)>) -> Element {
     )
 }
 
+/// Fuzz-failure pill: panics and hangs per million mutated inputs from
+/// `sqlbench fuzz`, with the distinct crashers behind them.
+fn fuzz_panic_pill(f: &viz::ParserFuzz) -> Element {
+    let ppm = f.failures_per_million.unwrap_or(0.0);
+    let failures = f.panics + f.hangs;
+    let (value, desc) = if failures == 0 {
+        (
+            "0".to_string(),
+            format!(
+                "Fuzzing: 0 panics or hangs across {} mutated corpus statements (tokens \
+                 deleted, duplicated, swapped, spliced between statements, or nested deep \
+                 in parentheses).",
+                commas(f.inputs)
+            ),
+        )
+    } else {
+        (
+            format!("{ppm:.0} / 1M"),
+            format!(
+                "Fuzzing: {} of {} mutated corpus statements made the parser panic (caught) \
+                 and {} made it hang (no result within the fuzzer's timeout), {ppm:.0} per \
+                 million inputs, from {} distinct crashers. The inputs are committed under \
+                 fuzz/crashers/, panics minimized.",
+                commas(f.panics),
+                commas(f.inputs),
+                commas(f.hangs),
+                f.crashers
+            ),
+        )
+    };
+    meta_flag(
+        rsx! { Icon { width: 12, height: 12, fill: "currentColor".to_string(), icon: FaBug } },
+        "fuzz failures",
+        value,
+        failures == 0,
+        &desc,
+    )
+}
+
 /// Failed-to-parse pill: how many statements the parser rejected that it was
 /// expected to accept, summed across every dialect. Neutral (informational):
 /// every parser misses some real-world SQL, so this is a coverage figure, not an
@@ -2420,6 +2608,43 @@ impl Cell {
             sub,
         }
     }
+    /// Error-position cell: the share of located errors that match the
+    /// reference's position, with a grey sub-line for how often the parser
+    /// locates its errors at all. The position share stays the sort key.
+    fn error_pos(position: Option<f64>, located: Option<f64>) -> Cell {
+        Cell {
+            text: fmt_pct(position),
+            num: position,
+            sub: located.map(|l| format!("{} located", fmt_pct(Some(l)))),
+        }
+    }
+    /// Splitter-recall cell, with a grey sub-line for the recall on compound
+    /// statements and, on MySQL, in `DELIMITER` blocks. Overall recall stays
+    /// the sort key.
+    fn split(m: &ParserMetrics) -> Cell {
+        let mut parts = Vec::new();
+        if let Some(c) = m.split_compound_recall_pct {
+            parts.push(format!("{} compound", fmt_pct(Some(c))));
+        }
+        if let Some(d) = m.split_delimiter_recall_pct {
+            parts.push(format!("{} DELIMITER", fmt_pct(Some(d))));
+        }
+        Cell {
+            text: fmt_pct(m.split_recall_pct),
+            num: m.split_recall_pct,
+            sub: (!parts.is_empty()).then(|| parts.join(", ")),
+        }
+    }
+    /// Statement-kind cell: recall over the kind's valid statements, with a
+    /// grey sub-line for the median parse time of the ones accepted. Recall
+    /// stays the sort key.
+    fn kind(recall: Option<f64>, median_ns: Option<f64>) -> Cell {
+        Cell {
+            text: fmt_pct(recall),
+            num: recall,
+            sub: median_ns.map(|ns| format!("{} ns median", commas(ns as usize))),
+        }
+    }
     /// Nanosecond cell from an optional value (comma-grouped, "N/A" if missing).
     fn ns(v: Option<f64>) -> Cell {
         Cell {
@@ -2518,6 +2743,9 @@ fn render_head(head: &Head) -> Element {
             th { scope: "row", class: "pname",
                 span { class: "dot", style: "background: {parser_hex(p)}", "aria-hidden": "true" }
                 Link { to: Route::ParserView { name: slug(p) }, "{p}" }
+                if crate::data::is_out_of_process(p) {
+                    span { class: "oop", title: "Runs out of process through the subprocess adapter protocol: graded, but not timed.", "subprocess" }
+                }
             }
         },
         Head::Dialect { dir, name } => rsx! {
@@ -2549,9 +2777,36 @@ fn col_help(name: &str) -> Option<&'static str> {
         "accept / recall" => "Recall where a reference parser exists (agreement with it on valid statements), otherwise the plain acceptance rate. Higher is better.",
         "false pos" => "False positives: of the statements the reference parser rejects as invalid, the share this parser wrongly accepted. Lower is better.",
         "round-trip" | "RT %" => "Round-trip rate: of the statements it accepted, the share that print back to SQL and re-parse unchanged. Shown as n/a when the parser cannot print. Higher is better.",
+        "AST agree" => "AST agreement: of the valid statements this parser accepted, the share whose parse tree matches the reference parser's once both are normalized (statement kind, tables, select list, joins, and predicates, with operator precedence explicit). Catches a parser that accepts a statement but understands it wrongly. n/a for the reference parser itself and for parsers that expose no tree. Higher is better.",
+        "error pos" => "Error position: of the invalid statements this parser rejected with a located error, the share whose reported position is within about a token (8 bytes) of where the reference parser places the error. The grey line is the share of rejections that carry a location at all (a byte offset or a line and column). n/a for the reference parser itself. Higher is better.",
+        "spans" => "Span accuracy: of the identifier and literal spans in this parser's trees of accepted valid statements, the share that slice back to the same token in the original SQL (ignoring case and surrounding quotes). Linters and language servers underline whatever the span covers. n/a for parsers that attach no spans to their AST. Higher is better.",
         "missed %" => "Missed: the share of statements the parser was expected to accept but did not. On reference dialects this is one minus recall, elsewhere the unaccepted fraction. Lower is better.",
+        "ns/byte" => "Median parse time divided by the statement's length in bytes, so parsers that accept different statements compare on equal terms. Lower is faster.",
+        "size exp" => "Size exponent: the slope of log parse time against log statement length. About 1 means time grows in proportion to the statement, above 1 that long statements cost disproportionately more.",
         "median ns" => "Median parse time per accepted statement, in nanoseconds: half of statements parse faster than this.",
         "p90 ns" => "90th-percentile parse time per accepted statement, in nanoseconds: nine in ten statements parse faster than this.",
+        "recovered" => "Share of the valid statements in scripts with one invalid statement planted that the parser still returns, each whole and on its own. N/A for parsers that fail the whole script on the first error.",
+        "gave up" => "Share of those scripts from which the parser returned no valid statement at all.",
+        "$1" => "Numbered dollar placeholder, as in PostgreSQL and the Rust Postgres drivers: share of the templates this parser accepts with a literal that it also accepts with $1 in its place.",
+        "?" => "Positional question-mark placeholder, as in JDBC, ODBC, MySQL and SQLite drivers.",
+        "?1" => "Numbered question-mark placeholder, as in SQLite.",
+        ":name" => "Named colon placeholder, as in Oracle, SQLite and most ORMs.",
+        "@name" => "Named at-sign placeholder, as in SQL Server and SQLite.",
+        "%s" => "Python DB-API format-style placeholder, as in psycopg and MySQLdb.",
+        "${var}" => "Template variable, as in Hive and Spark variable substitution and MyBatis.",
+        "split prec" => "Statement-splitter precision: of the boundaries between the statements this parser reports in random multi-statement scripts (built from statements it accepts alone), the share that fall on a real terminator. A statement cut in two adds a false boundary. n/a for parsers that only count statements or stream them without positions. Higher is better.",
+        "split recall" => "Statement-splitter recall: the share of the scripts' real terminators this parser's statement boundaries find. Two statements merged into one miss a boundary. Underneath: recall on scripts mixing in compound statements (trigger and function bodies, BEGIN ... END blocks, whose inner semicolons a naive splitter cuts on) and, on MySQL, on the same statements in a DELIMITER // block. Higher is better.",
+        "lex p50" => "Median time the parser's standalone lexer takes to tokenize one accepted statement, in nanoseconds.",
+        "lex p90" => "90th-percentile lex time: nine in ten statements tokenize faster than this.",
+        "parse p50" => "Median full parse time of the same statements (the speed table's median), for comparison.",
+        "lex share" => "Median lex time as a share of median parse time: how much of a parse is spent tokenizing.",
+        "edit p50" => "Median time to reparse a statement after a one-token edit, over every edit kind, in nanoseconds.",
+        "edit p90" => "90th-percentile edit-reparse time: nine in ten edits reparse faster than this.",
+        "edit mean" => "Mean edit-reparse time over every edit kind, in nanoseconds.",
+        "rename p50" => "Median reparse time after the statement's last plain identifier is renamed (`t` to `t_1`).",
+        "literal p50" => "Median reparse time after the statement's last number or string literal gains a character.",
+        "predicate p50" => "Median reparse time after a `1 = 1 AND` conjunct is inserted after the statement's last WHERE.",
+        "reparse" => "incremental: the parser reuses the previous tree and reparses only what the edit touched. cold: the edited text is parsed from scratch.",
         "peak p50" => "Median peak live memory while parsing one statement: the working-set high-water mark that half of statements stay under.",
         "peak p90" => "90th-percentile peak live memory per statement: nine in ten statements stay under this high-water mark.",
         "retained p50" => "Median retained memory per statement: the bytes the produced AST (plus the scaffolding it keeps alive) holds after parsing. Half of statements retain less.",
@@ -2693,6 +2948,8 @@ fn perf_table(d: &DialectData) -> Element {
         "batch ns/stmt",
         "missed %",
         "RT %",
+        "ns/byte",
+        "size exp",
     ]
     .iter()
     .map(ToString::to_string)
@@ -2711,6 +2968,20 @@ fn perf_table(d: &DialectData) -> Element {
                 Cell::ns(batch_of(d, &p.parser).and_then(|x| x.ns_per_stmt)),
                 Cell::with(missed_pct(d, p), missed_val(d, p)),
                 Cell::pct(p.roundtrip_pct),
+                Cell::with(
+                    p.scaling.as_ref().map_or_else(
+                        || "N/A".to_string(),
+                        |s| format!("{:.1}", s.ns_per_byte.median),
+                    ),
+                    p.scaling.as_ref().map(|s| s.ns_per_byte.median),
+                ),
+                Cell::with(
+                    p.scaling
+                        .as_ref()
+                        .and_then(|s| s.exponent)
+                        .map_or_else(|| "N/A".to_string(), |e| format!("{e:.2}")),
+                    p.scaling.as_ref().and_then(|s| s.exponent),
+                ),
             ],
         })
         .collect();
@@ -2721,7 +2992,7 @@ fn perf_table(d: &DialectData) -> Element {
                 "Speed"
             }
             p { class: "table-cap",
-                "One row per parser. \"median ns\" and \"p90 ns\" are per-statement parse times in nanoseconds (lower is faster). \"mean ns\" is the per-statement average. \"batch ok%\" is the share of 200 random 128-statement scripts (built from statements the parser accepts) that reparse to the exact count, and \"batch ns/stmt\" is the per-statement time over the scripts that did, so comparing it to the mean shows what bulk parsing saves or costs. \"missed %\" is the share of expected statements not accepted, \"RT %\" the round-trip rate, the share of accepted statements that re-parse unchanged."
+                "One row per parser. \"median ns\" and \"p90 ns\" are per-statement parse times in nanoseconds (lower is faster). \"mean ns\" is the per-statement average. \"batch ok%\" is the share of 200 random 128-statement scripts (built from statements the parser accepts) that reparse to the exact count, and \"batch ns/stmt\" is the per-statement time over the scripts that did, so comparing it to the mean shows what bulk parsing saves or costs. \"missed %\" is the share of expected statements not accepted, \"RT %\" the round-trip rate, the share of accepted statements that re-parse unchanged. \"ns/byte\" is the median parse time per byte of statement, which does not reward a parser for accepting only short statements, and \"size exp\" is how time grows with statement length (about 1 is linear, above 1 super-linear)."
             }
             SortTable {
                 caption: format!("Per-parser parse time in nanoseconds for {}", d.display_name),
@@ -2734,6 +3005,133 @@ fn perf_table(d: &DialectData) -> Element {
     }
 }
 
+fn lex_table(d: &DialectData) -> Element {
+    if d.lex.is_empty() {
+        return rsx! {};
+    }
+    let columns = ["lex p50", "lex p90", "parse p50", "lex share"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let lexes: Vec<&viz::ParserLex> = display_order(d)
+        .iter()
+        .filter_map(|name| d.lex.iter().find(|l| l.parser.as_str() == *name))
+        .collect();
+    let rows = lexes
+        .iter()
+        .map(|l| {
+            let parse = d
+                .perf
+                .iter()
+                .find(|p| p.parser == l.parser)
+                .map(|p| p.median);
+            let share = parse
+                .filter(|&p| p > 0.0)
+                .map(|p| (100.0 * l.time.median / p).min(100.0));
+            Row {
+                key: l.parser.clone(),
+                head: Head::Parser(l.parser.clone()),
+                cells: vec![
+                    Cell::ns(Some(l.time.median)),
+                    Cell::ns(Some(l.time.p90)),
+                    Cell::ns(parse),
+                    Cell::pct(share),
+                ],
+            }
+        })
+        .collect();
+    let lines: Vec<viz::Line> = lexes
+        .iter()
+        .map(|l| viz::mem_line(l.parser.clone(), parser_rgb(&l.parser), &l.time))
+        .collect();
+    let title = format!("{} lex time", d.display_name);
+    let ecdf = viz::ecdf_lines(&title, &lines, 760, 460, "ns / statement");
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaCode }
+                "Lexing"
+            }
+            p { class: "table-cap",
+                "Nanoseconds per accepted statement spent in the parser's standalone lexer alone, for the parsers that expose one (sqlparser-rs, databend-common-ast, sqlite3-parser, turso_parser). It is timed on the same statements and with the same harness as the parse time, so \"lex share\" (median lex time over median parse time) splits the parse into lexing and everything after it. Useful when only the token stream is needed, as for syntax highlighting or redaction."
+            }
+            div { class: "charts",
+                {chart_figure(&format!("chart-{}-lex-ecdf", d.dir_name), &ecdf, &format!("Empirical CDF of lex time for {}, one curve per parser.", d.display_name), "Lex-only time per statement, one curve per parser. Further left is faster (log scale).", &format!("{}-lex-ecdf", d.dir_name))}
+            }
+            SortTable {
+                caption: format!("Per-parser lex time in ns for {}", d.display_name),
+                corner: "parser".to_string(),
+                columns,
+                rows,
+                footer: None,
+            }
+        }
+    }
+}
+
+fn edit_table(d: &DialectData) -> Element {
+    if d.edit.is_empty() {
+        return rsx! {};
+    }
+    let kinds = ["rename", "literal", "predicate"];
+    let columns = ["edit p50", "edit p90", "edit mean"]
+        .iter()
+        .map(ToString::to_string)
+        .chain(kinds.iter().map(|k| format!("{k} p50")))
+        .chain(std::iter::once("reparse".to_string()))
+        .collect();
+    let edits: Vec<&viz::ParserEdit> = display_order(d)
+        .iter()
+        .filter_map(|name| d.edit.iter().find(|e| e.parser.as_str() == *name))
+        .collect();
+    let rows = edits
+        .iter()
+        .map(|e| {
+            let kind = |k: &str| e.kinds.iter().find(|t| t.kind == k).map(|t| t.median);
+            let mut cells = vec![
+                Cell::ns(Some(e.time.median)),
+                Cell::ns(Some(e.time.p90)),
+                Cell::ns(Some(e.time.mean)),
+            ];
+            cells.extend(kinds.iter().map(|k| Cell::ns(kind(k))));
+            let mode = if e.incremental { "incremental" } else { "cold" };
+            cells.push(Cell::with(mode.to_string(), None));
+            Row {
+                key: e.parser.clone(),
+                head: Head::Parser(e.parser.clone()),
+                cells,
+            }
+        })
+        .collect();
+    let lines: Vec<viz::Line> = edits
+        .iter()
+        .map(|e| viz::mem_line(e.parser.clone(), parser_rgb(&e.parser), &e.time))
+        .collect();
+    let title = format!("{} edit reparse", d.display_name);
+    let ecdf = viz::ecdf_lines(&title, &lines, 760, 460, "ns / edit");
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaArrowsRotate }
+                "Edit reparse"
+            }
+            p { class: "table-cap",
+                "Nanoseconds to reparse a statement after a one-token edit, the workload of an editor or language server. Each sampled accepted statement gets up to three edits: an identifier renamed, a literal lengthened, and a `1 = 1 AND` conjunct added after WHERE. \"incremental\" parsers reparse against the previous tree (only tree-sitter has such an API); \"cold\" ones parse the edited text from scratch, so their numbers track the speed table."
+            }
+            div { class: "charts",
+                {chart_figure(&format!("chart-{}-edit-ecdf", d.dir_name), &ecdf, &format!("Empirical CDF of edit-reparse time for {}, one curve per parser.", d.display_name), "Reparse time per edit over every edit kind, one curve per parser. Further left is faster (log scale).", &format!("{}-edit-reparse-ecdf", d.dir_name))}
+            }
+            SortTable {
+                caption: format!("Per-parser edit-reparse time in ns for {}", d.display_name),
+                corner: "parser".to_string(),
+                columns,
+                rows,
+                footer: None,
+            }
+        }
+    }
+}
+
 fn memory_table(d: &DialectData) -> Element {
     if d.memory.is_empty() {
         return rsx! {};
@@ -2815,18 +3213,368 @@ fn memory_table(d: &DialectData) -> Element {
     }
 }
 
+fn kind_table(d: &DialectData) -> Element {
+    // Every parser is graded over the same statements, so any row's kinds are
+    // the dialect's.
+    let Some(kinds) = d
+        .correctness
+        .iter()
+        .map(|m| &m.by_kind)
+        .find(|k| !k.is_empty())
+    else {
+        return rsx! {};
+    };
+    let columns = kinds
+        .iter()
+        .map(|k| format!("{} ({})", k.kind, commas(k.valid)))
+        .collect();
+    let rows = display_order(d)
+        .iter()
+        .filter_map(|name| d.correctness.iter().find(|m| m.parser.as_str() == *name))
+        .map(|m| {
+            let perf = d.perf.iter().find(|p| p.parser == m.parser);
+            Row {
+                key: m.parser.clone(),
+                head: Head::Parser(m.parser.clone()),
+                cells: kinds
+                    .iter()
+                    .map(|k| {
+                        let recall = m
+                            .by_kind
+                            .iter()
+                            .find(|r| r.kind == k.kind)
+                            .and_then(|r| r.recall_pct);
+                        let median = perf
+                            .and_then(|p| p.by_kind.iter().find(|t| t.kind == k.kind))
+                            .map(|t| t.median);
+                        Cell::kind(recall, median)
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaTableCells }
+                "By statement kind"
+            }
+            p { class: "table-cap",
+                if d.has_reference {
+                    "Recall split by what the statement does, read from its leading keywords, so a team that only parses queries can set DDL gaps aside and one building migration tooling can look at them first. Column headers give the number of valid statements of each kind, and the grey line under each cell is the median parse time of the ones the parser accepts."
+                } else {
+                    "Acceptance split by what the statement does, read from its leading keywords, so a team that only parses queries can set DDL gaps aside and one building migration tooling can look at them first. Column headers give the number of statements of each kind, and the grey line under each cell is the median parse time of the ones the parser accepts."
+                }
+            }
+            SortTable {
+                caption: format!("Recall by statement kind for {}", d.display_name),
+                corner: "parser".to_string(),
+                columns,
+                rows,
+                footer: None,
+            }
+        }
+    }
+}
+
+fn placeholder_table(d: &DialectData) -> Element {
+    let Some(m) = &d.placeholders else {
+        return rsx! {};
+    };
+    let rows = display_order(d)
+        .iter()
+        .filter_map(|name| m.parsers.iter().find(|p| p.parser.as_str() == *name))
+        .map(|p| Row {
+            key: p.parser.clone(),
+            head: Head::Parser(p.parser.clone()),
+            cells: p.accepted_pct.iter().map(|&v| Cell::pct(v)).collect(),
+        })
+        .collect();
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaCode }
+                "Placeholders"
+            }
+            p { class: "table-cap",
+                "Which bind-parameter syntaxes each parser accepts. {commas(m.templates)} data statements from this dialect's corpus have their last literal replaced by a placeholder in each style, and each cell is the share of the templates the parser accepts with the literal that it still accepts with the placeholder. Parsers are probed as configured here, so qusql-parse (set to dollar arguments) shows only $1 even though other settings take ? or %s."
+            }
+            SortTable {
+                caption: format!("Placeholder acceptance by style for {}", d.display_name),
+                corner: "parser".to_string(),
+                columns: m.styles.clone(),
+                rows,
+                footer: None,
+            }
+        }
+    }
+}
+
+/// Background for a heat-map cell: red at 0% recall through amber to green at
+/// 100%, pale enough to keep the figure readable.
+fn heat_bg(pct: f64) -> String {
+    format!("hsl({:.0}, 70%, 88%)", pct.clamp(0.0, 100.0) * 1.2)
+}
+
+fn construct_heatmap(d: &DialectData) -> Element {
+    let Some(m) = &d.constructs else {
+        return rsx! {};
+    };
+    // (parser, [(construct id, recall, tooltip)]) in display order.
+    let rows: Vec<(String, Vec<(String, Option<f64>, String)>)> = display_order(d)
+        .into_iter()
+        .filter_map(|name| m.parsers.iter().find(|p| p.parser.as_str() == name))
+        .map(|p| {
+            let cells = m
+                .constructs
+                .iter()
+                .zip(&p.recall_pct)
+                .map(|(c, &v)| {
+                    let tip = format!(
+                        "{}: {} of {} using {}",
+                        p.parser,
+                        fmt_pct(v),
+                        count_noun(c.valid, "valid statement"),
+                        c.title
+                    );
+                    (c.id.clone(), v, tip)
+                })
+                .collect();
+            (p.parser.clone(), cells)
+        })
+        .collect();
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaLayerGroup }
+                "SQL constructs"
+            }
+            p { class: "table-cap",
+                "Recall per SQL feature. Statements the reference engine accepts are tagged with the constructs they use (a statement can carry several), and each cell is the share of a construct's valid statements the parser accepts. Column headers give the number of valid statements behind each construct; hover for what it matches."
+            }
+            div { class: "scroll",
+                table { class: "data heat",
+                    caption { class: "sr-only", "Recall per SQL construct for {d.display_name}" }
+                    thead {
+                        tr {
+                            th { scope: "col", class: "heat-corner", "parser" }
+                            for c in m.constructs.iter() {
+                                th { key: "{c.id}", scope: "col", title: "{c.description}",
+                                    span { class: "heat-title", "{c.title}" }
+                                    span { class: "heat-n", "{commas(c.valid)}" }
+                                }
+                            }
+                        }
+                    }
+                    tbody {
+                        for (parser, cells) in rows {
+                            tr { key: "{parser}",
+                                {render_head(&Head::Parser(parser.clone()))}
+                                for (id, v, tip) in cells {
+                                    if let Some(x) = v {
+                                        td { key: "{id}", style: "background: {heat_bg(x)}", title: "{tip}", "{fmt_pct(v)}" }
+                                    } else {
+                                        td { key: "{id}", class: "heat-na", title: "{tip}", "N/A" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Statements `a` accepts and `b` rejects, with samples, whichever way round
+/// the export stored the pair.
+fn one_sided<'a>(
+    d: &'a DialectData,
+    a: &str,
+    b: &str,
+) -> Option<(usize, &'a [viz::DisagreementSample])> {
+    d.disagreements.iter().find_map(|x| {
+        if x.a == a && x.b == b {
+            Some((x.only_a, x.samples_a.as_slice()))
+        } else if x.a == b && x.b == a {
+            Some((x.only_b, x.samples_b.as_slice()))
+        } else {
+            None
+        }
+    })
+}
+
+fn disagreement_matrix(d: &DialectData, mut pair: Signal<Option<(String, String)>>) -> Element {
+    if d.disagreements.is_empty() {
+        return rsx! {};
+    }
+    let order: Vec<String> = display_order(d)
+        .into_iter()
+        .filter(|p| {
+            d.disagreements
+                .iter()
+                .any(|x| x.a.as_str() == *p || x.b.as_str() == *p)
+        })
+        .map(ToString::to_string)
+        .collect();
+    let max = d
+        .disagreements
+        .iter()
+        .map(|x| x.only_a.max(x.only_b))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let selected = pair();
+    // (row parser, [(column parser, count, tint, selected)]) in display order.
+    let rows: Vec<(String, Vec<(String, Option<usize>, String, bool)>)> = order
+        .iter()
+        .map(|row| {
+            let cells = order
+                .iter()
+                .map(|col| {
+                    let n = one_sided(d, row, col).map(|(n, _)| n);
+                    let tint = heat_bg(100.0 - n.unwrap_or(0) as f64 * 100.0 / max as f64);
+                    let active = selected.as_ref() == Some(&(row.clone(), col.clone()));
+                    (col.clone(), n, tint, active)
+                })
+                .collect();
+            (row.clone(), cells)
+        })
+        .collect();
+    let drill = selected
+        .as_ref()
+        .and_then(|(a, b)| one_sided(d, a, b).map(|(n, samples)| (a, b, n, samples)));
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaTableCells }
+                "Parser disagreements"
+            }
+            p { class: "table-cap",
+                "For every pair of parsers, the statements the row parser accepts and the column parser rejects, over the whole corpus, valid or not. Moving from the column parser to the row parser, these are the statements that start parsing, and the mirrored cell those that stop. Redder cells disagree more. Click a cell for sample statements."
+            }
+            div { class: "scroll",
+                table { class: "data heat",
+                    caption { class: "sr-only", "Pairwise parser disagreements for {d.display_name}" }
+                    thead {
+                        tr {
+                            th { scope: "col", class: "heat-corner", "accepts / rejects" }
+                            for col in order.iter() {
+                                th { key: "{col}", scope: "col", span { class: "heat-title", "{col}" } }
+                            }
+                        }
+                    }
+                    tbody {
+                        for (row, cells) in rows {
+                            tr { key: "{row}",
+                                {render_head(&Head::Parser(row.clone()))}
+                                for (col, n, tint, active) in cells {
+                                    if let Some(n) = n {
+                                        td {
+                                            key: "{col}",
+                                            class: if active { "pair-cell active" } else { "pair-cell" },
+                                            style: "background: {tint}",
+                                            title: "{row} accepts, {col} rejects: {count_noun(n, \"statement\")}",
+                                            onclick: {
+                                                let (row, col) = (row.clone(), col.clone());
+                                                move |_| pair.set(Some((row.clone(), col.clone())))
+                                            },
+                                            "{commas(n)}"
+                                        }
+                                    } else {
+                                        td { key: "{col}", class: "heat-na", "" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some((a, b, n, samples)) = drill {
+                div { class: "pair-drill",
+                    h3 { "{a} accepts, {b} rejects: {count_noun(n, \"statement\")}" }
+                    if samples.is_empty() {
+                        p { class: "fail-intro", "None." }
+                    }
+                    for (i, x) in samples.iter().enumerate() {
+                        div { class: "fail-code-wrap", key: "{i}",
+                            pre { class: "fail-code", "{x.sql}" }
+                            if let Some(valid) = x.reference {
+                                div { class: "fail-reason",
+                                    if valid { "Reference engine: valid" } else { "Reference engine: invalid" }
+                                }
+                            }
+                        }
+                    }
+                    if n > samples.len() {
+                        p { class: "fail-intro", "Showing the first {samples.len()} in corpus order." }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn recovery_table(d: &DialectData) -> Element {
+    if d.recovery.is_empty() {
+        return rsx! {};
+    }
+    let rows = display_order(d)
+        .iter()
+        .filter_map(|name| d.recovery.iter().find(|r| r.parser.as_str() == *name))
+        .map(|r| Row {
+            key: r.parser.clone(),
+            head: Head::Parser(r.parser.clone()),
+            cells: vec![Cell::pct(r.recovered_pct), Cell::pct(r.gave_up_pct)],
+        })
+        .collect();
+    rsx! {
+        section { class: "block",
+            h2 {
+                Icon { width: 17, height: 17, fill: "currentColor".to_string(), class: "h2-ico".to_string(), icon: FaHeartPulse }
+                "Error recovery"
+            }
+            p { class: "table-cap",
+                "How much of a broken script each parser still returns. Scripts of 15 statements the parser accepts have one statement the reference engine rejects planted among them, and a valid statement counts only if the parser returns it whole and on its own. Parsers that fail the whole script on the first error show N/A."
+            }
+            SortTable {
+                caption: format!("Error recovery for {}", d.display_name),
+                corner: "parser".to_string(),
+                columns: vec!["recovered".to_string(), "gave up".to_string()],
+                rows,
+                footer: None,
+            }
+        }
+    }
+}
+
 fn correctness_table(d: &DialectData) -> Element {
     let reference = d.has_reference;
     let columns: Vec<String> = if reference {
-        ["recall", "false pos", "round-trip"]
-            .iter()
-            .map(ToString::to_string)
-            .collect()
+        [
+            "recall",
+            "false pos",
+            "round-trip",
+            "AST agree",
+            "error pos",
+            "spans",
+            "split prec",
+            "split recall",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect()
     } else {
-        ["accept", "round-trip"]
-            .iter()
-            .map(ToString::to_string)
-            .collect()
+        [
+            "accept",
+            "round-trip",
+            "spans",
+            "split prec",
+            "split recall",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect()
     };
     let rows = display_order(d)
         .iter()
@@ -2839,9 +3587,20 @@ fn correctness_table(d: &DialectData) -> Element {
                     Cell::recall(m.recall_pct, m.recall_excl_contentious_pct),
                     Cell::pct(m.false_positive_pct),
                     Cell::pct(m.roundtrip_pct),
+                    Cell::pct(m.ast_agreement_pct),
+                    Cell::error_pos(m.error_position_pct, m.error_location_pct),
+                    Cell::pct(m.span_accuracy_pct),
+                    Cell::pct(m.split_precision_pct),
+                    Cell::split(m),
                 ]
             } else {
-                vec![Cell::pct(m.accept_pct), Cell::pct(m.roundtrip_pct)]
+                vec![
+                    Cell::pct(m.accept_pct),
+                    Cell::pct(m.roundtrip_pct),
+                    Cell::pct(m.span_accuracy_pct),
+                    Cell::pct(m.split_precision_pct),
+                    Cell::split(m),
+                ]
             },
         })
         .collect();
@@ -2853,9 +3612,9 @@ fn correctness_table(d: &DialectData) -> Element {
             }
             p { class: "table-cap",
                 if reference {
-                    "One row per parser, graded against this dialect's reference parser. \"recall\" is the share of reference-valid statements accepted (agreement with the reference on valid SQL, not whether the parser runs). \"false pos\" is the share of invalid statements wrongly accepted (lower is better). \"round-trip\" is the share of accepted statements that re-parse unchanged."
+                    "One row per parser, graded against this dialect's reference parser. \"recall\" is the share of reference-valid statements accepted (agreement with the reference on valid SQL, not whether the parser runs). \"false pos\" is the share of invalid statements wrongly accepted (lower is better). \"round-trip\" is the share of accepted statements that re-parse unchanged. \"AST agree\" is the share of accepted valid statements whose tree matches the reference parser's, leaving out statements where either tree could not be normalized at all (n/a for the reference itself). \"error pos\" is the share of rejected invalid statements where the parser points at the same place as the reference, with how often it reports a location at all underneath. \"spans\" is the share of identifier and literal spans in the parser's trees that cover the right text (n/a for parsers without AST spans). \"split prec\" and \"split recall\" score the statement boundaries the parser reports in multi-statement scripts of statements it accepts, with recall on scripts of compound statements (triggers, function bodies, BEGIN ... END blocks) underneath (n/a for parsers that do not report statement positions)."
                 } else {
-                    "One row per parser. With no reference parser here, every statement counts as expected-valid. \"accept\" is the share of the corpus accepted, \"round-trip\" the share of accepted statements that re-parse unchanged."
+                    "One row per parser. With no reference parser here, every statement counts as expected-valid. \"accept\" is the share of the corpus accepted, \"round-trip\" the share of accepted statements that re-parse unchanged,, \"spans\" the share of identifier and literal spans in the parser's trees that cover the right text (n/a for parsers without AST spans), and \"split prec\" and \"split recall\" score the statement boundaries the parser reports in multi-statement scripts, with recall on scripts of compound statements underneath (n/a for parsers that do not report statement positions)."
                 }
             }
             SortTable {
@@ -7,7 +7,9 @@
 //! (the bundle is ~25x smaller compressed).
 
 use std::sync::OnceLock;
-use viz::{Bundle, DepthReport, DepthScan, FamilyHistory, FeatureScan, ParserFeatures};
+use viz::{
+    Bundle, DepthReport, DepthScan, FamilyHistory, FeatureScan, ParserFeatures, ParserFuzz,
+};
 
 /// The results bundle, zstd-compressed and embedded.
 static BUNDLE_RAW: &[u8] = include_bytes!("../assets/bench.json.zst");
@@ -82,6 +84,13 @@ pub fn parser_depth(parser: &str) -> Option<&'static DepthReport> {
     depth_scan().parsers.iter().find(|p| p.parser == parser)
 }
 
+/// Whether `parser` ran out of process through the subprocess adapter protocol
+/// (graded like the others, but with no timing or memory numbers).
+#[must_use]
+pub fn is_out_of_process(parser: &str) -> bool {
+    bundle().out_of_process.iter().any(|p| p == parser)
+}
+
 /// Aggregate empirical panic totals for one parser across every dialect it runs:
 /// `(panicked, attempted)`. The per-parser panic rate is `panicked / attempted`.
 /// Returns `None` if nothing was attempted (e.g. an older snapshot without the
@@ -98,6 +107,12 @@ pub fn panic_totals(parser: &str) -> Option<(usize, usize)> {
     (attempted > 0).then_some((panicked, attempted))
 }
 
+/// Mutation-fuzzing totals for one parser, if the snapshot has a fuzz run.
+#[must_use]
+pub fn fuzz_totals(parser: &str) -> Option<&'static ParserFuzz> {
+    bundle().fuzz.iter().find(|f| f.parser == parser)
+}
+
 /// Aggregate failed-to-parse totals for one parser across every dialect:
 /// `(rejected, expected)`. A statement counts as failed when the parser was
 /// expected to accept it (reference-valid statements in reference dialects, every
@@ -43,6 +43,7 @@ pub fn parser_blurb(name: &str) -> &'static str {
         "sqlglot-rust" => "[sqlglot-rust](https://crates.io/crates/sqlglot-rust) is a Rust parser and transpiler in the spirit of Python's [SQLGlot](https://github.com/tobymao/sqlglot), covering many dialects. It regenerates its AST as SQL, so it is graded for round-trip across the dialects it models.",
         "sqlite3-parser" => "[sqlite3-parser](https://crates.io/crates/sqlite3-parser) (also known as [lemon-rs](https://github.com/gwenn/lemon-rs)) is a pure-Rust streaming lexer and LALR parser reimplementing SQLite's grammar. It models only SQLite and provides the SQLite reference. It can reprint statements, so it is graded for round-trip on SQLite.",
         "turso_parser" => "[turso_parser](https://crates.io/crates/turso_parser) is the SQL front end of [Turso](https://github.com/tursodatabase/turso), a from-scratch Rust rewrite of SQLite (formerly Limbo). It pairs a lemon-generated token table with a hand-written recursive-descent parser for SQLite's grammar, so unlike sqlite3-parser's LALR tables the parsing is hand-rolled. It models only SQLite and can reprint statements, so it is graded for round-trip on SQLite.",
+        "tree-sitter-sql" => "[tree-sitter-sql](https://github.com/DerekStride/tree-sitter-sql) (crate [tree-sitter-sequel](https://crates.io/crates/tree-sitter-sequel)) is a SQL grammar for [tree-sitter](https://tree-sitter.github.io/), the parser generator editors such as Neovim, Helix and Zed use for highlighting and folding. The generated C parser never fails: it recovers by wrapping what it cannot place in ERROR nodes, so a statement counts as accepted here only when its tree has no ERROR or MISSING node. It has one dialect-agnostic grammar, graded on every dialect, has no printer, and is the only parser with an incremental API, timed on reparses after a one-token edit.",
         _ => "",
     }
 }